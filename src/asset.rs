@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+use log::*;
+
+use super::catalogue::*;
+use super::graph::*;
+use super::package::*;
+
+/// The datum key that lists, as a string or an array of strings, the files a node needs besides any held by its path data.
+pub const ASSETS_DATUM: &str = "assets";
+
+///
+/// Where the relative asset paths in the data of a node are looked up.
+/// The paths are relative to the graph version holding the node, so the roots are its version folder, then its graph folder, then its library folder.
+/// Any of them can be within a package.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct AssetContext {
+    /// The library holding the graph the node is in, if it is in one.
+    pub library: Option<uuid::Uuid>,
+    /// The folders to look for assets in, in order.
+    pub roots: Vec<PathBuf>,
+}
+
+impl AssetContext {
+    ///
+    /// The asset context of the nodes within a version of a graph.
+    /// Internal graphs hold no nodes of their own, and give an empty context.
+    ///
+    pub fn for_graph(catalogue: &Catalogue, graph: &GraphRef) -> Self {
+        let resolved = match catalogue.resolve_graph_ref(graph) {
+            Ok(resolved) => resolved,
+            Err(e) => {
+                warn!("no asset folders for graph {} : {}: {}", graph.uuid, graph.name, e);
+                return Default::default();
+            }
+        };
+        let lib = match resolved.library.and_then(|library| catalogue.libraries.get(&library)) {
+            Some(lib) => lib,
+            None => return Default::default(),
+        };
+        let graph_path = match lib.graphs.get(&resolved.uuid) {
+            Some(graph) if !graph.path.as_os_str().is_empty() => graph.path.clone(),
+            _ => return Default::default(),
+        };
+        let version = resolved.version.exact().unwrap_or_default();
+        Self {
+            library: Some(lib.info.uuid),
+            roots: vec![graph_path.join(format!("{}", version)), graph_path, lib.path.clone()],
+        }
+    }
+
+    ///
+    /// Finds the file an asset path points to.
+    /// Absolute paths are used as they are.
+    /// Relative paths are looked for in each root in order, and must be made only of plain names so they cannot reach outside of it.
+    /// The file found may be within a package, see `read_library_file`.
+    ///
+    pub fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        let asset = Path::new(path);
+        if asset.is_absolute() {
+            return match library_file_exists(asset) {
+                true => Ok(asset.to_path_buf()),
+                false => Err(format!("asset {} does not exist", path)),
+            };
+        }
+        if !asset.components().all(|component| matches!(component, std::path::Component::Normal(_))) {
+            return Err(format!("asset {} is not a plain relative path", path));
+        }
+        match self.roots.iter().map(|root| root.join(asset)).find(|candidate| library_file_exists(candidate)) {
+            Some(found) => Ok(found),
+            None => Err(format!(
+                "asset {} is not in any of {:?}",
+                path,
+                self.roots.iter().map(|root| root.display().to_string()).collect::<Vec<String>>()
+            )),
+        }
+    }
+}
+
+///
+/// Gives a path on disk for an asset, so that it can be opened by anything that reads files.
+/// Assets within packages are extracted to the temporary folder first, and extracted again only when the package changes.
+///
+pub fn asset_file(resolved: &Path) -> Result<PathBuf, String> {
+    let (package, inner) = match split_package_path(resolved) {
+        Some(split) => split,
+        None => return Ok(resolved.to_path_buf()),
+    };
+    if !stays_within(&inner) {
+        return Err(format!("asset {} is not within package {}", inner, package.display()));
+    }
+    let mut hasher = DefaultHasher::new();
+    package.hash(&mut hasher);
+    crate::library::stamp(&package).hash(&mut hasher);
+    let extracted = inner
+        .split('/')
+        .fold(std::env::temp_dir().join("proc_flow_assets").join(format!("{:016x}", hasher.finish())), |path, part| path.join(part));
+    if !extracted.is_file() {
+        let bytes = read_library_file(resolved)?;
+        if let Some(parent) = extracted.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+        }
+        // Each extraction writes its own file and renames it into place, so nothing ever opens half an asset.
+        let name = extracted.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let partial = extracted.with_file_name(format!("{}.{}.{}.partial", name, std::process::id(), uuid::Uuid::new_v4().to_simple()));
+        if let Err(e) = std::fs::write(&partial, bytes) {
+            let _ = std::fs::remove_file(&partial);
+            return Err(format!("could not extract asset {}: {}", partial.display(), e));
+        }
+        std::fs::rename(&partial, &extracted).map_err(|e| {
+            let _ = std::fs::remove_file(&partial);
+            format!("could not extract asset {}: {}", extracted.display(), e)
+        })?;
+    }
+    Ok(extracted)
+}
+
+///
+/// A file that a node within a graph version needs.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AssetDependency {
+    /// The node instance within the graph.
+    pub node: uuid::Uuid,
+    /// The datum key the path was found in.
+    pub key: String,
+    /// The path as the node's data holds it.
+    pub path: String,
+    /// Where the path points, if the file was found.
+    pub resolved: Option<PathBuf>,
+}
+
+///
+/// The asset paths held in the data of a node.
+/// These are the strings of the `ASSETS_DATUM` datum, and of any datum whose key is `path` or ends in `_path`.
+///
+pub fn node_asset_paths(node: &NodeInfo) -> Vec<(String, String)> {
+    let mut paths = Vec::new();
+    let mut keys: Vec<&String> = node.data.keys().collect();
+    keys.sort();
+    for key in keys {
+        if key != ASSETS_DATUM && key != "path" && !key.ends_with("_path") {
+            continue;
+        }
+        match &node.data[key] {
+            serde_json::Value::String(path) => paths.push((key.clone(), path.clone())),
+            serde_json::Value::Array(values) => {
+                for value in values.iter() {
+                    if let serde_json::Value::String(path) = value {
+                        paths.push((key.clone(), path.clone()));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    paths
+}
+
+///
+/// The files that the nodes within a graph version need, found or not.
+///
+pub fn graph_assets(catalogue: &Catalogue, graph: &GraphRef, version: &VersionInfo) -> Vec<AssetDependency> {
+    let context = AssetContext::for_graph(catalogue, graph);
+    let mut assets = Vec::new();
+    for node in version.nodes.iter() {
+        for (key, path) in node_asset_paths(node) {
+            let resolved = context.resolve(&path).ok();
+            assets.push(AssetDependency {
+                node: node.uuid,
+                key,
+                path,
+                resolved,
+            });
+        }
+    }
+    assets
+}
@@ -5,20 +5,188 @@ use std::path::PathBuf;
 
 use log::*;
 
+use super::asset::*;
 use super::graph::*;
+use super::index::*;
 use super::library::*;
 
-fn load_libraries() -> Vec<Library> {
-    let mut libs = Vec::new();
+/// The environment variable holding extra library search paths, separated like the `PATH` variable of the platform.
+pub const LIBRARY_PATH_VAR: &str = "PROC_FLOW_LIBRARY_PATH";
+
+/// The environment variable holding the path of a catalogue config file to use instead of the default one.
+pub const CONFIG_PATH_VAR: &str = "PROC_FLOW_CONFIG";
+
+///
+/// Where the catalogue looks for libraries.
+/// The internal library is always loaded, followed by every search path in order, followed by the default locations unless they are disabled.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CatalogueConfig {
+    /// Folders holding libraries, looked in before the default locations.
+    #[serde(default)]
+    pub search_paths: Vec<PathBuf>,
+    /// Whether to also look in `data` in the working directory and `ProcFlow/Libraries` in the documents folder.
+    #[serde(default = "default_use_default_paths")]
+    pub use_default_paths: bool,
+    /// Where to keep an index of the scanned libraries, so that only what changed is scanned at the next start.
+    /// Without one, every library is scanned every time.
+    #[serde(default)]
+    pub index: Option<PathBuf>,
+}
+
+fn default_use_default_paths() -> bool {
+    true
+}
+
+impl Default for CatalogueConfig {
+    fn default() -> Self {
+        CatalogueConfig {
+            search_paths: Vec::new(),
+            use_default_paths: true,
+            index: None,
+        }
+    }
+}
+
+impl CatalogueConfig {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Builds the config the way an application starts up: from the config file if there is one, followed by the paths in the environment.
+    /// The config file is the one named by `PROC_FLOW_CONFIG`, or else `ProcFlow/catalogue.json` in the config folder of the user.
+    /// Unless the file says otherwise, the index is kept at `ProcFlow/catalogue.index` in the cache folder of the user.
+    ///
+    pub fn load() -> Self {
+        let file = match std::env::var_os(CONFIG_PATH_VAR) {
+            Some(path) => Some(PathBuf::from(path)),
+            None => dirs::config_dir()
+                .map(|dir| dir.join("ProcFlow").join("catalogue.json"))
+                .filter(|path| path.is_file()),
+        };
+        let config = match file {
+            Some(file) => match CatalogueConfig::from_file(&file) {
+                Ok(config) => config,
+                Err(e) => {
+                    error!("{}", e);
+                    CatalogueConfig::new()
+                }
+            },
+            None => CatalogueConfig::new(),
+        };
+        match config.index {
+            Some(_) => config.with_env(),
+            None => config.with_env().with_index(CatalogueConfig::default_index()),
+        }
+    }
+
+    ///
+    /// Reads a config from a json file.
+    ///
+    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("could not read catalogue config {}: {}", path.display(), e))?;
+        serde_json::from_str(&text)
+            .map_err(|e| format!("could not parse catalogue config {}: {}", path.display(), e))
+    }
+
+    ///
+    /// Adds the paths in `PROC_FLOW_LIBRARY_PATH`, if it is set, after the current search paths.
+    ///
+    pub fn with_env(mut self) -> Self {
+        if let Some(paths) = std::env::var_os(LIBRARY_PATH_VAR) {
+            self.search_paths
+                .extend(std::env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()));
+        }
+        self
+    }
+
+    ///
+    /// Adds a search path after the current ones.
+    ///
+    pub fn with_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    ///
+    /// Adds a search path before the current ones, so its libraries are found first.
+    ///
+    pub fn with_first_path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.search_paths.insert(0, path.into());
+        self
+    }
+
+    ///
+    /// Keeps an index of the scanned libraries at the given file, if any.
+    ///
+    pub fn with_index(mut self, index: Option<PathBuf>) -> Self {
+        self.index = index;
+        self
+    }
+
+    ///
+    /// The default index file, which is `ProcFlow/catalogue.index` in the cache folder.
+    ///
+    pub fn default_index() -> Option<PathBuf> {
+        dirs::cache_dir().map(|dir| dir.join("ProcFlow").join("catalogue.index"))
+    }
+
+    ///
+    /// Stops the catalogue from looking in the default locations.
+    ///
+    pub fn without_default_paths(mut self) -> Self {
+        self.use_default_paths = false;
+        self
+    }
+
+    ///
+    /// The default locations, which are `data` in the working directory and `ProcFlow/Libraries` in the documents folder.
+    ///
+    pub fn default_paths() -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        match std::env::current_dir() {
+            Ok(cdir) => paths.push(cdir.join(PathBuf::from("data"))),
+            Err(e) => error!(
+                "could not get application built-in libraries at working directory: {}",
+                e
+            ),
+        }
+        match document_dir() {
+            Some(ddir) => {
+                paths.push(ddir.join(PathBuf::from("ProcFlow").join(PathBuf::from("Libraries"))))
+            }
+            _ => error!("could not get libraries at document directory"),
+        }
+        paths
+    }
+
+    ///
+    /// Every folder to look for libraries in, in the order they are looked in.
+    ///
+    pub fn paths(&self) -> Vec<PathBuf> {
+        let mut paths = self.search_paths.clone();
+        if self.use_default_paths {
+            paths.append(&mut CatalogueConfig::default_paths());
+        }
+        paths
+    }
+}
+
+fn internal_library() -> Library {
     let mut internal = Library {
         info: LibraryInfo {
             name: String::from("internal"),
             uuid: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").unwrap(),
             author: String::from("Proc Flow Internal"),
             format: 1,
+            version: 1,
+            dependencies: Vec::new(),
         },
         path: PathBuf::default(),
         graphs: std::collections::HashMap::new(),
+        loaded_versions: Default::default(),
     };
     for (info, versions) in super::nodes::register() {
         internal.graphs.insert(
@@ -37,93 +205,464 @@ fn load_libraries() -> Vec<Library> {
         );
     }
     info!("added internal libraries to catalogue");
-    let mut applibs = match std::env::current_dir() {
-        Ok(cdir) => get_libraries(cdir.join(PathBuf::from("data"))),
-        Err(e) => {
-            error!(
-                "could not get application built-in libraries at working directory: {}",
-                e
-            );
-            Vec::new()
-        }
+    internal
+}
+
+fn load_libraries(config: &CatalogueConfig) -> Vec<Library> {
+    let mut libs = vec![internal_library()];
+    let index = match &config.index {
+        Some(index) => LibraryIndex::load(index),
+        None => LibraryIndex::new(),
     };
-    info!("added application libraries to catalogue");
-    let mut doclibs = match document_dir() {
-        Some(ddir) => {
-            get_libraries(ddir.join(PathBuf::from("ProcFlow").join(PathBuf::from("Libraries"))))
-        }
-        _ => {
-            error!("could not get libraries at document directory");
-            Vec::new()
+    let mut next = LibraryIndex::new();
+    for path in config.paths() {
+        let mut found = index.scan(&path, &mut next);
+        info!("added {} libraries at {} to catalogue", found.len(), path.display());
+        libs.append(&mut found);
+    }
+    if let Some(index) = &config.index {
+        if let Err(e) = next.save(index) {
+            error!("{}", e);
         }
-    };
-    info!("added document libraries to catalogue");
-
-    libs.push(internal);
-    libs.append(&mut applibs);
-    libs.append(&mut doclibs);
+    }
     libs
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Catalogue {
     pub libraries: std::collections::HashMap<uuid::Uuid, Library>,
+    /// Where the libraries were loaded from.
+    pub config: CatalogueConfig,
 }
 
 impl Catalogue {
     pub fn new() -> Catalogue {
         Catalogue {
             libraries: Default::default(),
+            config: Default::default(),
         }
     }
 
     pub fn load_default_libraries(&mut self) {
+        self.load_libraries(CatalogueConfig::default());
+    }
+
+    ///
+    /// Replaces the libraries with the internal library and the libraries found in the search paths of a config.
+    /// A library found earlier wins over a later one with the same id.
+    ///
+    pub fn load_libraries(&mut self, config: CatalogueConfig) {
         self.libraries.clear();
-        for lib in load_libraries() {
+        for lib in load_libraries(&config) {
+            if self.libraries.contains_key(&lib.info.uuid) {
+                warn!(
+                    "library {} : {} at {} is hidden by a library found before it",
+                    lib.info.uuid,
+                    lib.info.name,
+                    lib.path.display()
+                );
+                continue;
+            }
             self.libraries.insert(lib.info.uuid, lib);
         }
+        self.config = config;
+        for problem in self.check_dependencies() {
+            warn!("{}", problem);
+        }
     }
 
-    pub fn get_graph_info(&self, id: uuid::Uuid) -> Option<GraphInfo> {
-        for lib in self.libraries.values() {
-            if let Some(graph) = lib.graphs.get(&id) {
-                return Some(graph.info.clone());
-            };
+    ///
+    /// Loads the libraries again after files in them changed, and gives the graphs the changed files belong to.
+    /// A changed `lib.json` or package counts for every graph in its library, and graphs that were added or removed count too.
+    ///
+    pub fn reload(&mut self, changed: &[PathBuf]) -> Vec<uuid::Uuid> {
+        let mut affected = self.graphs_at(changed);
+        let config = self.config.clone();
+        self.load_libraries(config);
+        affected.append(&mut self.graphs_at(changed));
+        affected.sort();
+        affected.dedup();
+        affected
+    }
+
+    ///
+    /// The graphs that any of the given files belong to.
+    ///
+    fn graphs_at(&self, files: &[PathBuf]) -> Vec<uuid::Uuid> {
+        let mut graphs = Vec::new();
+        for lib in self.libraries.values().filter(|lib| !lib.path.as_os_str().is_empty()) {
+            let lib_changed = files
+                .iter()
+                .any(|file| file.parent() == Some(lib.path.as_path()) || *file == lib.path);
+            for graph in lib.graphs.values().filter(|graph| !graph.path.as_os_str().is_empty()) {
+                if lib_changed || files.iter().any(|file| file.starts_with(&graph.path)) {
+                    graphs.push(graph.info.uuid);
+                }
+            }
+        }
+        graphs
+    }
+
+    ///
+    /// The libraries in a fixed order, so that looking a graph up gives the same answer every time.
+    ///
+    fn ordered_libraries(&self) -> Vec<&Library> {
+        let mut libs: Vec<&Library> = self.libraries.values().collect();
+        libs.sort_by_key(|lib| lib.info.uuid);
+        libs
+    }
+
+    ///
+    /// The libraries to look for a graph in, in the order they are looked in.
+    /// An explicit library is the only one looked in.
+    /// Otherwise the library the reference comes from is first, followed by the libraries it depends on in the order it lists them, the internal library, and every other library ordered by id.
+    ///
+    fn search_order(&self, library: Option<uuid::Uuid>, from: Option<uuid::Uuid>) -> Vec<&Library> {
+        if let Some(library) = library {
+            return self.libraries.get(&library).into_iter().collect();
+        }
+        let mut order: Vec<uuid::Uuid> = Vec::new();
+        if let Some(from_lib) = from.and_then(|from| self.libraries.get(&from)) {
+            order.push(from_lib.info.uuid);
+            order.extend(from_lib.info.dependencies.iter().map(|dependency| dependency.uuid));
         }
-        None
+        order.push(uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").unwrap());
+        order.extend(self.ordered_libraries().iter().map(|lib| lib.info.uuid));
+        let mut seen = std::collections::HashSet::new();
+        order
+            .into_iter()
+            .filter(|id| seen.insert(*id))
+            .filter_map(|id| self.libraries.get(&id))
+            .collect()
     }
 
+    ///
+    /// Finds the library holding a graph, see `search_order` for which library wins.
+    ///
+    fn find_graph(&self, id: uuid::Uuid, library: Option<uuid::Uuid>, from: Option<uuid::Uuid>) -> Option<(&Library, &LibraryGraphInfo)> {
+        self.search_order(library, from)
+            .into_iter()
+            .find_map(|lib| lib.graphs.get(&id).map(|graph| (lib, graph)))
+    }
+
+    pub fn get_graph_info(&self, id: uuid::Uuid) -> Option<GraphInfo> {
+        self.find_graph(id, None, None).map(|(_, graph)| graph.info.clone())
+    }
+
+    ///
+    /// Gets a reference to an exact version of a graph, if that version exists.
+    ///
     pub fn get_graph_ref(&self, id: uuid::Uuid, version: u64) -> Option<GraphRef> {
-        for lib in self.libraries.values() {
-            if let Some(graph) = lib.graphs.get(&id) {
-                return Some(GraphRef {
-                    name: graph.info.name.clone(),
-                    uuid: graph.info.uuid,
-                    library: Some(lib.info.uuid),
-                    version,
-                });
-            };
+        match self.find_graph(id, None, None) {
+            Some((lib, graph)) if get_graph_versions(graph).contains(&version) => Some(GraphRef {
+                name: graph.info.name.clone(),
+                uuid: graph.info.uuid,
+                library: Some(lib.info.uuid),
+                version: VersionSpec::Exact(version),
+            }),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Resolves a version specifier of a graph to the exact version it points to.
+    ///
+    pub fn resolve_version(&self, id: uuid::Uuid, spec: &VersionSpec) -> Result<u64, String> {
+        match self.find_graph(id, None, None) {
+            Some((lib, graph)) => resolve_graph_version(lib, graph, spec),
+            None => Err(format!("graph {} does not exist in the catalogue", id)),
+        }
+    }
+
+    ///
+    /// Resolves a graph reference to the library that has the graph and the exact version it points to.
+    ///
+    pub fn resolve_graph_ref(&self, graph_ref: &GraphRef) -> Result<GraphRef, String> {
+        self.resolve_graph_ref_from(graph_ref, None)
+    }
+
+    ///
+    /// Resolves a graph reference made by a graph in a library, so that a blank library means that one.
+    ///
+    pub fn resolve_graph_ref_from(&self, graph_ref: &GraphRef, from: Option<uuid::Uuid>) -> Result<GraphRef, String> {
+        let (lib, graph) = match (self.find_graph(graph_ref.uuid, graph_ref.library, from), graph_ref.library) {
+            (Some(found), _) => found,
+            (None, Some(library)) => return Err(format!("graph {} : {} does not exist in library {}", graph_ref.uuid, graph_ref.name, library)),
+            (None, None) => return Err(format!("graph {} : {} does not exist in the catalogue", graph_ref.uuid, graph_ref.name)),
+        };
+        let version = resolve_graph_version(lib, graph, &graph_ref.version)?;
+        Ok(GraphRef {
+            name: graph.info.name.clone(),
+            uuid: graph.info.uuid,
+            library: Some(lib.info.uuid),
+            version: VersionSpec::Exact(version),
+        })
+    }
+
+    ///
+    /// Lists the differences between the pins of two versions of a graph, to report breaking changes.
+    ///
+    pub fn compare_versions(&self, id: uuid::Uuid, old: u64, new: u64) -> Result<Vec<PinChange>, String> {
+        let old_info = self.get_graph_version(&GraphRef { name: String::new(), uuid: id, library: None, version: VersionSpec::Exact(old) });
+        let new_info = self.get_graph_version(&GraphRef { name: String::new(), uuid: id, library: None, version: VersionSpec::Exact(new) });
+        match (old_info, new_info) {
+            (Some(old_info), Some(new_info)) => Ok(pin_changes(&old_info, &new_info)),
+            (None, _) => Err(format!("graph {} version {} could not be read", id, old)),
+            (_, None) => Err(format!("graph {} version {} could not be read", id, new)),
         }
-        None
     }
 
     pub fn get_graph_version(&self, graph_ref: &GraphRef) -> Option<VersionInfo> {
-        for lib in self.libraries.values() {
-            if let Some(graph) =
-                get_graph_version_from_library(&lib, graph_ref.uuid, graph_ref.version)
-            {
-                return Some(graph.clone());
-            };
+        let (lib, graph) = self.find_graph(graph_ref.uuid, graph_ref.library, None)?;
+        match resolve_graph_version(lib, graph, &graph_ref.version) {
+            Ok(version) => get_graph_version_from_library(lib, graph_ref.uuid, version),
+            Err(e) => {
+                error!("could not resolve graph version: {}", e);
+                None
+            }
         }
-        None
     }
 
     pub fn has_graph_version(&self, graph_ref: &GraphRef) -> bool {
-        for lib in self.libraries.values() {
-            if has_graph_version_from_library(&lib, graph_ref.uuid, graph_ref.version) {
-                return true;
+        match self.find_graph(graph_ref.uuid, graph_ref.library, None) {
+            Some((lib, graph)) => match resolve_graph_version(lib, graph, &graph_ref.version) {
+                Ok(version) => graph.path.as_os_str().is_empty() || has_graph_version_from_library(lib, graph_ref.uuid, version),
+                Err(_) => false,
+            },
+            None => false,
+        }
+    }
+
+    ///
+    /// Checks that every library's dependencies are in the catalogue at a version they allow, and that no graph is in more than one library.
+    /// This only reads what is already loaded, so it is cheap enough to run whenever libraries load.
+    ///
+    pub fn check_dependencies(&self) -> Vec<CatalogueProblem> {
+        let mut problems = Vec::new();
+        for lib in self.ordered_libraries() {
+            for dependency in lib.info.dependencies.iter() {
+                match self.libraries.get(&dependency.uuid) {
+                    Some(found) if !dependency.allows(found.info.version) => problems.push(CatalogueProblem::IncompatibleDependency {
+                        library: lib.info.uuid,
+                        dependency: dependency.clone(),
+                        found: found.info.version,
+                    }),
+                    Some(_) => {}
+                    None => problems.push(CatalogueProblem::MissingDependency {
+                        library: lib.info.uuid,
+                        dependency: dependency.clone(),
+                    }),
+                }
+            }
+        }
+        let mut holders: std::collections::BTreeMap<uuid::Uuid, Vec<(&Library, &LibraryGraphInfo)>> = std::collections::BTreeMap::new();
+        for lib in self.ordered_libraries() {
+            for graph in lib.graphs.values() {
+                holders.entry(graph.info.uuid).or_insert_with(Vec::new).push((lib, graph));
+            }
+        }
+        for (graph, holders) in holders.into_iter().filter(|(_, holders)| holders.len() > 1) {
+            let libraries = holders.iter().map(|(lib, _)| lib.info.uuid).collect();
+            let (_, first) = holders[0];
+            let same = holders
+                .iter()
+                .all(|(_, other)| other.info.name == first.info.name && get_graph_versions(other) == get_graph_versions(first));
+            problems.push(match same {
+                true => CatalogueProblem::DuplicateGraph { graph, libraries },
+                false => CatalogueProblem::ConflictingGraph { graph, libraries },
+            });
+        }
+        problems
+    }
+
+    ///
+    /// Lists the files that the nodes within a version of a graph need, and where each was found.
+    ///
+    pub fn graph_assets(&self, graph_ref: &GraphRef) -> Result<Vec<AssetDependency>, String> {
+        let resolved = self.resolve_graph_ref(graph_ref)?;
+        match self.get_graph_version(&resolved) {
+            Some(version) => Ok(graph_assets(self, &resolved, &version)),
+            None => Err(format!("graph {} : {} version {} could not be read", resolved.uuid, resolved.name, resolved.version)),
+        }
+    }
+
+    ///
+    /// Checks everything `check_dependencies` does, and also reads every version of every graph to check that the graphs its nodes use can be found, in the library itself or one it depends on.
+    /// The assets its nodes use must be found within the library too, so that packing the library takes them along.
+    ///
+    pub fn check_libraries(&self) -> Vec<CatalogueProblem> {
+        let mut problems = self.check_dependencies();
+        let internal_lib = uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").unwrap();
+        for lib in self.ordered_libraries() {
+            let mut graphs: Vec<&LibraryGraphInfo> = lib.graphs.values().filter(|graph| !graph.path.as_os_str().is_empty()).collect();
+            graphs.sort_by_key(|graph| graph.info.uuid);
+            for graph in graphs {
+                for version in get_graph_versions(graph) {
+                    let info = match get_graph_version_from_library(lib, graph.info.uuid, version) {
+                        Some(info) => info,
+                        None => continue,
+                    };
+                    let graph_ref = GraphRef {
+                        name: graph.info.name.clone(),
+                        uuid: graph.info.uuid,
+                        library: Some(lib.info.uuid),
+                        version: VersionSpec::Exact(version),
+                    };
+                    for asset in graph_assets(self, &graph_ref, &info) {
+                        match &asset.resolved {
+                            Some(resolved) if resolved.starts_with(&lib.path) => {}
+                            Some(resolved) => problems.push(CatalogueProblem::ExternalAsset {
+                                library: lib.info.uuid,
+                                graph: graph.info.uuid,
+                                version,
+                                node: asset.node,
+                                path: resolved.clone(),
+                            }),
+                            None => problems.push(CatalogueProblem::MissingAsset {
+                                library: lib.info.uuid,
+                                graph: graph.info.uuid,
+                                version,
+                                node: asset.node,
+                                path: asset.path.clone(),
+                            }),
+                        }
+                    }
+                    for node in info.nodes.iter() {
+                        match self.resolve_graph_ref_from(&node.graph, Some(lib.info.uuid)) {
+                            Ok(resolved) => {
+                                let used = resolved.library.unwrap_or(lib.info.uuid);
+                                let declared = used == lib.info.uuid
+                                    || used == internal_lib
+                                    || lib.info.dependencies.iter().any(|dependency| dependency.uuid == used);
+                                if !declared {
+                                    problems.push(CatalogueProblem::UndeclaredDependency {
+                                        library: lib.info.uuid,
+                                        graph: graph.info.uuid,
+                                        version,
+                                        used,
+                                    });
+                                }
+                            }
+                            Err(reason) => problems.push(CatalogueProblem::MissingGraph {
+                                library: lib.info.uuid,
+                                graph: graph.info.uuid,
+                                version,
+                                missing: node.graph.clone(),
+                                reason,
+                            }),
+                        }
+                    }
+                }
+            }
+        }
+        problems
+    }
+}
+
+///
+/// Resolves a version specifier of a graph in a library to the exact version it points to.
+/// Versions are compatible when none of their pins break, see `PinChange::is_breaking`.
+/// The pins of internal graphs are not known without a node, so each of their versions is only compatible with itself.
+///
+fn resolve_graph_version(lib: &Library, graph: &LibraryGraphInfo, spec: &VersionSpec) -> Result<u64, String> {
+    let id = graph.info.uuid;
+    let versions = get_graph_versions(graph);
+    match spec {
+        VersionSpec::Exact(version) => match versions.contains(version) {
+            true => Ok(*version),
+            false => Err(format!("graph {} : {} does not have version {}", id, graph.info.name, version)),
+        },
+        VersionSpec::Latest => match versions.last() {
+            Some(version) => Ok(*version),
+            None => Err(format!("graph {} : {} does not have any versions", id, graph.info.name)),
+        },
+        VersionSpec::LatestCompatible(base) => {
+            if !versions.contains(base) {
+                return Err(format!("graph {} : {} does not have version {}", id, graph.info.name, base));
+            }
+            if graph.path.as_os_str().is_empty() {
+                return Ok(*base);
+            }
+            let base_info = match get_graph_version_from_library(lib, id, *base) {
+                Some(base_info) => base_info,
+                None => return Err(format!("graph {} : {} version {} could not be read", id, graph.info.name, base)),
             };
+            for version in versions.iter().rev().filter(|version| *version > base) {
+                if let Some(info) = get_graph_version_from_library(lib, id, *version) {
+                    if is_compatible(&base_info, &info) {
+                        return Ok(*version);
+                    }
+                }
+            }
+            Ok(*base)
+        }
+    }
+}
+
+///
+/// Something wrong with how the libraries in a catalogue fit together.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum CatalogueProblem {
+    /// A library depends on a library that is not in the catalogue.
+    MissingDependency { library: uuid::Uuid, dependency: LibraryDependency },
+    /// A library depends on a library whose version it does not allow.
+    IncompatibleDependency { library: uuid::Uuid, dependency: LibraryDependency, found: u64 },
+    /// Copies of the same graph, with the same name and versions, are in more than one library.
+    DuplicateGraph { graph: uuid::Uuid, libraries: Vec<uuid::Uuid> },
+    /// Different graphs with the same id are in more than one library.
+    ConflictingGraph { graph: uuid::Uuid, libraries: Vec<uuid::Uuid> },
+    /// A version of a graph has a node whose graph cannot be found.
+    MissingGraph { library: uuid::Uuid, graph: uuid::Uuid, version: u64, missing: GraphRef, reason: String },
+    /// A version of a graph has a node whose asset cannot be found.
+    MissingAsset { library: uuid::Uuid, graph: uuid::Uuid, version: u64, node: uuid::Uuid, path: String },
+    /// A version of a graph has a node whose asset is outside of the library.
+    ExternalAsset { library: uuid::Uuid, graph: uuid::Uuid, version: u64, node: uuid::Uuid, path: PathBuf },
+    /// A version of a graph has a node whose graph is in a library that its own library does not depend on.
+    UndeclaredDependency { library: uuid::Uuid, graph: uuid::Uuid, version: u64, used: uuid::Uuid },
+}
+
+impl std::fmt::Display for CatalogueProblem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogueProblem::MissingDependency { library, dependency } => write!(
+                f,
+                "library {} depends on library {} {} which is not in the catalogue",
+                library,
+                dependency.uuid,
+                dependency.name.clone().unwrap_or_default()
+            ),
+            CatalogueProblem::IncompatibleDependency { library, dependency, found } => write!(
+                f,
+                "library {} depends on library {} between versions {:?} and {:?}, but version {} is in the catalogue",
+                library, dependency.uuid, dependency.min_version, dependency.max_version, found
+            ),
+            CatalogueProblem::DuplicateGraph { graph, libraries } => {
+                write!(f, "graph {} is duplicated in libraries {:?}", graph, libraries)
+            }
+            CatalogueProblem::ConflictingGraph { graph, libraries } => {
+                write!(f, "different graphs with id {} are in libraries {:?}", graph, libraries)
+            }
+            CatalogueProblem::MissingGraph { library, graph, version, missing, reason } => write!(
+                f,
+                "graph {} version {} in library {} uses graph {} : {} which could not be found: {}",
+                graph, version, library, missing.uuid, missing.name, reason
+            ),
+            CatalogueProblem::MissingAsset { library, graph, version, node, path } => write!(
+                f,
+                "graph {} version {} in library {} has node {} using asset {} which could not be found",
+                graph, version, library, node, path
+            ),
+            CatalogueProblem::ExternalAsset { library, graph, version, node, path } => write!(
+                f,
+                "graph {} version {} in library {} has node {} using asset {} which is outside of the library",
+                graph, version, library, node, path.display()
+            ),
+            CatalogueProblem::UndeclaredDependency { library, graph, version, used } => write!(
+                f,
+                "graph {} version {} in library {} uses library {} without depending on it",
+                graph, version, library, used
+            ),
         }
-        false
     }
 }
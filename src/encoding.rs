@@ -0,0 +1,232 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use super::graph::*;
+
+/// The bytes that start every binary version file.
+pub const BINARY_MAGIC: &[u8; 4] = b"PFVB";
+
+/// The layout of binary version files written by this version.
+pub const BINARY_FORMAT: u32 = 1;
+
+/// The name of a version stored as json.
+pub const VERSION_JSON: &str = "version.json";
+
+/// The name of a version stored in the binary format, which is read instead of the json when both exist.
+pub const VERSION_BIN: &str = "version.bin";
+
+///
+/// How a version of a graph is stored.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VersionEncoding {
+    Json,
+    Binary,
+}
+
+impl VersionEncoding {
+    ///
+    /// The encoding a file should be written in, going by its extension.
+    ///
+    pub fn for_path(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some("bin") => VersionEncoding::Binary,
+            _ => VersionEncoding::Json,
+        }
+    }
+
+    ///
+    /// The encoding of the contents of a version file, going by whether it starts with `BINARY_MAGIC`.
+    ///
+    pub fn detect(bytes: &[u8]) -> Self {
+        match bytes.starts_with(BINARY_MAGIC) {
+            true => VersionEncoding::Binary,
+            false => VersionEncoding::Json,
+        }
+    }
+}
+
+///
+/// Whether a version folder, which may be within a package, holds a version in either encoding.
+///
+pub fn has_version_file(version_dir: &Path) -> bool {
+    crate::package::library_file_exists(&version_dir.join(VERSION_BIN)) || crate::package::library_file_exists(&version_dir.join(VERSION_JSON))
+}
+
+///
+/// Reads a version from the contents of a version file, in whichever encoding it is.
+///
+pub fn decode_version(bytes: &[u8]) -> Result<VersionInfo, String> {
+    match VersionEncoding::detect(bytes) {
+        VersionEncoding::Json => serde_json::from_slice(bytes).map_err(|e| format!("could not parse version json: {}", e)),
+        VersionEncoding::Binary => {
+            let header = BINARY_MAGIC.len() + 4;
+            if bytes.len() < header {
+                return Err(String::from("binary version is too short to hold its header"));
+            }
+            let mut format = [0; 4];
+            format.copy_from_slice(&bytes[BINARY_MAGIC.len()..header]);
+            let format = u32::from_le_bytes(format);
+            if format > BINARY_FORMAT {
+                return Err(format!("binary version has format {}, newer than {}", format, BINARY_FORMAT));
+            }
+            bincode::deserialize(&bytes[header..]).map_err(|e| format!("could not parse binary version: {}", e))
+        }
+    }
+}
+
+///
+/// Writes a version in the given encoding.
+///
+pub fn encode_version(version: &VersionInfo, encoding: VersionEncoding) -> Result<Vec<u8>, String> {
+    match encoding {
+        VersionEncoding::Json => serde_json::to_vec_pretty(version).map_err(|e| format!("could not write version json: {}", e)),
+        VersionEncoding::Binary => {
+            let mut bytes = BINARY_MAGIC.to_vec();
+            bytes.extend_from_slice(&BINARY_FORMAT.to_le_bytes());
+            bincode::serialize_into(&mut bytes, version).map_err(|e| format!("could not write binary version: {}", e))?;
+            Ok(bytes)
+        }
+    }
+}
+
+///
+/// Converts a version file to the encoding its new path calls for, see `VersionEncoding::for_path`.
+///
+pub fn convert_version_file(from: &Path, to: &Path) -> Result<VersionEncoding, String> {
+    let bytes = std::fs::read(from).map_err(|e| format!("could not read {}: {}", from.display(), e))?;
+    let version = decode_version(&bytes).map_err(|e| format!("{}: {}", from.display(), e))?;
+    let encoding = VersionEncoding::for_path(to);
+    std::fs::write(to, encode_version(&version, encoding)?).map_err(|e| format!("could not write {}: {}", to.display(), e))?;
+    Ok(encoding)
+}
+
+///
+/// A json value in a form that formats which are not self describing, like bincode, can hold.
+/// Arrays holding only floats or only integers are packed, as large matrices and arrays are common in node data.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum BinaryValue {
+    Null,
+    Bool(bool),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    String(String),
+    F64Array(Vec<f64>),
+    I64Array(Vec<i64>),
+    Array(Vec<BinaryValue>),
+    Object(Vec<(String, BinaryValue)>),
+}
+
+impl From<&Value> for BinaryValue {
+    fn from(value: &Value) -> Self {
+        match value {
+            Value::Null => BinaryValue::Null,
+            Value::Bool(value) => BinaryValue::Bool(*value),
+            Value::Number(number) => match (number.as_u64(), number.as_i64(), number.as_f64()) {
+                (Some(value), _, _) => BinaryValue::U64(value),
+                (_, Some(value), _) => BinaryValue::I64(value),
+                (_, _, value) => BinaryValue::F64(value.unwrap_or_default()),
+            },
+            Value::String(value) => BinaryValue::String(value.clone()),
+            Value::Array(values) if !values.is_empty() && values.iter().all(|value| value.as_f64().is_some() && value.is_f64()) => {
+                BinaryValue::F64Array(values.iter().filter_map(|value| value.as_f64()).collect())
+            }
+            Value::Array(values) if !values.is_empty() && values.iter().all(|value| value.is_i64()) => {
+                BinaryValue::I64Array(values.iter().filter_map(|value| value.as_i64()).collect())
+            }
+            Value::Array(values) => BinaryValue::Array(values.iter().map(BinaryValue::from).collect()),
+            Value::Object(map) => BinaryValue::Object(map.iter().map(|(key, value)| (key.clone(), BinaryValue::from(value))).collect()),
+        }
+    }
+}
+
+impl From<BinaryValue> for Value {
+    fn from(value: BinaryValue) -> Self {
+        match value {
+            BinaryValue::Null => Value::Null,
+            BinaryValue::Bool(value) => Value::Bool(value),
+            BinaryValue::U64(value) => Value::from(value),
+            BinaryValue::I64(value) => Value::from(value),
+            BinaryValue::F64(value) => Value::from(value),
+            BinaryValue::String(value) => Value::String(value),
+            BinaryValue::F64Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
+            BinaryValue::I64Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
+            BinaryValue::Array(values) => Value::Array(values.into_iter().map(Value::from).collect()),
+            BinaryValue::Object(entries) => Value::Object(entries.into_iter().map(|(key, value)| (key, Value::from(value))).collect()),
+        }
+    }
+}
+
+///
+/// Serializes a json value as itself for human readable formats, and as a `BinaryValue` otherwise.
+/// For use with `#[serde(with = "crate::encoding::value")]`.
+///
+pub mod value {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Value, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => value.serialize(serializer),
+            false => BinaryValue::from(value).serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Value, D::Error> {
+        match deserializer.is_human_readable() {
+            true => Value::deserialize(deserializer),
+            false => BinaryValue::deserialize(deserializer).map(Value::from),
+        }
+    }
+}
+
+///
+/// Like `value`, for optional json values.
+///
+pub mod value_option {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(value: &Option<Value>, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => value.serialize(serializer),
+            false => value.as_ref().map(BinaryValue::from).serialize(serializer),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+        match deserializer.is_human_readable() {
+            true => Option::<Value>::deserialize(deserializer),
+            false => Option::<BinaryValue>::deserialize(deserializer).map(|value| value.map(Value::from)),
+        }
+    }
+}
+
+///
+/// Like `value`, for maps of json values, which are written in key order so the same data always gives the same bytes.
+///
+pub mod value_map {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(map: &HashMap<String, Value>, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => map.serialize(serializer),
+            false => {
+                let mut entries: Vec<(&String, BinaryValue)> = map.iter().map(|(key, value)| (key, BinaryValue::from(value))).collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                entries.serialize(serializer)
+            }
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<HashMap<String, Value>, D::Error> {
+        match deserializer.is_human_readable() {
+            true => HashMap::<String, Value>::deserialize(deserializer),
+            false => Vec::<(String, BinaryValue)>::deserialize(deserializer)
+                .map(|entries| entries.into_iter().map(|(key, value)| (key, Value::from(value))).collect()),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 // use crate::node::*;
 
 use crate::catalogue::*;
+use crate::event::*;
+use crate::metrics::*;
+use crate::trace::*;
 // use crate::graph::*;
 
 use axiom::prelude::*;
@@ -21,6 +24,14 @@ pub struct Engine {
     pub system: ActorSystem,
     /// Mutable shared reference to the Catalogue.
     pub catalogue: Arc<Mutex<Catalogue>>,
+    /// Shared event bus that the controller publishes to.
+    pub events: Arc<Mutex<EventBus>>,
+    /// Shared execution tracer that the controller and nodes record spans to.
+    pub tracer: Arc<Mutex<Tracer>>,
+    /// Shared metrics that the controller and nodes count into.
+    pub metrics: Arc<Mutex<Metrics>>,
+    /// Shared counts of the commands handled by the controller and nodes.
+    pub messages: Arc<MessageCounters>,
     /// Sending channel to send things.
     pub send_to_controller: Sender<Message>,
     /// Recieving channel to get things back.
@@ -32,16 +43,29 @@ pub struct Engine {
 impl Engine {
     ///
     /// Constructs and initiates a Proc Flow engine and associated controller.
+    /// Libraries are found in the default locations only, without reading the config file or the environment and without an index.
+    /// Use `with_config` and `CatalogueConfig::load` to find them the way the application does.
     ///
     pub fn new() -> Self {
+        Self::with_config(CatalogueConfig::new())
+    }
+
+    ///
+    /// Constructs and initiates a Proc Flow engine that finds its libraries as the given config says.
+    ///
+    pub fn with_config(config: CatalogueConfig) -> Self {
         // Create engine state.
         let system = ActorSystem::create(ActorSystemConfig::default());
         let catalogue = Arc::new(Mutex::new(Catalogue::new()));
+        let events = Arc::new(Mutex::new(EventBus::new()));
+        let tracer = Arc::new(Mutex::new(Tracer::new()));
+        let metrics = Arc::new(Mutex::new(Metrics::new()));
+        let messages = Arc::new(MessageCounters::new());
         let (send_to_controller, recv_from_engine) = crossbeam::unbounded();
         let (send_to_engine, recv_from_controller) = crossbeam::unbounded();
         let nodes = HashMap::new();
 
-        catalogue.lock().unwrap().load_default_libraries();
+        catalogue.lock().unwrap().load_libraries(config);
 
         // Create and spawn the controller.
         let controller_state = Controller {
@@ -49,6 +73,11 @@ impl Engine {
             send_to_engine,
             recv_from_engine,
             catalogue: catalogue.clone(),
+            events: events.clone(),
+            tracer: tracer.clone(),
+            metrics: metrics.clone(),
+            messages: messages.clone(),
+            running: HashMap::new(),
         };
         let controller = system
             .spawn()
@@ -60,6 +89,10 @@ impl Engine {
         Self {
             system,
             catalogue,
+            events,
+            tracer,
+            metrics,
+            messages,
             send_to_controller,
             recv_from_controller,
             controller,
@@ -119,26 +152,34 @@ impl Engine {
             }
         }
     }
-    pub fn compute_output_pin_value(&mut self, node_actor: Aid, output: uuid::Uuid, parameters: Option<Message>) -> Option<Message> {
+    ///
+    /// Computes the value of an output pin, waiting for it.
+    /// Gives back why when the output, or anything it needs, could not be computed.
+    ///
+    pub fn compute_output_pin_value(&mut self, node_actor: Aid, output: uuid::Uuid, parameters: Option<Message>) -> Result<Option<Message>, String> {
         info!("engine get output pin value");
         match self.controller.send_new(ControllerCommand::ComputeOutputPinValue(node_actor.clone(), output, parameters)) {
             Ok(()) => {
                 if let Some(msg) = self.recv_from_controller.recv().unwrap().content_as::<ControllerResponse>() {
                     match &*msg {
                         ControllerResponse::OutputValue(_node_actor, value) => {
-                            return value.clone();
+                            return Ok(value.clone());
+                        }
+                        ControllerResponse::OutputError(_node_actor, e) => {
+                            return Err(e.clone());
                         }
                         _ => {
                             error!("bad response on get output pin value request to controller");
                         }
                     }
                 };
+                Err(format!("bad response on get output pin {} value request to controller", output))
             }
             Err(e) => {
                 error!("could not send message to get output pin {:?} value on node actor {:?}: {}", output.clone(), node_actor.clone(), e.to_string());
+                Err(format!("could not ask controller for output pin {}: {}", output, e))
             }
         }
-        None
     }
     pub fn send_value(&mut self, node_actor: Aid, receiver: uuid::Uuid, value: Option<Message>) {
         info!("engine send value");
@@ -160,6 +201,105 @@ impl Engine {
             }
         }
     }
+    /// Links a send pin to a receive pin, or an input pin to an output pin, of another node actor.
+    /// Returns whether or not the link was made.
+    pub fn link_pins(&mut self, node_actor: Aid, pin: uuid::Uuid, target_actor: Aid, target_pin: crate::graph::PinRef) -> bool {
+        info!("engine link pins");
+        match self.controller.send_new(ControllerCommand::LinkPins(node_actor.clone(), pin.clone(), target_actor, target_pin)) {
+            Ok(()) => {
+                if let Some(msg) = self.recv_from_controller.recv().unwrap().content_as::<ControllerResponse>() {
+                    match &*msg {
+                        ControllerResponse::PinsLinked(linked) => {
+                            return *linked;
+                        }
+                        _ => {
+                            error!("bad response on link pins request to controller");
+                        }
+                    }
+                };
+            }
+            Err(e) => {
+                error!("could not send message to link pin {:?} on node actor {:?}: {}", pin.clone(), node_actor.clone(), e.to_string());
+            }
+        }
+        false
+    }
+    /// Creates or updates an internal data value of a node actor, such as its seed or the idle timeout of a graph.
+    pub fn update_datum(&mut self, node_actor: Aid, key: String, value: serde_json::Value) {
+        info!("engine update datum");
+        match self.controller.send_new(ControllerCommand::UpdateDatum(node_actor.clone(), key.clone(), value)) {
+            Ok(()) => {
+                if let Some(msg) = self.recv_from_controller.recv().unwrap().content_as::<ControllerResponse>() {
+                    match &*msg {
+                        ControllerResponse::DatumUpdated => {
+                            info!("engine datum updated")
+                        }
+                        _ => {
+                            error!("bad response on update datum request to controller");
+                        }
+                    }
+                };
+            }
+            Err(e) => {
+                error!("could not send message to update datum {} on node actor {:?}: {}", key, node_actor.clone(), e.to_string());
+            }
+        }
+    }
+    ///
+    /// Starts watching the catalogue search paths, checking for changed library files every interval.
+    /// Changed libraries are loaded again, and running node actors of the changed graphs refresh their pins.
+    /// Watching stops when the returned handle is stopped or dropped.
+    ///
+    pub fn watch_libraries(&self, interval: std::time::Duration) -> LibraryWatch {
+        let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let catalogue = self.catalogue.clone();
+        let controller = self.controller.clone();
+        let mut watcher = crate::watcher::LibraryWatcher::new(&catalogue.lock().unwrap().config.paths());
+        let thread_running = running.clone();
+        let thread = std::thread::spawn(move || {
+            while thread_running.load(std::sync::atomic::Ordering::SeqCst) {
+                std::thread::sleep(interval);
+                let paths = catalogue.lock().unwrap().config.paths();
+                let changed = watcher.poll(&paths);
+                if changed.is_empty() {
+                    continue;
+                }
+                let graphs = catalogue.lock().unwrap().reload(&changed);
+                if let Err(e) = controller.send_new(ControllerCommand::RefreshGraphs(graphs)) {
+                    error!("could not tell controller about changed graphs: {}", e.to_string());
+                }
+            }
+        });
+        LibraryWatch {
+            running,
+            thread: Some(thread),
+        }
+    }
+    /// Starts recording trace spans for every compute and controller command, throwing away any previous trace.
+    pub fn start_tracing(&self) {
+        self.tracer.lock().unwrap().start();
+    }
+    /// Stops recording trace spans and hands back what was recorded.
+    /// The spans can be written out for a trace viewer with `trace::write_chrome_trace`.
+    pub fn stop_tracing(&self) -> Vec<TraceSpan> {
+        self.tracer.lock().unwrap().stop()
+    }
+    /// Takes a snapshot of the engine's metrics.
+    pub fn metrics(&self) -> Metrics {
+        let mut metrics = self.metrics.lock().unwrap().clone();
+        self.messages.fold_into(&mut metrics);
+        metrics
+    }
+    /// Serves the engine's metrics in the Prometheus text format on the given address, such as 127.0.0.1:9898.
+    /// Serving stops, and the port is freed, when the returned handle is stopped or dropped.
+    pub fn serve_metrics(&self, address: std::net::SocketAddr) -> std::io::Result<MetricsServer> {
+        serve_prometheus(self.metrics.clone(), self.messages.clone(), address)
+    }
+    /// Subscribes to events happening inside the engine that pass the given filter.
+    /// Events are delivered until the receiver is dropped.
+    pub fn subscribe(&self, filter: EngineEventFilter) -> Receiver<EngineEvent> {
+        self.events.lock().unwrap().subscribe(filter)
+    }
     /// Tells the engine that it can stop waiting for new messages.
     pub fn shutdown(&mut self) {
         self.system.trigger_shutdown();
@@ -170,6 +310,33 @@ impl Engine {
     }
 }
 
+///
+/// Handle to the thread watching library files, which stops it when dropped.
+///
+pub struct LibraryWatch {
+    running: Arc<std::sync::atomic::AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl LibraryWatch {
+    /// Stops watching, and waits for the watching thread to finish.
+    pub fn stop(mut self) {
+        self.halt();
+    }
+    fn halt(&mut self) {
+        self.running.store(false, std::sync::atomic::Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for LibraryWatch {
+    fn drop(&mut self) {
+        self.halt();
+    }
+}
+
 ///
 /// Each possible command from the engine or a node to the controller.
 ///
@@ -203,10 +370,45 @@ pub enum ControllerCommand {
     /// Second is the UUID of the pin to send to.
     /// Message is the message to send.
     SendValue(Aid, uuid::Uuid, Option<Message>),
+    /// Links a send or input pin of one node to a receive or output pin of another.
+    /// First id is the node actor that owns the send or input pin.
+    /// Second is the UUID of that pin.
+    /// Third id is the node actor being linked to.
+    /// Pin is the receive or output pin being linked to.
+    LinkPins(Aid, uuid::Uuid, Aid, crate::graph::PinRef),
+    /// Creates or updates an internal data value of a node.
+    /// Id is the node actor to update.
+    /// String is the datum key.
+    /// The value is the datum.
+    UpdateDatum(Aid, String, serde_json::Value),
+    /// Publishes an event to every matching subscriber of the engine.
+    PublishEvent(EngineEvent),
+    /// Tells every running node actor of the given graphs to read its pins again, after the files of those graphs changed.
+    /// Ids are the graphs.
+    RefreshGraphs(Vec<uuid::Uuid>),
     /// Tells the engine that nodes are fine with being shutdown and that no new messages need to be processed.
     StopWaitingForNewMessages,
 }
 
+impl ControllerCommand {
+    /// The name of the command, used when tracing and counting commands.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControllerCommand::GiveMe5 => "GiveMe5",
+            ControllerCommand::BootGraph(_, _, _, _) => "BootGraph",
+            ControllerCommand::RouteMessage(_, _, _) => "RouteMessage",
+            ControllerCommand::ComputeOutputPinValue(_, _, _) => "ComputeOutputPinValue",
+            ControllerCommand::SetInputPinValue(_, _, _, _) => "SetInputPinValue",
+            ControllerCommand::SendValue(_, _, _) => "SendValue",
+            ControllerCommand::LinkPins(_, _, _, _) => "LinkPins",
+            ControllerCommand::UpdateDatum(_, _, _) => "UpdateDatum",
+            ControllerCommand::PublishEvent(_) => "PublishEvent",
+            ControllerCommand::RefreshGraphs(_) => "RefreshGraphs",
+            ControllerCommand::StopWaitingForNewMessages => "StopWaitingForNewMessages",
+        }
+    }
+}
+
 ///
 /// Each possible response from the controller.
 ///
@@ -216,10 +418,16 @@ pub enum ControllerResponse {
     GraphBooted(uuid::Uuid, Option<Aid>),
     /// Presents a value from the pin of an output.
     OutputValue(Aid, Option<Message>),
+    /// Presents why the value of an output could not be computed.
+    OutputError(Aid, String),
     /// Presents that a pin's value was set sucessfully.
     InputPinSet,
     /// TODO Proper comment here.
     ValueSent,
+    /// Presents whether or not two pins were linked.
+    PinsLinked(bool),
+    /// Presents that a node's datum was created or updated.
+    DatumUpdated,
 }
 
 ///
@@ -234,17 +442,95 @@ pub struct Controller {
     pub recv_from_engine: Receiver<Message>,
     /// Reference to the node library.
     pub catalogue: Arc<Mutex<Catalogue>>,
+    /// Event bus shared with the engine.
+    pub events: Arc<Mutex<EventBus>>,
+    /// Execution tracer shared with the engine and handed to every booted node.
+    pub tracer: Arc<Mutex<Tracer>>,
+    /// Metrics shared with the engine and handed to every booted node.
+    pub metrics: Arc<Mutex<Metrics>>,
+    /// Command counts shared with the engine and handed to every booted node.
+    pub messages: Arc<MessageCounters>,
+    /// Every running node actor, including the ones within graphs, with the graph it represents.
+    /// Kept up to date from the boot and stop events that pass through the controller.
+    pub running: HashMap<Aid, uuid::Uuid>,
 }
 
 use log::*;
 use crate::node::NodeResponse;
 
 impl Controller {
+    /// Publishes an event to the engine's subscribers.
+    fn publish(&mut self, event: EngineEvent) {
+        if let (Some(graph), Some(actor)) = (&event.graph, &event.actor) {
+            match event.event {
+                EngineEventType::NodeBooted => {
+                    self.running.insert(actor.clone(), graph.uuid);
+                }
+                EngineEventType::NodeStopped => {
+                    self.running.remove(actor);
+                }
+                _ => {}
+            }
+        }
+        self.events.lock().unwrap().publish(event);
+    }
+    /// Publishes an error event to the engine's subscribers.
+    fn publish_error(&mut self, graph: Option<crate::graph::GraphRef>, instance: Option<uuid::Uuid>, reason: String) {
+        self.publish(EngineEvent {
+            graph,
+            instance,
+            actor: None,
+            event: EngineEventType::Error(reason),
+        });
+    }
+    /// Tells whoever asked for a graph to be booted about its node actor, or the engine if no node asked.
+    fn reply_booted(&self, requestor: &Option<Aid>, instance_id: &uuid::Uuid, actor: Option<Aid>) {
+        match requestor {
+            Some(requestor) => {
+                let _ = requestor.send_new(ControllerResponse::GraphBooted(instance_id.clone(), actor));
+            }
+            None => {
+                let _ = self.send_to_engine.send(Message::new(ControllerResponse::GraphBooted(instance_id.clone(), actor)));
+            }
+        }
+    }
+    /// Spawns a booted node as an actor, and replies with it.
+    fn spawn_node(&mut self, context: &Context, mut node: crate::node::Node, instance_id: &uuid::Uuid, requestor: &Option<Aid>) {
+        node.tracer = Some(self.tracer.clone());
+        node.metrics = Some(self.metrics.clone());
+        node.messages = Some(self.messages.clone());
+        node.subscribers = Some(self.events.lock().unwrap().subscribers());
+        let node_graph = node.info.graph.clone();
+        match context
+            .system
+            .spawn()
+            .name(format!("{} {}", node.info.graph.name, node.info.uuid))
+            .with(node, crate::node::Node::handle)
+        {
+            Ok(actor) => {
+                info!("graph {} : {} version {} node actor spawned", node_graph.uuid, node_graph.name.clone(), node_graph.version.clone());
+                self.publish(EngineEvent {
+                    graph: Some(node_graph),
+                    instance: Some(instance_id.clone()),
+                    actor: Some(actor.clone()),
+                    event: EngineEventType::NodeBooted,
+                });
+                self.reply_booted(requestor, instance_id, Some(actor));
+            }
+            Err(e) => {
+                error!("graph {} : {} version {} node actor could not be spawned: {:?}", node_graph.uuid, node_graph.name.clone(), node_graph.version.clone(), e);
+                self.publish_error(Some(node_graph), Some(instance_id.clone()), format!("node actor could not be spawned: {:?}", e));
+                self.reply_booted(requestor, instance_id, None);
+            }
+        }
+    }
     ///
     /// Handle messages sent by other actors.
     ///
-    pub async fn handle(self, context: Context, message: Message) -> ActorResult<Self> {
+    pub async fn handle(mut self, context: Context, message: Message) -> ActorResult<Self> {
         if let Some(msg) = message.content_as::<ControllerCommand>() {
+            let command_start = std::time::Instant::now();
+            self.messages.controller.count(msg.name());
             match &*msg {
                 ControllerCommand::GiveMe5 => {
                     let _ = self.send_to_engine.send(Message::new(5 as u64));
@@ -279,84 +565,18 @@ impl Controller {
                             {
                                 // Create an internal library node.
                                 let node = crate::nodes::create(
-                                    context.aid,
+                                    context.aid.clone(),
                                     self.catalogue.clone(),
                                     graph_id.clone(),
                                     version.clone(),
                                     instance_id.clone(),
                                 );
                                 match node {
-                                    Some(node) => {
-                                        match context
-                                            .system
-                                            .spawn()
-                                            .name(node.info.graph.name.clone())
-                                            .with(node, crate::node::Node::handle)
-                                        {
-                                            Ok(actor) => {
-                                                info!("internal graph {} : {} version {} node actor spawned", graph_id, graph_ref.name.clone(), version.clone());
-                                                match requestor {
-                                                    Some(requestor) => {
-                                                        let _ = requestor.send_new(
-                                                            ControllerResponse::GraphBooted(
-                                                                instance_id.clone(),
-                                                                Some(actor),
-                                                            ),
-                                                        );
-                                                    }
-                                                    None => {
-                                                        let _ = self.send_to_engine.send(Message::new(
-                                                            ControllerResponse::GraphBooted(
-                                                                instance_id.clone(),
-                                                                Some(actor),
-                                                            ),
-                                                        ));
-                                                    }
-                                                }
-                                            }
-                                            Err(e) => {
-                                                error!("internal graph {} : {} version {} node actor could not be spawned: {:?}", graph_id, graph_ref.name.clone(), version.clone(), e);
-                                                match requestor {
-                                                    Some(requestor) => {
-                                                        let _ = requestor.send_new(
-                                                            ControllerResponse::GraphBooted(
-                                                                instance_id.clone(),
-                                                                None,
-                                                            ),
-                                                        );
-                                                    }
-                                                    None => {
-                                                        let _ = self.send_to_engine.send(Message::new(
-                                                            ControllerResponse::GraphBooted(
-                                                                instance_id.clone(),
-                                                                None,
-                                                            ),
-                                                        ));
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
+                                    Some(node) => self.spawn_node(&context, node, instance_id, requestor),
                                     None => {
                                         error!("internal graph {} : {} version {} could not be created", graph_id, graph_ref.name.clone(), version.clone());
-                                        match requestor {
-                                            Some(requestor) => {
-                                                let _ = requestor.send_new(
-                                                    ControllerResponse::GraphBooted(
-                                                        instance_id.clone(),
-                                                        None,
-                                                    ),
-                                                );
-                                            }
-                                            None => {
-                                                let _ = self.send_to_engine.send(Message::new(
-                                                    ControllerResponse::GraphBooted(
-                                                        instance_id.clone(),
-                                                        None,
-                                                    ),
-                                                ));
-                                            }
-                                        }
+                                        self.publish_error(Some(graph_ref.clone()), Some(instance_id.clone()), String::from("internal graph could not be created"));
+                                        self.reply_booted(requestor, instance_id, None);
                                     }
                                 }
                             } else {
@@ -375,7 +595,21 @@ impl Controller {
                                         graph_ref.name.clone(),
                                         version.clone()
                                     );
-                                    // TODO Implement dynamic user graph booting, likely as a graph node.
+                                    let node = crate::nodes::meta::graph::instantiate(
+                                        context.aid.clone(),
+                                        self.catalogue.clone(),
+                                        &graph_ref,
+                                        None,
+                                        instance_id.clone(),
+                                    );
+                                    match node {
+                                        Some(node) => self.spawn_node(&context, node, instance_id, requestor),
+                                        None => {
+                                            error!("graph {} : {} version {} could not be loaded", graph_id, graph_ref.name.clone(), version.clone());
+                                            self.publish_error(Some(graph_ref.clone()), Some(instance_id.clone()), format!("graph version {} could not be loaded", version));
+                                            self.reply_booted(requestor, instance_id, None);
+                                        }
+                                    }
                                 } else {
                                     error!(
                                         "graph {} : {} does not have version {} in catalogue",
@@ -383,42 +617,15 @@ impl Controller {
                                         graph_ref.name.clone(),
                                         version.clone()
                                     );
-                                    match requestor {
-                                        Some(requestor) => {
-                                            let _ = requestor.send_new(
-                                                ControllerResponse::GraphBooted(
-                                                    instance_id.clone(),
-                                                    None,
-                                                ),
-                                            );
-                                        }
-                                        None => {
-                                            let _ = self.send_to_engine.send(Message::new(
-                                                ControllerResponse::GraphBooted(
-                                                    instance_id.clone(),
-                                                    None,
-                                                ),
-                                            ));
-                                        }
-                                    }
+                                    self.publish_error(Some(graph_ref.clone()), Some(instance_id.clone()), format!("graph does not have version {} in catalogue", version));
+                                    self.reply_booted(requestor, instance_id, None);
                                 }
                             }
                         }
                         None => {
-                            error!("graph {} does not exist in the catalogue", graph_id);
-                            match requestor {
-                                Some(requestor) => {
-                                    let _ = requestor.send_new(ControllerResponse::GraphBooted(
-                                        instance_id.clone(),
-                                        None,
-                                    ));
-                                }
-                                None => {
-                                    let _ = self.send_to_engine.send(Message::new(
-                                        ControllerResponse::GraphBooted(instance_id.clone(), None),
-                                    ));
-                                }
-                            }
+                            error!("graph {} version {} does not exist in the catalogue", graph_id, version);
+                            self.publish_error(None, Some(instance_id.clone()), format!("graph {} version {} does not exist in the catalogue", graph_id, version));
+                            self.reply_booted(requestor, instance_id, None);
                         }
                     }
                 }
@@ -439,9 +646,10 @@ impl Controller {
                 ControllerCommand::ComputeOutputPinValue(node_actor, pin_id, parameters) => {
                     // TODO! Determine getting an output should block! Make both versions? Poll returns current output. Compute computes it!
                     info!("controller get output pin value");
-                    match node_actor.send_new(crate::node::NodeCommand::ComputeOutput(context.aid, pin_id.clone(), parameters.clone())) {
-                        Err(e) => error!("controller could not send command to node actor {} to get output of pin {}: {}", node_actor.clone(), pin_id.clone(), e.to_string()),
-                        _ => {}
+                    if let Err(e) = node_actor.send_new(crate::node::NodeCommand::ComputeOutput(context.aid, pin_id.clone(), parameters.clone())) {
+                        error!("controller could not send command to node actor {} to get output of pin {}: {}", node_actor.clone(), pin_id.clone(), e.to_string());
+                        let reason = format!("could not ask node actor {} for output pin {}: {}", node_actor, pin_id, e);
+                        let _ = self.send_to_engine.send(Message::new(ControllerResponse::OutputError(node_actor.clone(), reason)));
                     }
                 }
                 ControllerCommand::SendValue(node_actor, pin_id, parameters) => {
@@ -451,10 +659,61 @@ impl Controller {
                         _ => {}
                     }
                 }
+                ControllerCommand::LinkPins(node_actor, pin_id, target_actor, target_pin) => {
+                    info!("controller link pins");
+                    match node_actor.send_new(crate::node::NodeCommand::LinkPin(context.aid.clone(), pin_id.clone(), target_actor.clone(), target_pin.clone())) {
+                        Err(e) => error!("controller could not send command to node actor {} to link pin {}: {}", node_actor.clone(), pin_id.clone(), e.to_string()),
+                        _ => {}
+                    }
+                }
+                ControllerCommand::UpdateDatum(node_actor, key, value) => {
+                    info!("controller update datum");
+                    match node_actor.send_new(crate::node::NodeCommand::UpdateDatum(context.aid.clone(), key.clone(), value.clone())) {
+                        Err(e) => error!("controller could not send command to node actor {} to update datum {}: {}", node_actor.clone(), key, e.to_string()),
+                        _ => {}
+                    }
+                }
+                ControllerCommand::PublishEvent(event) => {
+                    self.publish(event.clone());
+                }
+                ControllerCommand::RefreshGraphs(graphs) => {
+                    info!("controller refreshing node actors of {} changed graphs", graphs.len());
+                    for (actor, graph) in self.running.iter() {
+                        if !graphs.contains(graph) {
+                            continue;
+                        }
+                        if let Err(e) = actor.send_new(crate::node::NodeCommand::RefreshPins(context.aid.clone())) {
+                            error!("controller could not tell node actor {} to refresh its pins: {}", actor.clone(), e.to_string());
+                        }
+                    }
+                    self.publish(EngineEvent {
+                        graph: None,
+                        instance: None,
+                        actor: None,
+                        event: EngineEventType::CatalogueReloaded(graphs.clone()),
+                    });
+                }
                 ControllerCommand::StopWaitingForNewMessages => {
                     context.system.trigger_shutdown();
                 }
             }
+            {
+                let mut tracer = self.tracer.lock().unwrap();
+                if tracer.enabled {
+                    let span = TraceSpan {
+                        name: String::from(msg.name()),
+                        category: String::from("controller"),
+                        graph: None,
+                        instance: None,
+                        pin: None,
+                        cache_hit: None,
+                        actor: String::from("controller"),
+                        start: tracer.since_start(command_start),
+                        duration: None,
+                    };
+                    tracer.record(span);
+                }
+            }
         }
         if let Some(msg) = message.content_as::<NodeResponse>() {
             match &*msg {
@@ -464,6 +723,12 @@ impl Controller {
                         _ => {}
                     }
                 }
+                NodeResponse::OutputPinError(node_actor, output_pin, reason) => {
+                    match self.send_to_engine.send(Message::new(ControllerResponse::OutputError(node_actor.clone(), reason.clone()))) {
+                        Err(e) => error!("controller could not send node actor {} pin {} error to engine channel: {}", node_actor.clone(), output_pin.clone(), e.to_string()),
+                        _ => {}
+                    }
+                }
                 NodeResponse::InputPinSet => {
                     match self.send_to_engine.send(Message::new(ControllerResponse::InputPinSet)) {
                         Err(e) => error!("controller could not get input pin value set confirmation to engine channel: {}", e.to_string()),
@@ -476,6 +741,18 @@ impl Controller {
                         _ => {}
                     }
                 }
+                NodeResponse::PinLinked(linked) => {
+                    match self.send_to_engine.send(Message::new(ControllerResponse::PinsLinked(*linked))) {
+                        Err(e) => error!("controller could not get pin link confirmation to engine channel: {}", e.to_string()),
+                        _ => {}
+                    }
+                }
+                NodeResponse::DatumUpdated => {
+                    match self.send_to_engine.send(Message::new(ControllerResponse::DatumUpdated)) {
+                        Err(e) => error!("controller could not get datum update confirmation to engine channel: {}", e.to_string()),
+                        _ => {}
+                    }
+                }
                 _ => {}
             }
         }
@@ -0,0 +1,175 @@
+use crate::graph::*;
+
+use axiom::prelude::*;
+
+use crossbeam::{Receiver, Sender};
+use serde::{Deserialize, Serialize};
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+///
+/// The kind of an engine event, used to filter subscriptions.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EngineEventKind {
+    NodeBooted,
+    NodeStopped,
+    OutputComputed,
+    InputPinSet,
+    MessageSent,
+    PinsRefreshed,
+    CatalogueReloaded,
+    Error,
+}
+
+///
+/// What happened for a given engine event.
+///
+#[derive(Serialize, Deserialize, Clone)]
+pub enum EngineEventType {
+    /// A node actor was spawned for a graph.
+    NodeBooted,
+    /// A node actor was stopped, such as a node within a graph that was idle for too long.
+    NodeStopped,
+    /// An output pin was computed or pulled from its cache.
+    /// Id is the output pin.
+    /// Message is the value of the output.
+    OutputComputed(uuid::Uuid, Option<Message>),
+    /// An input pin had its value set.
+    /// Id is the input pin.
+    InputPinSet(uuid::Uuid),
+    /// A message was sent on a send pin.
+    /// Id is the send pin.
+    /// Message is what was sent.
+    MessageSent(uuid::Uuid, Option<Message>),
+    /// A node actor read its pins again, such as after the files of its graph changed.
+    PinsRefreshed,
+    /// Libraries were loaded again after their files changed.
+    /// Ids are the graphs whose files changed.
+    CatalogueReloaded(Vec<uuid::Uuid>),
+    /// Something went wrong.
+    /// String is the reason.
+    Error(String),
+}
+
+impl EngineEventType {
+    pub fn kind(&self) -> EngineEventKind {
+        match self {
+            EngineEventType::NodeBooted => EngineEventKind::NodeBooted,
+            EngineEventType::NodeStopped => EngineEventKind::NodeStopped,
+            EngineEventType::OutputComputed(_, _) => EngineEventKind::OutputComputed,
+            EngineEventType::InputPinSet(_) => EngineEventKind::InputPinSet,
+            EngineEventType::MessageSent(_, _) => EngineEventKind::MessageSent,
+            EngineEventType::PinsRefreshed => EngineEventKind::PinsRefreshed,
+            EngineEventType::CatalogueReloaded(_) => EngineEventKind::CatalogueReloaded,
+            EngineEventType::Error(_) => EngineEventKind::Error,
+        }
+    }
+}
+
+///
+/// Something observable that happened inside the engine.
+///
+#[derive(Serialize, Deserialize, Clone)]
+pub struct EngineEvent {
+    /// The graph that the node represents, if the event came from a node.
+    pub graph: Option<GraphRef>,
+    /// The node instance the event came from, if any.
+    pub instance: Option<uuid::Uuid>,
+    /// The node actor the event came from, if any.
+    pub actor: Option<Aid>,
+    /// What happened.
+    pub event: EngineEventType,
+}
+
+impl EngineEvent {
+    pub fn kind(&self) -> EngineEventKind {
+        self.event.kind()
+    }
+}
+
+///
+/// Describes which events a subscriber is interested in.
+/// Any field left empty matches everything.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct EngineEventFilter {
+    /// Only events from nodes of this graph.
+    pub graph: Option<uuid::Uuid>,
+    /// Only events from this node instance.
+    pub instance: Option<uuid::Uuid>,
+    /// Only events of these kinds.
+    pub kinds: Vec<EngineEventKind>,
+}
+
+impl EngineEventFilter {
+    /// A filter that matches every event.
+    pub fn all() -> Self {
+        Self::default()
+    }
+    pub fn with_graph(mut self, graph: uuid::Uuid) -> Self {
+        self.graph = Some(graph);
+        self
+    }
+    pub fn with_instance(mut self, instance: uuid::Uuid) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+    pub fn with_kind(mut self, kind: EngineEventKind) -> Self {
+        self.kinds.push(kind);
+        self
+    }
+    pub fn matches(&self, event: &EngineEvent) -> bool {
+        if let Some(graph) = self.graph {
+            match &event.graph {
+                Some(event_graph) if event_graph.uuid == graph => {}
+                _ => return false,
+            }
+        }
+        if let Some(instance) = self.instance {
+            if event.instance != Some(instance) {
+                return false;
+            }
+        }
+        self.kinds.is_empty() || self.kinds.contains(&event.kind())
+    }
+}
+
+///
+/// Fans out engine events to every interested subscriber.
+///
+#[derive(Default)]
+pub struct EventBus {
+    subscribers: Vec<(EngineEventFilter, Sender<EngineEvent>)>,
+    /// How many subscribers there are, shared with node actors so they can skip publishing when nobody listens.
+    count: Arc<AtomicUsize>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Adds a subscriber and gives back the channel its events will arrive on.
+    pub fn subscribe(&mut self, filter: EngineEventFilter) -> Receiver<EngineEvent> {
+        let (send, recv) = crossbeam::unbounded();
+        self.subscribers.push((filter, send));
+        self.count.store(self.subscribers.len(), Ordering::Relaxed);
+        recv
+    }
+    /// Sends the event to every matching subscriber, dropping the ones that have hung up.
+    pub fn publish(&mut self, event: EngineEvent) {
+        self.subscribers.retain(|(filter, send)| {
+            if filter.matches(&event) {
+                send.send(event.clone()).is_ok()
+            } else {
+                true
+            }
+        });
+        self.count.store(self.subscribers.len(), Ordering::Relaxed);
+    }
+    /// The number of subscribers, kept up to date as they subscribe and hang up.
+    pub fn subscribers(&self) -> Arc<AtomicUsize> {
+        self.count.clone()
+    }
+}
@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 
+/// The datatype of pins that take or give values of every datatype, like the pins of flow control nodes.
+pub const ANY_DATATYPE: &str = "any";
+
 ///
 /// Information about a pin.
 ///
@@ -47,6 +50,13 @@ impl PinInfo {
             expandable,
         }
     }
+    ///
+    /// Whether a value of a datatype can be set on or computed from this pin.
+    /// Pins of the any datatype work with every datatype, and values of the any datatype work with every pin.
+    ///
+    pub fn accepts(&self, datatype: &str) -> bool {
+        self.datatype == datatype || self.datatype == ANY_DATATYPE || datatype == ANY_DATATYPE
+    }
 }
 
 ///
@@ -58,12 +68,121 @@ pub struct GraphRef {
     pub name: String,
     pub uuid: uuid::Uuid,
     pub library: Option<uuid::Uuid>,
-    pub version: u64,
+    /// Which version of the graph to use, resolved by the catalogue.
+    pub version: VersionSpec,
+}
+
+///
+/// Specifies which version of a graph a reference points to.
+/// Written in files as a number for an exact version, "latest", or "^" and a version number for the latest version compatible with it.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VersionSpec {
+    /// This version and no other.
+    Exact(u64),
+    /// The highest version that exists.
+    Latest,
+    /// The highest version whose pins are compatible with this version, meaning nothing connected to this version breaks.
+    LatestCompatible(u64),
+}
+
+impl VersionSpec {
+    /// The version number, if this is an exact version.
+    pub fn exact(&self) -> Option<u64> {
+        match self {
+            VersionSpec::Exact(version) => Some(*version),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for VersionSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VersionSpec::Exact(version) => write!(f, "{}", version),
+            VersionSpec::Latest => write!(f, "latest"),
+            VersionSpec::LatestCompatible(version) => write!(f, "^{}", version),
+        }
+    }
+}
+
+impl std::str::FromStr for VersionSpec {
+    type Err = String;
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let number = |number: &str| {
+            number
+                .trim()
+                .parse::<u64>()
+                .map_err(|e| format!("could not read version {}: {}", text, e))
+        };
+        match text {
+            "latest" => Ok(VersionSpec::Latest),
+            _ if text.starts_with('^') => Ok(VersionSpec::LatestCompatible(number(&text[1..])?)),
+            _ => Ok(VersionSpec::Exact(number(text)?)),
+        }
+    }
+}
+
+impl From<u64> for VersionSpec {
+    fn from(version: u64) -> Self {
+        VersionSpec::Exact(version)
+    }
+}
+
+impl Serialize for VersionSpec {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match (serializer.is_human_readable(), self) {
+            (true, VersionSpec::Exact(version)) => serializer.serialize_u64(*version),
+            _ => serializer.serialize_str(&self.to_string()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VersionSpec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct VersionSpecVisitor;
+        impl<'de> serde::de::Visitor<'de> for VersionSpecVisitor {
+            type Value = VersionSpec;
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "a version number, \"latest\", or \"^\" and a version number")
+            }
+            fn visit_u64<E>(self, version: u64) -> Result<Self::Value, E> {
+                Ok(VersionSpec::Exact(version))
+            }
+            fn visit_i64<E>(self, version: i64) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match version >= 0 {
+                    true => Ok(VersionSpec::Exact(version as u64)),
+                    false => Err(E::custom(format!("version {} is negative", version))),
+                }
+            }
+            fn visit_str<E>(self, text: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                text.parse().map_err(E::custom)
+            }
+        }
+        match deserializer.is_human_readable() {
+            true => deserializer.deserialize_any(VersionSpecVisitor),
+            false => deserializer.deserialize_str(VersionSpecVisitor),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Datum {
     pub name: String,
+    #[serde(with = "crate::encoding::value")]
     pub value: serde_json::Value,
 }
 
@@ -82,6 +201,8 @@ pub struct NodeInfo {
     pub y: f32,
     /// Data variables used by the node to store data that is not held on any inputs, including large array data, matrix data, and a string or many strings pointing to relative file paths or not-recommended absolute file paths of data files.
     /// Basically just whatever the node needs to hold.
+    /// Relative file paths are relative to the version of the graph holding the node, see `AssetContext`.
+    #[serde(with = "crate::encoding::value_map")]
     pub data: HashMap<String, serde_json::Value>,
     /// The reference to the graph that this node instance represents.
     pub graph: GraphRef,
@@ -102,9 +223,42 @@ pub struct PinRef {
     /// This defaults to true for both inputs and outputs.
     pub cache: Option<bool>,
     /// A direct value input from the user.
+    #[serde(default, with = "crate::encoding::value_option")]
     pub value: Option<serde_json::Value>,
 }
 
+impl PinRef {
+    pub fn new_pin(node: uuid::Uuid, pin: uuid::Uuid) -> Self {
+        Self {
+            node,
+            pin: Some(pin),
+            property: None,
+            cache: None,
+            value: None,
+        }
+    }
+    ///
+    /// References one of the pins of the graph itself, rather than a pin of a node within it.
+    ///
+    pub fn new_boundary(pin: uuid::Uuid) -> Self {
+        Self::new_pin(GRAPH_BOUNDARY_NODE, pin)
+    }
+    ///
+    /// Whether this references one of the pins of the graph itself.
+    ///
+    pub fn is_boundary(&self) -> bool {
+        self.node == GRAPH_BOUNDARY_NODE
+    }
+}
+
+///
+/// The node id that pin references use to point at the pins of the graph they are in, rather than a node within it.
+/// From inside the graph, graph inputs act as outputs and graph receives act as sends, and the other way around.
+/// So a graph input is connected with a boundary reference as the connection's output, and a graph output with one as the connection's input.
+/// Likewise a graph receive is a boundary reference in the connection's sends, and a graph send one in the connection's receives.
+///
+pub const GRAPH_BOUNDARY_NODE: uuid::Uuid = uuid::Uuid::nil();
+
 ///
 /// References to two connected pins.
 ///
@@ -122,6 +276,8 @@ pub struct ConnectionInfo {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct VersionInfo {
     pub format: u16,
+    /// The seed that every node's randomness within this graph version is derived from.
+    pub seed: Option<u64>,
     pub receives: Vec<PinInfo>,
     pub sends: Vec<PinInfo>,
     pub inputs: Vec<PinInfo>,
@@ -130,6 +286,129 @@ pub struct VersionInfo {
     pub connections: Vec<ConnectionInfo>,
 }
 
+impl VersionInfo {
+    ///
+    /// Finds the output that an input is connected to, which may be a graph input at the boundary.
+    ///
+    pub fn output_for_input(&self, input: &PinRef) -> Option<&PinRef> {
+        self.connections.iter().find_map(|connection| match (&connection.output, &connection.input) {
+            (Some(output), Some(connected)) if connected.node == input.node && connected.pin == input.pin => Some(output),
+            _ => None,
+        })
+    }
+    ///
+    /// Finds every receive that a send is connected to, which may include graph sends at the boundary.
+    ///
+    pub fn receives_for_send(&self, send: &PinRef) -> Vec<&PinRef> {
+        self.connections
+            .iter()
+            .filter_map(|connection| match (&connection.sends, &connection.receives) {
+                (Some(connected), Some(receive)) if connected.node == send.node && connected.pin == send.pin => Some(receive),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+///
+/// The kinds of pins a graph has.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PinKind {
+    Input,
+    Output,
+    Receive,
+    Send,
+}
+
+///
+/// A difference between the pins of two versions of a graph.
+/// Pins are matched by their id, so a name can change without breaking anything.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum PinChange {
+    /// A pin exists only in the newer version.
+    Added(PinKind, PinInfo),
+    /// A pin exists only in the older version.
+    Removed(PinKind, PinInfo),
+    /// A pin has a new name.
+    /// First pin is the older one, second the newer one.
+    Renamed(PinKind, PinInfo, PinInfo),
+    /// A pin has a new datatype, number of dimensions, or is expandable where it was not or the other way around.
+    /// First pin is the older one, second the newer one.
+    Retyped(PinKind, PinInfo, PinInfo),
+}
+
+impl PinChange {
+    ///
+    /// Whether this change breaks connections or values made against the older version.
+    /// Removed and retyped pins break, while new pins are simply left unconnected.
+    ///
+    pub fn is_breaking(&self) -> bool {
+        match self {
+            PinChange::Added(_, _) | PinChange::Renamed(_, _, _) => false,
+            PinChange::Removed(_, _) | PinChange::Retyped(_, _, _) => true,
+        }
+    }
+}
+
+impl std::fmt::Display for PinChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PinChange::Added(kind, pin) => write!(f, "{:?} pin {} ({}) was added", kind, pin.name, pin.uuid),
+            PinChange::Removed(kind, pin) => write!(f, "{:?} pin {} ({}) was removed", kind, pin.name, pin.uuid),
+            PinChange::Renamed(kind, old, new) => write!(f, "{:?} pin {} ({}) was renamed to {}", kind, old.name, old.uuid, new.name),
+            PinChange::Retyped(kind, old, new) => write!(f, "{:?} pin {} ({}) changed from datatype {} to {}", kind, old.name, old.uuid, old.datatype, new.datatype),
+        }
+    }
+}
+
+///
+/// Compares the pins of one kind between two versions of a graph.
+///
+fn compare_pins(kind: PinKind, old: &[PinInfo], new: &[PinInfo]) -> Vec<PinChange> {
+    let mut changes = Vec::new();
+    for old_pin in old {
+        match new.iter().find(|new_pin| new_pin.uuid == old_pin.uuid) {
+            Some(new_pin) => {
+                if old_pin.datatype != new_pin.datatype
+                    || old_pin.dimensions.unwrap_or(1) != new_pin.dimensions.unwrap_or(1)
+                    || old_pin.expandable.unwrap_or(false) != new_pin.expandable.unwrap_or(false)
+                {
+                    changes.push(PinChange::Retyped(kind, old_pin.clone(), new_pin.clone()));
+                } else if old_pin.name != new_pin.name {
+                    changes.push(PinChange::Renamed(kind, old_pin.clone(), new_pin.clone()));
+                }
+            }
+            None => changes.push(PinChange::Removed(kind, old_pin.clone())),
+        }
+    }
+    for new_pin in new {
+        if !old.iter().any(|old_pin| old_pin.uuid == new_pin.uuid) {
+            changes.push(PinChange::Added(kind, new_pin.clone()));
+        }
+    }
+    changes
+}
+
+///
+/// Gives back every difference between the pins of two versions of a graph, ordered by kind of pin.
+///
+pub fn pin_changes(old: &VersionInfo, new: &VersionInfo) -> Vec<PinChange> {
+    let mut changes = compare_pins(PinKind::Input, &old.inputs, &new.inputs);
+    changes.append(&mut compare_pins(PinKind::Output, &old.outputs, &new.outputs));
+    changes.append(&mut compare_pins(PinKind::Receive, &old.receives, &new.receives));
+    changes.append(&mut compare_pins(PinKind::Send, &old.sends, &new.sends));
+    changes
+}
+
+///
+/// Whether everything connected to the older version of a graph still works with the newer version.
+///
+pub fn is_compatible(old: &VersionInfo, new: &VersionInfo) -> bool {
+    !pin_changes(old, new).iter().any(|change| change.is_breaking())
+}
+
 ///
 /// Information about a graph as a whole, regardless of version.
 ///
@@ -140,70 +419,42 @@ pub struct GraphInfo {
     pub format: u16,
 }
 
+use super::encoding::*;
 use super::library::*;
+use super::package::*;
 use std::path::PathBuf;
 
 use log::*;
 
+///
+/// Reads a version of a graph in a library, the first time it is needed.
+/// The library may be a folder or a mounted package, and the version may be stored as json or binary, see `decode_version`.
+///
 pub fn get_graph_version_from_library(
     lib: &Library,
     id: uuid::Uuid,
     version: u64,
 ) -> Option<VersionInfo> {
-    match lib.graphs.get(&id) {
-        Some(pair) => {
-            let graph_path = &pair.path;
-            let graph_info = &pair.info;
-            let version_path = graph_path.join(PathBuf::from(format!("{}", version)));
-            match version_path.is_dir() {
-                true => {
-                    let version_json = version_path.join(PathBuf::from("version.json"));
-                    match version_json.is_file() {
-                        true => match std::fs::read_to_string(&version_json) {
-                            Ok(json) => match serde_json::from_str::<VersionInfo>(&json) {
-                                Ok(info) => Some(info),
-                                Err(e) => {
-                                    error!(
-                                        "could not read {} version json: {}",
-                                        version_json.display(),
-                                        e
-                                    );
-                                    None
-                                }
-                            },
-                            Err(e) => {
-                                error!(
-                                    "could not read {} version file: {}",
-                                    version_json.display(),
-                                    e
-                                );
-                                None
-                            }
-                        },
-                        false => {
-                            error!(
-                                "version.json file does not exist in the {} directory",
-                                version_path.display()
-                            );
-                            None
-                        }
-                    }
-                }
-                false => {
-                    error!(
-                        "version directory {} does not exist for {} graph with id of {}",
-                        version_path.display(),
-                        graph_info.name,
-                        graph_info.uuid
-                    );
-                    None
-                }
+    if let Some(info) = lib.loaded_versions.get(id, version) {
+        return Some(info);
+    }
+    let version_path = version_file(lib, id, version)?;
+    match read_library_file(&version_path) {
+        Ok(bytes) => match decode_version(&bytes) {
+            Ok(info) => {
+                lib.loaded_versions.insert(id, version, info.clone());
+                Some(info)
             }
-        }
-        _ => {
-            info!(
-                "graph with id of {} does not exist in the {} library with id of {}",
-                id, lib.info.name, lib.info.uuid
+            Err(e) => {
+                error!("could not read {} version: {}", version_path.display(), e);
+                None
+            }
+        },
+        Err(e) => {
+            error!(
+                "could not read {} version file: {}",
+                version_path.display(),
+                e
             );
             None
         }
@@ -211,44 +462,41 @@ pub fn get_graph_version_from_library(
 }
 
 pub fn has_graph_version_from_library(lib: &Library, id: uuid::Uuid, version: u64) -> bool {
+    match version_file(lib, id, version) {
+        Some(version_json) => match library_file_exists(&version_json) {
+            true => true,
+            false => {
+                error!(
+                    "version file {} does not exist for graph with id of {}",
+                    version_json.display(),
+                    id
+                );
+                false
+            }
+        },
+        None => false,
+    }
+}
+
+///
+/// The path of the file holding a version of a graph in a library.
+/// This is the version.bin when there is one, and the version.json otherwise.
+///
+fn version_file(lib: &Library, id: uuid::Uuid, version: u64) -> Option<PathBuf> {
     match lib.graphs.get(&id) {
         Some(pair) => {
-            let graph_path = &pair.path;
-            let graph_info = &pair.info;
-            let version_path = graph_path.join(PathBuf::from(format!("{}", version)));
-            match version_path.is_dir() {
-                true => {
-                    let version_json = version_path.join(PathBuf::from("version.json"));
-                    match version_json.is_file() {
-                        true => true,
-                        false => {
-                            error!(
-                                "version file {} does not exist for {} graph with id of {}",
-                                version_json.display(),
-                                graph_info.name,
-                                graph_info.uuid
-                            );
-                            false
-                        }
-                    }
-                }
-                false => {
-                    error!(
-                        "version directory {} does not exist for {} graph with id of {}",
-                        version_path.display(),
-                        graph_info.name,
-                        graph_info.uuid
-                    );
-                    false
-                }
+            let version_dir = pair.path.join(PathBuf::from(format!("{}", version)));
+            match library_file_exists(&version_dir.join(VERSION_BIN)) {
+                true => Some(version_dir.join(VERSION_BIN)),
+                false => Some(version_dir.join(VERSION_JSON)),
             }
         }
         _ => {
-            error!(
+            info!(
                 "graph with id of {} does not exist in the {} library with id of {}",
                 id, lib.info.name, lib.info.uuid
             );
-            false
+            None
         }
     }
 }
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use log::*;
+
+use super::library::*;
+
+/// The format of the index file, which is thrown away when it does not match.
+pub const INDEX_FORMAT: u32 = 2;
+
+///
+/// A search path as it was last scanned.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IndexedSearchPath {
+    /// Stamps of the folders walked to find the libraries.
+    pub stamps: Stamps,
+    /// The library folders found.
+    pub libraries: Vec<PathBuf>,
+}
+
+///
+/// A library as it was last scanned.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct IndexedLibrary {
+    /// Stamps of everything that was read to find the library's graphs.
+    pub stamps: Stamps,
+    pub library: Library,
+}
+
+///
+/// What scanning the library search paths found, kept between runs so that only what changed is scanned again.
+/// Entries are trusted for as long as the modification times and sizes of the files and folders they were read from stay the same.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LibraryIndex {
+    pub format: u32,
+    pub search_paths: HashMap<PathBuf, IndexedSearchPath>,
+    pub libraries: HashMap<PathBuf, IndexedLibrary>,
+}
+
+impl Default for LibraryIndex {
+    fn default() -> Self {
+        LibraryIndex {
+            format: INDEX_FORMAT,
+            search_paths: HashMap::new(),
+            libraries: HashMap::new(),
+        }
+    }
+}
+
+impl LibraryIndex {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Reads an index file, or gives an empty index when there is no usable one.
+    ///
+    pub fn load(path: &Path) -> Self {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                info!("no library index read from {}: {}", path.display(), e);
+                return LibraryIndex::new();
+            }
+        };
+        match bincode::deserialize::<LibraryIndex>(&bytes) {
+            Ok(index) if index.format == INDEX_FORMAT => index,
+            Ok(index) => {
+                info!("library index {} has format {}, so it is rebuilt", path.display(), index.format);
+                LibraryIndex::new()
+            }
+            Err(e) => {
+                warn!("could not parse library index {}, so it is rebuilt: {}", path.display(), e);
+                LibraryIndex::new()
+            }
+        }
+    }
+
+    ///
+    /// Writes the index to a file, replacing it whole so a reader never sees half of it.
+    ///
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("could not create folder for library index {}: {}", path.display(), e))?;
+        }
+        let bytes = bincode::serialize(self)
+            .map_err(|e| format!("could not serialize library index: {}", e))?;
+        // Each save writes its own file, so processes saving at the same time do not write over each other.
+        let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+        let partial = path.with_file_name(format!("{}.{}.{}.partial", name, std::process::id(), uuid::Uuid::new_v4().to_simple()));
+        if let Err(e) = std::fs::write(&partial, bytes) {
+            let _ = std::fs::remove_file(&partial);
+            return Err(format!("could not write library index {}: {}", partial.display(), e));
+        }
+        std::fs::rename(&partial, path).map_err(|e| {
+            let _ = std::fs::remove_file(&partial);
+            format!("could not replace library index {}: {}", path.display(), e)
+        })
+    }
+
+    ///
+    /// Gives the libraries in a search path, using this index for whatever has not changed and scanning the rest.
+    /// Everything found is recorded in the next index, so entries for paths that are no longer searched are dropped.
+    ///
+    pub fn scan(&self, search_path: &Path, next: &mut LibraryIndex) -> Vec<Library> {
+        let indexed = match self.search_paths.get(search_path) {
+            Some(indexed) if stamps_unchanged(&indexed.stamps) => indexed.clone(),
+            _ => {
+                let (libraries, stamps) = find_library_dirs(search_path);
+                IndexedSearchPath { stamps, libraries }
+            }
+        };
+        let scanned: Vec<(PathBuf, IndexedLibrary, bool)> = indexed
+            .libraries
+            .par_iter()
+            .filter_map(|dir| match self.libraries.get(dir) {
+                Some(cached) if stamps_unchanged(&cached.stamps) => Some((dir.clone(), cached.clone(), true)),
+                _ => scan_library(dir).map(|(library, stamps)| (dir.clone(), IndexedLibrary { stamps, library }, false)),
+            })
+            .collect();
+        let reused = scanned.iter().filter(|(_, _, reused)| *reused).count();
+        info!(
+            "found {} libraries in {}, {} of them unchanged since they were indexed",
+            scanned.len(),
+            search_path.display(),
+            reused
+        );
+        next.search_paths.insert(search_path.to_path_buf(), indexed);
+        let mut libs = Vec::new();
+        for (dir, indexed_library, _) in scanned {
+            libs.push(indexed_library.library.clone());
+            next.libraries.insert(dir, indexed_library);
+        }
+        libs
+    }
+}
@@ -1,11 +1,20 @@
+pub mod asset;
 pub mod catalogue;
+pub mod encoding;
 pub mod graph;
+pub mod index;
 pub mod library;
+pub mod metrics;
+pub mod package;
 
 pub mod node;
 
 pub mod nodes;
+pub mod seed;
+pub mod trace;
+pub mod watcher;
 
 pub mod engine;
+pub mod event;
 
 pub use axiom;
\ No newline at end of file
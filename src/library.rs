@@ -1,10 +1,16 @@
 use serde::{Deserialize, Serialize};
 
+use rayon::prelude::*;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use log::*;
 
+use super::encoding::*;
 use super::graph::*;
+use super::package::*;
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct LibraryInfo {
@@ -12,6 +18,41 @@ pub struct LibraryInfo {
     pub uuid: uuid::Uuid,
     pub author: String,
     pub format: u64,
+    /// The version of the library itself, which dependencies are checked against.
+    #[serde(default = "default_library_version")]
+    pub version: u64,
+    /// Other libraries that graphs in this library use.
+    #[serde(default)]
+    pub dependencies: Vec<LibraryDependency>,
+}
+
+fn default_library_version() -> u64 {
+    1
+}
+
+///
+/// A library that another library needs, and which of its versions work.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LibraryDependency {
+    /// The id of the library that is needed.
+    pub uuid: uuid::Uuid,
+    /// The name of the library, to help find it when it is missing.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// The lowest version of the library that works, if there is one.
+    #[serde(default)]
+    pub min_version: Option<u64>,
+    /// The highest version of the library that works, if there is one.
+    #[serde(default)]
+    pub max_version: Option<u64>,
+}
+
+impl LibraryDependency {
+    /// Whether a version of the library works for this dependency.
+    pub fn allows(&self, version: u64) -> bool {
+        self.min_version.map_or(true, |min| version >= min) && self.max_version.map_or(true, |max| version <= max)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
@@ -26,62 +67,82 @@ pub struct Library {
     pub info: LibraryInfo,
     pub path: PathBuf,
     pub graphs: std::collections::HashMap<uuid::Uuid, LibraryGraphInfo>,
+    /// Versions of graphs in this library that have been read, so each is only read once.
+    #[serde(skip)]
+    pub loaded_versions: VersionCache,
 }
 
-pub fn get_library_graphs(
-    library_path: PathBuf,
-) -> std::collections::HashMap<uuid::Uuid, LibraryGraphInfo> {
-    let mut graphs = std::collections::HashMap::new();
-    for entry in walkdir::WalkDir::new(&library_path) {
+///
+/// Versions of graphs that have been read from a library, shared between clones of the library.
+///
+#[derive(Clone, Debug, Default)]
+pub struct VersionCache(Arc<Mutex<HashMap<(uuid::Uuid, u64), VersionInfo>>>);
+
+impl VersionCache {
+    pub fn get(&self, id: uuid::Uuid, version: u64) -> Option<VersionInfo> {
+        self.0.lock().unwrap().get(&(id, version)).cloned()
+    }
+    pub fn insert(&self, id: uuid::Uuid, version: u64, info: VersionInfo) {
+        self.0.lock().unwrap().insert((id, version), info);
+    }
+}
+
+///
+/// The modification times and sizes of files and folders, or none for ones that did not exist.
+/// A scan of the same folders finds the same things for as long as these do not change.
+///
+pub type Stamps = Vec<(PathBuf, Option<(SystemTime, u64)>)>;
+
+///
+/// The modification time and size of a file or folder, if it exists.
+/// The size catches edits made within the resolution of the modification time.
+///
+pub fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    std::fs::metadata(path).and_then(|metadata| Ok((metadata.modified()?, metadata.len()))).ok()
+}
+
+///
+/// Whether every file and folder is as it was when stamped.
+///
+pub fn stamps_unchanged(stamps: &Stamps) -> bool {
+    stamps.par_iter().all(|(path, time)| stamp(path) == *time)
+}
+
+///
+/// Whether a folder is a numbered version folder of a graph.
+///
+fn is_version_dir(path: &Path) -> bool {
+    let numbered = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map_or(false, |name| name.trim().parse::<u64>().is_ok());
+    numbered && path.parent().map_or(false, |parent| parent.join("graph.json").is_file())
+}
+
+///
+/// Finds the graphs in a library, and stamps everything that would change what is found.
+/// The contents of version folders are not searched, as they only hold the version and its assets.
+///
+pub fn scan_library_graphs(library_path: &Path) -> (HashMap<uuid::Uuid, LibraryGraphInfo>, Stamps) {
+    let mut stamps = Stamps::new();
+    let mut graph_dirs = Vec::new();
+    let mut walker = walkdir::WalkDir::new(library_path).sort_by(|a, b| a.file_name().cmp(b.file_name())).into_iter();
+    while let Some(entry) = walker.next() {
         match entry {
             Ok(entry) => {
                 let entry = entry.path();
-                if entry.is_dir() {
-                    let graphjson = entry.join(Path::new("graph.json"));
-                    if graphjson.is_file() {
-                        match std::fs::read_to_string(&graphjson) {
-                            Ok(json) => match serde_json::from_str::<GraphInfo>(&json) {
-                                Ok(info) => {
-                                    let name = info.name.clone();
-                                    let mut versions: u64 = 0;
-                                    for version_entry in
-                                        walkdir::WalkDir::new(&entry).min_depth(1).max_depth(1)
-                                    {
-                                        if let Ok(version_entry) = version_entry {
-                                            if version_entry.path().is_dir() {
-                                                let vestr = version_entry.file_name().to_str();
-                                                if let Some(vestr) = vestr {
-                                                    if String::from(vestr)
-                                                        .trim()
-                                                        .parse::<u64>()
-                                                        .is_ok()
-                                                    {
-                                                        versions += 1;
-                                                    }
-                                                }
-                                            }
-                                        }
-                                    }
-                                    graphs.insert(
-                                        info.uuid,
-                                        LibraryGraphInfo {
-                                            path: entry.to_path_buf(),
-                                            info: info,
-                                            versions: versions.clone(),
-                                        },
-                                    );
-                                    info!(
-                                        "added graph info {}: {} with {} versions",
-                                        entry.display(),
-                                        name,
-                                        versions
-                                    );
-                                }
-                                Err(e) => error!("could not parse {}: {}", graphjson.display(), e),
-                            },
-                            Err(e) => error!("could not open {}: {}", graphjson.display(), e),
-                        }
-                    }
+                if !entry.is_dir() {
+                    continue;
+                }
+                stamps.push((entry.to_path_buf(), stamp(entry)));
+                if is_version_dir(entry) {
+                    walker.skip_current_dir();
+                    continue;
+                }
+                let graphjson = entry.join(Path::new("graph.json"));
+                if graphjson.is_file() {
+                    stamps.push((graphjson.clone(), stamp(&graphjson)));
+                    graph_dirs.push(entry.to_path_buf());
                 }
             }
             Err(e) => warn!(
@@ -91,35 +152,129 @@ pub fn get_library_graphs(
             ),
         }
     }
-    graphs
+    let graphs = graph_dirs
+        .par_iter()
+        .filter_map(|graph_path| read_graph(graph_path))
+        .map(|graph| (graph.info.uuid, graph))
+        .collect();
+    (graphs, stamps)
 }
 
-pub fn get_libraries(libraries_path: PathBuf) -> Vec<Library> {
-    let mut libs = Vec::new();
+///
+/// Reads the graph.json of a graph folder and counts its versions.
+///
+fn read_graph(graph_path: &Path) -> Option<LibraryGraphInfo> {
+    let graphjson = graph_path.join(Path::new("graph.json"));
+    let info = match std::fs::read_to_string(&graphjson) {
+        Ok(json) => match serde_json::from_str::<GraphInfo>(&json) {
+            Ok(info) => info,
+            Err(e) => {
+                error!("could not parse {}: {}", graphjson.display(), e);
+                return None;
+            }
+        },
+        Err(e) => {
+            error!("could not open {}: {}", graphjson.display(), e);
+            return None;
+        }
+    };
+    let versions = match std::fs::read_dir(graph_path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_dir())
+            .filter(|entry| entry.file_name().to_str().map_or(false, |name| name.trim().parse::<u64>().is_ok()))
+            .filter(|entry| has_version_file(&entry.path()))
+            .count() as u64,
+        Err(e) => {
+            error!("could not read versions of graph {}: {}", graph_path.display(), e);
+            0
+        }
+    };
+    info!(
+        "added graph info {}: {} with {} versions",
+        graph_path.display(),
+        info.name,
+        versions
+    );
+    Some(LibraryGraphInfo {
+        path: graph_path.to_path_buf(),
+        info,
+        versions,
+    })
+}
+
+pub fn get_library_graphs(
+    library_path: PathBuf,
+) -> std::collections::HashMap<uuid::Uuid, LibraryGraphInfo> {
+    scan_library_graphs(&library_path).0
+}
+
+///
+/// Gives back the version numbers of a graph that exist, in ascending order.
+/// Internal graphs are numbered from one up to their count of versions, and graphs in directories have a numbered directory holding a version.json or version.bin for each.
+///
+pub fn get_graph_versions(graph: &LibraryGraphInfo) -> Vec<u64> {
+    if graph.path.as_os_str().is_empty() {
+        return (1..=graph.versions).collect();
+    }
+    if !graph.path.is_dir() {
+        return match list_package_dir(&graph.path) {
+            Ok(names) => {
+                let mut versions: Vec<u64> = names
+                    .iter()
+                    .filter_map(|name| name.trim().parse::<u64>().ok())
+                    .filter(|version| has_version_file(&graph.path.join(format!("{}", version))))
+                    .collect();
+                versions.sort();
+                versions
+            }
+            Err(e) => {
+                error!("could not read versions of graph {}: {}", graph.path.display(), e);
+                Vec::new()
+            }
+        };
+    }
+    let mut versions: Vec<u64> = match std::fs::read_dir(&graph.path) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| has_version_file(&entry.path()))
+            .filter_map(|entry| entry.file_name().to_str().and_then(|name| name.trim().parse::<u64>().ok()))
+            .collect(),
+        Err(e) => {
+            error!("could not read versions of graph {}: {}", graph.path.display(), e);
+            Vec::new()
+        }
+    };
+    versions.sort();
+    versions
+}
+
+///
+/// Finds the library folders and packages in a search path, and stamps the folders walked to find them.
+/// Folders within a library are not searched for more libraries.
+///
+pub fn find_library_dirs(libraries_path: &Path) -> (Vec<PathBuf>, Stamps) {
+    let mut dirs = Vec::new();
+    let mut stamps = vec![(libraries_path.to_path_buf(), stamp(libraries_path))];
     info!("searching in {} for libraries", libraries_path.display());
-    for entry in walkdir::WalkDir::new(&libraries_path) {
+    let mut walker = walkdir::WalkDir::new(libraries_path).sort_by(|a, b| a.file_name().cmp(b.file_name())).into_iter();
+    while let Some(entry) = walker.next() {
         match entry {
             Ok(entry) => {
                 let entry = entry.path();
-                if entry.is_dir() {
-                    let libjson = entry.to_path_buf().join(Path::new("lib.json"));
-                    trace!("walking {}", entry.display());
-                    if libjson.is_file() {
-                        match std::fs::read_to_string(&libjson) {
-                            Ok(json) => match serde_json::from_str::<LibraryInfo>(&json) {
-                                Ok(info) => {
-                                    let library_path = entry.to_path_buf().clone();
-                                    libs.push(Library {
-                                        info: info,
-                                        path: library_path.clone(),
-                                        graphs: get_library_graphs(library_path),
-                                    });
-                                }
-                                Err(e) => error!("could not parse {}: {}", libjson.display(), e),
-                            },
-                            Err(e) => error!("could not open {}: {}", libjson.display(), e),
-                        }
-                    }
+                if is_package(entry) {
+                    dirs.push(entry.to_path_buf());
+                    continue;
+                }
+                if !entry.is_dir() {
+                    continue;
+                }
+                trace!("walking {}", entry.display());
+                if entry.join(Path::new("lib.json")).is_file() {
+                    dirs.push(entry.to_path_buf());
+                    walker.skip_current_dir();
+                } else {
+                    stamps.push((entry.to_path_buf(), stamp(entry)));
                 }
             }
             Err(e) => warn!(
@@ -129,5 +284,53 @@ pub fn get_libraries(libraries_path: PathBuf) -> Vec<Library> {
             ),
         }
     }
-    libs
+    (dirs, stamps)
+}
+
+///
+/// Reads a library folder or package and the graphs in it, and stamps everything that would change what is read.
+///
+pub fn scan_library(library_path: &Path) -> Option<(Library, Stamps)> {
+    if is_package(library_path) {
+        return match mount(library_path) {
+            Ok(library) => Some((library, vec![(library_path.to_path_buf(), stamp(library_path))])),
+            Err(e) => {
+                error!("could not mount package: {}", e);
+                None
+            }
+        };
+    }
+    let libjson = library_path.join(Path::new("lib.json"));
+    let info = match std::fs::read_to_string(&libjson) {
+        Ok(json) => match serde_json::from_str::<LibraryInfo>(&json) {
+            Ok(info) => info,
+            Err(e) => {
+                error!("could not parse {}: {}", libjson.display(), e);
+                return None;
+            }
+        },
+        Err(e) => {
+            error!("could not open {}: {}", libjson.display(), e);
+            return None;
+        }
+    };
+    let (graphs, mut stamps) = scan_library_graphs(library_path);
+    stamps.push((libjson.clone(), stamp(&libjson)));
+    Some((
+        Library {
+            info,
+            path: library_path.to_path_buf(),
+            graphs,
+            loaded_versions: Default::default(),
+        },
+        stamps,
+    ))
+}
+
+pub fn get_libraries(libraries_path: PathBuf) -> Vec<Library> {
+    let (dirs, _) = find_library_dirs(&libraries_path);
+    dirs.par_iter()
+        .filter_map(|dir| scan_library(dir))
+        .map(|(lib, _)| lib)
+        .collect()
 }
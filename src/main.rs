@@ -6,6 +6,7 @@ extern crate proc_flow_lib as pf;
 extern crate log;
 extern crate simplelog;
 
+use clap::{App, Arg, SubCommand};
 use simplelog::*;
 
 fn main() {
@@ -24,7 +25,112 @@ fn main() {
     ])
     .unwrap();
     
-    let mut engine = pf::engine::Engine::new();
+    let matches = App::new("Proc Flow")
+        .arg(
+            Arg::with_name("library")
+                .long("library")
+                .short("l")
+                .value_name("PATH")
+                .help("Looks for libraries in a folder before any other, can be given more than once")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1),
+        )
+        .arg(
+            Arg::with_name("config")
+                .long("config")
+                .value_name("FILE")
+                .help("Reads the catalogue config from a file instead of the default one")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("no-default-libraries")
+                .long("no-default-libraries")
+                .help("Does not look for libraries in the working directory or the documents folder"),
+        )
+        .subcommand(
+            SubCommand::with_name("pack")
+                .about("Packs a library folder into a single package file")
+                .arg(Arg::with_name("library").required(true).help("The library folder, holding lib.json"))
+                .arg(Arg::with_name("package").required(true).help("The package file to write, usually ending in .pflib")),
+        )
+        .subcommand(
+            SubCommand::with_name("unpack")
+                .about("Unpacks a package file into a library folder")
+                .arg(Arg::with_name("package").required(true).help("The package file to read"))
+                .arg(Arg::with_name("library").required(true).help("The library folder to write")),
+        )
+        .subcommand(
+            SubCommand::with_name("verify")
+                .about("Checks that a package file matches its manifest")
+                .arg(Arg::with_name("package").required(true).help("The package file to check")),
+        )
+        .subcommand(
+            SubCommand::with_name("convert")
+                .about("Converts a graph version between json and binary, going by the extension of the file to write")
+                .arg(Arg::with_name("from").required(true).help("The version file to read, in either encoding"))
+                .arg(Arg::with_name("to").required(true).help("The version file to write, binary if it ends in .bin and json otherwise")),
+        )
+        .get_matches();
+
+    if let ("convert", Some(args)) = matches.subcommand() {
+        let (from, to) = (std::path::Path::new(args.value_of("from").unwrap()), std::path::Path::new(args.value_of("to").unwrap()));
+        match pf::encoding::convert_version_file(from, to) {
+            Ok(encoding) => {
+                info!("converted {} to {:?} as {}", from.display(), encoding, to.display());
+                return;
+            }
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let package_result = match matches.subcommand() {
+        ("pack", Some(args)) => Some(pf::package::pack(
+            std::path::Path::new(args.value_of("library").unwrap()),
+            std::path::Path::new(args.value_of("package").unwrap()),
+        )),
+        ("unpack", Some(args)) => Some(pf::package::unpack(
+            std::path::Path::new(args.value_of("package").unwrap()),
+            std::path::Path::new(args.value_of("library").unwrap()),
+        )),
+        ("verify", Some(args)) => Some(pf::package::verify(std::path::Path::new(args.value_of("package").unwrap()))),
+        _ => None,
+    };
+    match package_result {
+        Some(Ok(manifest)) => {
+            info!("library {} : {} version {} with {} files", manifest.library.uuid, manifest.library.name, manifest.library.version, manifest.files.len());
+            return;
+        }
+        Some(Err(e)) => {
+            error!("{}", e);
+            std::process::exit(1);
+        }
+        None => {}
+    }
+
+    let mut catalogue_config = match matches.value_of("config") {
+        Some(file) => match pf::catalogue::CatalogueConfig::from_file(std::path::Path::new(file)) {
+            Ok(config) => config.with_env(),
+            Err(e) => {
+                error!("{}", e);
+                std::process::exit(1);
+            }
+        },
+        None => pf::catalogue::CatalogueConfig::load(),
+    };
+    if let Some(paths) = matches.values_of("library") {
+        for path in paths.rev() {
+            catalogue_config = catalogue_config.with_first_path(path);
+        }
+    }
+    if matches.is_present("no-default-libraries") {
+        catalogue_config = catalogue_config.without_default_paths();
+    }
+
+    let mut engine = pf::engine::Engine::with_config(catalogue_config);
     let canvas_node_id = uuid::Uuid::parse_str("a795c3e9-0a2f-48bf-a9e2-03378e8e59b8").unwrap();
     let canvas_node_edit_recv_id = uuid::Uuid::parse_str("7c5c2794-eb60-4661-9d25-585e1226233e").unwrap();
     let canvas_actor = engine.boot_graph(canvas_node_id, 1, uuid::Uuid::new_v4());
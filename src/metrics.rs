@@ -0,0 +1,386 @@
+use crate::graph::*;
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+use log::*;
+
+/// Upper bounds of the compute latency histogram buckets, in microseconds.
+pub const LATENCY_BUCKETS: [u64; 12] = [
+    10, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 50_000, 250_000, 1_000_000,
+];
+
+///
+/// Counts of observed values that fall under each of the latency bucket bounds.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Histogram {
+    /// How many observations fell into each bucket, with one extra bucket at the end for anything larger.
+    /// These are not cumulative.
+    pub buckets: Vec<u64>,
+    /// The sum of every observation.
+    pub sum: u64,
+    /// How many observations were made.
+    pub count: u64,
+}
+
+impl Histogram {
+    pub fn new() -> Self {
+        Self {
+            buckets: vec![0; LATENCY_BUCKETS.len() + 1],
+            sum: 0,
+            count: 0,
+        }
+    }
+    pub fn observe(&mut self, value: u64) {
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| value <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        self.buckets[bucket] += 1;
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+///
+/// Aggregate compute numbers for a node type or a single node.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ComputeMetrics {
+    /// The name of the graph the nodes represent.
+    pub name: String,
+    /// How many outputs were requested, including cached ones.
+    pub computes: u64,
+    /// How many outputs were served from a pin's cache.
+    pub cache_hits: u64,
+    /// How many outputs failed to compute.
+    pub errors: u64,
+    /// How long outputs took, in microseconds.
+    pub latency: Histogram,
+}
+
+impl ComputeMetrics {
+    pub fn new(name: String) -> Self {
+        Self {
+            name,
+            computes: 0,
+            cache_hits: 0,
+            errors: 0,
+            latency: Histogram::new(),
+        }
+    }
+    /// The fraction of computes that were served from a cache.
+    pub fn cache_hit_rate(&self) -> f64 {
+        if self.computes == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / self.computes as f64
+        }
+    }
+    fn record(&mut self, duration: u64, cache_hit: bool, error: bool) {
+        self.computes += 1;
+        if cache_hit {
+            self.cache_hits += 1;
+        }
+        if error {
+            self.errors += 1;
+        }
+        self.latency.observe(duration);
+    }
+}
+
+///
+/// How many requests a node has waiting to be answered, such as those waiting on linked inputs or on nodes within it.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct QueueDepth {
+    /// The name of the graph the node represents.
+    pub name: String,
+    /// The UUID of the graph the node represents.
+    pub graph: uuid::Uuid,
+    /// The UUID of the graph the node is within, or the nil UUID when the engine booted it directly.
+    pub parent: uuid::Uuid,
+    /// How many requests are waiting now.
+    pub depth: u64,
+    /// The most requests that have waited at once.
+    pub peak: u64,
+}
+
+///
+/// Aggregate numbers for everything an engine has done since it was created.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Metrics {
+    /// Compute numbers per node type, keyed by the UUID of the graph the nodes represent.
+    pub node_types: HashMap<uuid::Uuid, ComputeMetrics>,
+    /// Compute numbers per node, keyed by the node's instance UUID.
+    pub nodes: HashMap<uuid::Uuid, ComputeMetrics>,
+    /// Compute numbers per node type within each graph, keyed by the UUID of the graph the nodes are within and then by the UUID of the graph the nodes represent.
+    /// Nodes booted directly by the engine are within the nil UUID.
+    pub graphs: HashMap<uuid::Uuid, HashMap<uuid::Uuid, ComputeMetrics>>,
+    /// Waiting requests per running node, keyed by the node's instance UUID.
+    pub queue_depths: HashMap<uuid::Uuid, QueueDepth>,
+    /// How many of each node command were handled by nodes, as counted by `MessageCounters` when the snapshot was taken.
+    pub node_messages: HashMap<String, u64>,
+    /// How many of each controller command were handled by the controller, as counted by `MessageCounters` when the snapshot was taken.
+    pub controller_messages: HashMap<String, u64>,
+}
+
+///
+/// Counts of handled commands by name, which any number of actors can count into at once.
+/// The write lock is only taken the first time a command is counted.
+///
+#[derive(Debug, Default)]
+pub struct CommandCounter {
+    counts: RwLock<HashMap<&'static str, AtomicU64>>,
+}
+
+impl CommandCounter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Counts one handled command.
+    pub fn count(&self, command: &'static str) {
+        if let Some(count) = self.counts.read().unwrap().get(command) {
+            count.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        self.counts
+            .write()
+            .unwrap()
+            .entry(command)
+            .or_insert_with(|| AtomicU64::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+    }
+    /// The counts so far.
+    pub fn snapshot(&self) -> HashMap<String, u64> {
+        self.counts
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(command, count)| (String::from(*command), count.load(Ordering::Relaxed)))
+            .collect()
+    }
+}
+
+///
+/// The commands handled by nodes and by the controller, kept apart from `Metrics` so that counting a command never waits on its lock.
+///
+#[derive(Debug, Default)]
+pub struct MessageCounters {
+    pub node: CommandCounter,
+    pub controller: CommandCounter,
+}
+
+impl MessageCounters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Copies the counts so far into a metrics snapshot.
+    pub fn fold_into(&self, metrics: &mut Metrics) {
+        metrics.node_messages = self.node.snapshot();
+        metrics.controller_messages = self.controller.snapshot();
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Records a single output compute of a node, within the graph with the parent UUID if it is in one.
+    /// Duration is in microseconds.
+    pub fn record_compute(&mut self, graph: &GraphRef, parent: Option<uuid::Uuid>, instance: &uuid::Uuid, duration: u64, cache_hit: bool, error: bool) {
+        self.graphs
+            .entry(parent.unwrap_or_else(uuid::Uuid::nil))
+            .or_insert_with(HashMap::new)
+            .entry(graph.uuid)
+            .or_insert_with(|| ComputeMetrics::new(graph.name.clone()))
+            .record(duration, cache_hit, error);
+        self.node_types
+            .entry(graph.uuid)
+            .or_insert_with(|| ComputeMetrics::new(graph.name.clone()))
+            .record(duration, cache_hit, error);
+        self.nodes
+            .entry(instance.clone())
+            .or_insert_with(|| ComputeMetrics::new(graph.name.clone()))
+            .record(duration, cache_hit, error);
+    }
+    /// Sets how many requests a node has waiting, within the graph with the parent UUID if it is in one.
+    pub fn record_queue_depth(&mut self, graph: &GraphRef, parent: Option<uuid::Uuid>, instance: &uuid::Uuid, depth: usize) {
+        let queue = self.queue_depths.entry(instance.clone()).or_insert_with(|| QueueDepth {
+            name: graph.name.clone(),
+            graph: graph.uuid,
+            parent: parent.unwrap_or_else(uuid::Uuid::nil),
+            depth: 0,
+            peak: 0,
+        });
+        queue.depth = depth as u64;
+        queue.peak = queue.peak.max(queue.depth);
+    }
+    /// Forgets the waiting requests of a node that has stopped.
+    pub fn remove_queue_depth(&mut self, instance: &uuid::Uuid) {
+        self.queue_depths.remove(instance);
+    }
+    /// Renders the metrics in the Prometheus text exposition format.
+    /// Only node types within each graph are exported, as per node numbers would explode the series count.
+    /// The `parent_graph` label is empty for nodes booted directly by the engine.
+    pub fn to_prometheus(&self) -> String {
+        let mut text = String::new();
+        let mut node_types: Vec<(String, &ComputeMetrics)> = self
+            .graphs
+            .iter()
+            .flat_map(|(parent, types)| types.iter().map(move |(id, metrics)| (type_labels(id, &metrics.name, parent), metrics)))
+            .collect();
+        node_types.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let counters: [(&str, &str, fn(&ComputeMetrics) -> u64); 3] = [
+            ("proc_flow_node_computes_total", "Outputs requested per node type.", |m| m.computes),
+            ("proc_flow_node_cache_hits_total", "Outputs served from a pin cache per node type.", |m| m.cache_hits),
+            ("proc_flow_node_errors_total", "Outputs that failed to compute per node type.", |m| m.errors),
+        ];
+        for (metric, help, value) in counters.iter() {
+            text.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n", metric, help, metric));
+            for (labels, metrics) in node_types.iter() {
+                text.push_str(&format!("{}{{{}}} {}\n", metric, labels, value(metrics)));
+            }
+        }
+
+        let metric = "proc_flow_node_compute_latency_microseconds";
+        text.push_str(&format!("# HELP {} Time taken to compute outputs per node type.\n# TYPE {} histogram\n", metric, metric));
+        for (labels, metrics) in node_types.iter() {
+            let mut cumulative: u64 = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(metrics.latency.buckets.iter()) {
+                cumulative += count;
+                text.push_str(&format!("{}_bucket{{{},le=\"{}\"}} {}\n", metric, labels, bound, cumulative));
+            }
+            text.push_str(&format!("{}_bucket{{{},le=\"+Inf\"}} {}\n", metric, labels, metrics.latency.count));
+            text.push_str(&format!("{}_sum{{{}}} {}\n", metric, labels, metrics.latency.sum));
+            text.push_str(&format!("{}_count{{{}}} {}\n", metric, labels, metrics.latency.count));
+        }
+
+        let metric = "proc_flow_node_pending_requests";
+        text.push_str(&format!("# HELP {} Requests waiting to be answered by running nodes, per node type.\n# TYPE {} gauge\n", metric, metric));
+        let mut depths: HashMap<String, u64> = HashMap::new();
+        for queue in self.queue_depths.values() {
+            *depths.entry(type_labels(&queue.graph, &queue.name, &queue.parent)).or_insert(0) += queue.depth;
+        }
+        let mut depths: Vec<(String, u64)> = depths.into_iter().collect();
+        depths.sort();
+        for (labels, depth) in depths {
+            text.push_str(&format!("{}{{{}}} {}\n", metric, labels, depth));
+        }
+
+        let message_counters = [
+            ("proc_flow_node_messages_total", "Commands handled by nodes.", &self.node_messages),
+            ("proc_flow_controller_messages_total", "Commands handled by the controller.", &self.controller_messages),
+        ];
+        for (metric, help, messages) in message_counters.iter() {
+            text.push_str(&format!("# HELP {} {}\n# TYPE {} counter\n", metric, help, metric));
+            let mut messages: Vec<(&String, &u64)> = messages.iter().collect();
+            messages.sort();
+            for (command, count) in messages {
+                text.push_str(&format!("{}{{command=\"{}\"}} {}\n", metric, escape_label(command), count));
+            }
+        }
+        text
+    }
+}
+
+fn type_labels(graph: &uuid::Uuid, name: &str, parent: &uuid::Uuid) -> String {
+    let parent = match parent.is_nil() {
+        true => String::new(),
+        false => parent.to_string(),
+    };
+    format!("graph=\"{}\",name=\"{}\",parent_graph=\"{}\"", graph, escape_label(name), parent)
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+///
+/// Serves the metrics in the Prometheus text format to anything that connects to the given address.
+/// Every request, regardless of path, gets the current metrics.
+/// Serving stops when the returned handle is stopped or dropped.
+///
+pub fn serve_prometheus(
+    metrics: Arc<Mutex<Metrics>>,
+    messages: Arc<MessageCounters>,
+    address: std::net::SocketAddr,
+) -> std::io::Result<MetricsServer> {
+    let listener = std::net::TcpListener::bind(address)?;
+    let address = listener.local_addr()?;
+    // Accepting without blocking lets the thread notice when it is asked to stop.
+    listener.set_nonblocking(true)?;
+    info!("serving prometheus metrics at {}", address);
+    let running = Arc::new(AtomicBool::new(true));
+    let thread_running = running.clone();
+    let thread = std::thread::spawn(move || {
+        while thread_running.load(Ordering::SeqCst) {
+            match listener.accept() {
+                Ok((mut stream, _)) => {
+                    let _ = stream.set_nonblocking(false);
+                    // The request itself does not matter, but it has to be read before replying.
+                    let mut request = [0; 1024];
+                    let _ = stream.read(&mut request);
+                    let mut snapshot = metrics.lock().unwrap().clone();
+                    messages.fold_into(&mut snapshot);
+                    let body = snapshot.to_prometheus();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    if let Err(e) = stream.write_all(response.as_bytes()) {
+                        warn!("could not write prometheus metrics to {:?}: {}", stream.peer_addr(), e);
+                    }
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(std::time::Duration::from_millis(50)),
+                Err(e) => warn!("could not accept prometheus metrics connection: {}", e),
+            }
+        }
+        info!("stopped serving prometheus metrics at {}", address);
+    });
+    Ok(MetricsServer {
+        address,
+        running,
+        thread: Some(thread),
+    })
+}
+
+///
+/// Handle to the thread serving Prometheus metrics, which stops it when dropped.
+///
+pub struct MetricsServer {
+    /// The address metrics are served at, with the port filled in if port 0 was asked for.
+    pub address: std::net::SocketAddr,
+    running: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl MetricsServer {
+    /// Stops serving, and waits for the serving thread to finish, which frees the port.
+    pub fn stop(mut self) {
+        self.halt();
+    }
+    fn halt(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for MetricsServer {
+    fn drop(&mut self) {
+        self.halt();
+    }
+}
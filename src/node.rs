@@ -1,5 +1,9 @@
+use crate::asset::*;
 use crate::catalogue::*;
+use crate::event::*;
 use crate::graph::*;
+use crate::metrics::*;
+use crate::trace::*;
 //use crate::engine::*;
 use axiom::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -46,6 +50,18 @@ pub trait Nodeable {
         _context: &Context,
         _message: &Message,
     ){}
+    /// Cleans up before the node actor stops, such as stopping the node actors it owns.
+    fn handle_stop(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+    ){}
+    /// Reloads anything read from the catalogue before the node's pins are read again, such as after the files of its graph changed.
+    fn handle_refresh(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+    ){}
 }
 
 ///
@@ -149,6 +165,71 @@ pub struct Node {
     pub catalogue: Arc<Mutex<Catalogue>>,
     /// Controller that this node belongs to.
     pub controller: Aid,
+    /// Execution tracer shared with the engine, handed over by the controller when the node is booted.
+    pub tracer: Option<Arc<Mutex<Tracer>>>,
+    /// Metrics shared with the engine, handed over by the controller when the node is booted.
+    pub metrics: Option<Arc<Mutex<Metrics>>>,
+    /// Command counts shared with the engine, handed over by the controller when the node is booted.
+    pub messages: Option<Arc<MessageCounters>>,
+    /// How many subscribers the engine's event bus has, handed over by the controller when the node is booted.
+    pub subscribers: Option<Arc<atomic::AtomicUsize>>,
+    /// Requests for pin values that are waiting on other node actors before they can be answered.
+    pub pending: Vec<PendingRequest>,
+    /// The input links whose values have been requested from the linked node actors, but have not arrived yet.
+    pub pulling: std::collections::HashSet<uuid::Uuid>,
+    /// Messages sent to receive pins that are waiting on linked inputs before the process handles them.
+    pub receiving: Vec<PendingReceive>,
+    /// Set by the process while computing an output, when it will give the value later with `resolve`.
+    deferring: bool,
+    /// How many requests were waiting when that was last recorded in the metrics.
+    recorded_depth: Option<usize>,
+    /// Where the relative asset paths in this node's data are looked up, set by the graph the node is in.
+    pub assets: AssetContext,
+    /// The UUID of the graph this node is within, set by that graph when it spawns the node.
+    pub parent: Option<uuid::Uuid>,
+}
+
+///
+/// Who asked for the value of a pin, and so how the value is sent back.
+///
+#[derive(Clone, Debug)]
+pub enum Requestor {
+    /// Another node wants the value for one of its inputs.
+    /// Aid is the node actor.
+    /// Id is its input pin.
+    /// String is the datatype it asked for.
+    Input(Aid, uuid::Uuid, String),
+    /// The controller or another actor wants the value back directly.
+    Commander(Aid),
+}
+
+///
+/// A request for the value of a pin that is waiting on other node actors.
+///
+#[derive(Clone, Debug)]
+pub struct PendingRequest {
+    /// Where the value goes once it is known.
+    pub requestor: Requestor,
+    /// The output pin, or the input pin when a node within this one reads it.
+    pub pin: uuid::Uuid,
+    /// The parameter to compute the output with.
+    pub parameter: Option<Message>,
+    /// Whether the process has already been asked to compute, and will give the value with `resolve`.
+    /// Otherwise the request waits for linked inputs.
+    pub deferred: bool,
+    /// When the request arrived, for metrics and tracing.
+    pub start: std::time::Instant,
+}
+
+///
+/// A message sent to a receive pin that is waiting on other node actors.
+///
+#[derive(Clone, Debug)]
+pub struct PendingReceive {
+    /// The receive pin the message was sent to.
+    pub receiver: uuid::Uuid,
+    /// The message itself.
+    pub message: Option<Message>,
 }
 
 impl Named for Node {
@@ -189,6 +270,11 @@ pub enum NodeCommand {
     /// String is the datatype sent.
     /// The message is the value.
     InputValue(Aid, uuid::Uuid, String, Option<Message>),
+    /// Tells a node that the value it asked for one of its inputs could not be computed.
+    /// Aid is the sending node.
+    /// Pin is the input pin.
+    /// String is the reason.
+    InputError(Aid, uuid::Uuid, String),
     /// Sends a message of some sort to a receiver.
     /// Aid is the sending node.
     /// First id is the sending pin.
@@ -217,8 +303,38 @@ pub enum NodeCommand {
     RemoveDatum(Aid, String),
     /// Forces a node to refresh what pins are available on it.
     RefreshPins(Aid),
+    /// Links one of this node's send or input pins to a pin on another node.
+    /// Aid is the requestor.
+    /// Id is the send or input pin on this node.
+    /// Second aid is the linked node actor.
+    /// Pin is the receive or output pin on the linked node.
+    LinkPin(Aid, uuid::Uuid, Aid, PinRef),
     /// Tells the node to tell the controller to tell the engine to stop waiting.
     StopWaitingForNewMessages,
+    /// Stops the node actor, after it stops any node actors it owns.
+    /// Aid is the requestor.
+    Stop(Aid),
+}
+
+impl NodeCommand {
+    /// The name of the command, used when counting commands.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeCommand::ComputeOutputToInput(_, _, _, _, _) => "ComputeOutputToInput",
+            NodeCommand::ComputeOutput(_, _, _) => "ComputeOutput",
+            NodeCommand::InputValue(_, _, _, _) => "InputValue",
+            NodeCommand::InputError(_, _, _) => "InputError",
+            NodeCommand::ReceiverMessage(_, _, _) => "ReceiverMessage",
+            NodeCommand::RequestProgress(_, _) => "RequestProgress",
+            NodeCommand::UpdateProgress(_, _, _) => "UpdateProgress",
+            NodeCommand::UpdateDatum(_, _, _) => "UpdateDatum",
+            NodeCommand::RemoveDatum(_, _) => "RemoveDatum",
+            NodeCommand::RefreshPins(_) => "RefreshPins",
+            NodeCommand::LinkPin(_, _, _, _) => "LinkPin",
+            NodeCommand::StopWaitingForNewMessages => "StopWaitingForNewMessages",
+            NodeCommand::Stop(_) => "Stop",
+        }
+    }
 }
 
 ///
@@ -231,6 +347,11 @@ pub enum NodeResponse {
     /// Pin is the output pin.
     /// The message is the value.
     OutputPinValue(Aid, uuid::Uuid, Option<Message>),
+    /// Tells an actor that the output it asked for could not be computed.
+    /// Aid is the sending node.
+    /// Pin is the output pin.
+    /// String is the reason.
+    OutputPinError(Aid, uuid::Uuid, String),
     /// Simple flag indicating that a pin input set command succeeded.
     InputPinSet,
     /// Simple flag indicating that a value was sent to a receiver.
@@ -241,6 +362,8 @@ pub enum NodeResponse {
     DatumRemoved,
     /// Simple flag indicating that a node has refreshed what pins are available.
     PinsRefreshed,
+    /// Simple flag indicating whether or not a node linked one of its pins to another node.
+    PinLinked(bool),
 }
 
 use log::*;
@@ -283,6 +406,32 @@ fn pin_vec_to_hashmap(pins: Vec<Pin>) -> std::collections::HashMap<uuid::Uuid, P
     map
 }
 
+///
+/// Swaps in pins read again from the process, keeping the links and values of pins that still exist.
+/// Computed and pulled values are thrown away, as they may have come from what changed.
+/// A value set on a pin is kept unless the pin's datatype changed.
+///
+fn refresh_pin_map(
+    mut old: std::collections::HashMap<uuid::Uuid, Pin>,
+    pins: Vec<Pin>,
+    outputs: bool,
+) -> std::collections::HashMap<uuid::Uuid, Pin> {
+    let mut map = pin_vec_to_hashmap(pins);
+    for (id, pin) in map.iter_mut() {
+        if let Some(mut previous) = old.remove(id) {
+            if outputs || previous.info.datatype != pin.info.datatype {
+                previous.value = None;
+            }
+            for value in previous.link_value.values_mut() {
+                *value = None;
+            }
+            previous.info = pin.info.clone();
+            *pin = previous;
+        }
+    }
+    map
+}
+
 ///
 /// Implementation for a node.
 ///
@@ -309,156 +458,522 @@ impl Node {
             process: Arc::new(Mutex::new(process)),
             catalogue: catalogue.clone(),
             controller,
+            tracer: None,
+            metrics: None,
+            messages: None,
+            subscribers: None,
+            pending: Vec::new(),
+            pulling: std::collections::HashSet::new(),
+            receiving: Vec::new(),
+            deferring: false,
+            recorded_depth: None,
+            assets: Default::default(),
+            parent: None,
         }
     }
     ///
-    /// Handle messages sent by other actors.
+    /// Finds the file an asset path points to, relative to the graph version this node is in, see `AssetContext::resolve`.
+    /// The path given back is on disk, with assets in packages extracted first.
     ///
-    pub async fn handle(mut self, context: Context, message: Message) -> ActorResult<Self> {
-        if let Some(msg) = message.content_as::<NodeCommand>() {
-            match &*msg {
-                // This is a received request to process an output if needed and send them forward.
-                NodeCommand::ComputeOutputToInput(commander, input, output, datatype, parameter) => {
-                    // Gather the needed information prematurely, or the borrow checker will have a field day.
-                    let output_info: PinInfo;
-                    let output_uuid: uuid::Uuid;
-                    let output_value: Option<Message>;
-                    // Reference to self, and the pin as well, must go out of scope.
-                    {
-                        let output_pin = self.outputs.get_mut(&output);
-                        match output_pin {
-                            // The pin exists. Get the requried information.
-                            Some(output_pin) => {
-                                output_info = output_pin.info.clone();
-                                output_uuid = output_pin.info.uuid.clone();
-                                output_value = output_pin.value.clone();
-                            }
-                            // The pin does not exist. Return after logging an error.
-                            None => {
-                                error!(
-                                    "node actor {} does not have outpin pin with uuid of {}",
-                                    &context.aid, output
-                                );
-                                return Ok(Status::done(self));
+    pub fn resolve_asset(&self, path: &str) -> Result<std::path::PathBuf, String> {
+        crate::asset::asset_file(&self.assets.resolve(path)?)
+    }
+    ///
+    /// Reads the contents of an asset, relative to the graph version this node is in.
+    ///
+    pub fn read_asset(&self, path: &str) -> Result<Vec<u8>, String> {
+        crate::package::read_library_file(&self.assets.resolve(path)?)
+    }
+    ///
+    /// Publishes an event about this node to the engine's subscribers, via the controller.
+    /// Nothing is sent when there are no subscribers, except for boots and stops, which the controller keeps track of itself.
+    ///
+    pub fn publish(&self, context: &Context, event: EngineEventType) {
+        let lifecycle = matches!(event, EngineEventType::NodeBooted | EngineEventType::NodeStopped);
+        if let Some(subscribers) = &self.subscribers {
+            if !lifecycle && subscribers.load(atomic::Ordering::Relaxed) == 0 {
+                return;
+            }
+        }
+        let event = EngineEvent {
+            graph: Some(self.info.graph.clone()),
+            instance: Some(self.info.uuid.clone()),
+            actor: Some(context.aid.clone()),
+            event,
+        };
+        if let Err(e) = self.controller.send_new(super::engine::ControllerCommand::PublishEvent(event)) {
+            error!("node actor {} could not publish event to controller: {}", context.aid.clone(), e.to_string());
+        }
+    }
+    ///
+    /// Gets the value of an input pin as the given type, if it has one.
+    /// Without a value of its own, this is the value of the pin's first link, ordered by link id.
+    ///
+    pub fn get_input<T>(&self, input: &uuid::Uuid) -> Option<T>
+    where
+        T: Clone + Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        match self.get_input_message(input) {
+            Some(value) => value.content_as::<T>().map(|value| (*value).clone()),
+            None => None,
+        }
+    }
+    ///
+    /// Gets the value of an input pin without knowing its type.
+    /// Without a value of its own, this is the value of the pin's first link, ordered by link id.
+    ///
+    pub fn get_input_message(&self, input: &uuid::Uuid) -> Option<Message> {
+        self.get_input_messages(input).into_iter().next()
+    }
+    ///
+    /// Gets every value of an expandable input pin as the given type.
+    /// The pin's own value can be a single value or an array of them, and is followed by the value of each link, ordered by link id.
+    ///
+    pub fn get_inputs<T>(&self, input: &uuid::Uuid) -> Vec<T>
+    where
+        T: Clone + Serialize + serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        let mut values = Vec::new();
+        let pin = match self.inputs.get(input) {
+            Some(pin) => pin,
+            None => return values,
+        };
+        if let Some(value) = &pin.value {
+            if let Some(array) = value.content_as::<Vec<T>>() {
+                values.extend((*array).iter().cloned());
+            } else if let Some(single) = value.content_as::<T>() {
+                values.push((*single).clone());
+            }
+        }
+        let mut links: Vec<&uuid::Uuid> = pin.link_value.keys().collect();
+        links.sort();
+        for link in links {
+            if let Some(Some(value)) = pin.link_value.get(link) {
+                if let Some(single) = value.content_as::<T>() {
+                    values.push((*single).clone());
+                }
+            }
+        }
+        values
+    }
+    ///
+    /// Gets every value of an expandable input pin without knowing its type, for pins of the any datatype.
+    /// The pin's own value comes first, followed by the value of each link, ordered by link id.
+    ///
+    pub fn get_input_messages(&self, input: &uuid::Uuid) -> Vec<Message> {
+        let pin = match self.inputs.get(input) {
+            Some(pin) => pin,
+            None => return Vec::new(),
+        };
+        let mut links: Vec<&uuid::Uuid> = pin.link_value.keys().collect();
+        links.sort();
+        pin.value
+            .iter()
+            .cloned()
+            .chain(links.into_iter().filter_map(|link| pin.link_value.get(link).cloned().flatten()))
+            .collect()
+    }
+    ///
+    /// The seed of this node.
+    /// This is the seed datum if one was given, usually by the graph this node belongs to, or otherwise one derived from the instance id.
+    ///
+    pub fn seed(&self) -> u64 {
+        match self.info.data.get(crate::seed::SEED_DATUM).and_then(|seed| seed.as_u64()) {
+            Some(seed) => seed,
+            None => crate::seed::node_seed(None, &self.info.uuid),
+        }
+    }
+    ///
+    /// Creates a deterministic random number generator for this node.
+    /// Each stream, usually an output pin, gets its own numbers so that the order of computes does not matter.
+    ///
+    pub fn rng(&self, stream: &uuid::Uuid) -> crate::seed::SeededRng {
+        crate::seed::seeded_rng(crate::seed::derive_seed(self.seed(), stream))
+    }
+    ///
+    /// Records metrics for computing one of this node's outputs, and a trace span if tracing is running.
+    ///
+    pub fn record_compute(&self, context: &Context, output: &uuid::Uuid, start: std::time::Instant, cache_hit: bool, error: bool) {
+        if let Some(metrics) = &self.metrics {
+            metrics.lock().unwrap().record_compute(&self.info.graph, self.parent, &self.info.uuid, start.elapsed().as_micros() as u64, cache_hit, error);
+        }
+        if let Some(tracer) = &self.tracer {
+            let mut tracer = tracer.lock().unwrap();
+            if tracer.enabled {
+                let span = TraceSpan {
+                    name: match self.outputs.get(output) {
+                        Some(output_pin) => format!("{}: {}", self.info.graph.name, output_pin.info.name),
+                        None => self.info.graph.name.clone(),
+                    },
+                    category: String::from("compute"),
+                    graph: Some(self.info.graph.name.clone()),
+                    instance: Some(self.info.uuid.clone()),
+                    pin: Some(output.clone()),
+                    cache_hit: Some(cache_hit),
+                    actor: context.aid.to_string(),
+                    start: tracer.since_start(start),
+                    duration: Some(start.elapsed().as_micros() as u64),
+                };
+                tracer.record(span);
+            }
+        }
+    }
+    ///
+    /// Records how many requests this node has waiting, for metrics.
+    /// The shared metrics are only locked when the number changed since it was last recorded.
+    ///
+    fn record_queue_depth(&mut self) {
+        let depth = self.pending.len();
+        if self.recorded_depth == Some(depth) {
+            return;
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.lock().unwrap().record_queue_depth(&self.info.graph, self.parent, &self.info.uuid, depth);
+            self.recorded_depth = Some(depth);
+        }
+    }
+    ///
+    /// Links a send or input pin of this node to a pin on another node actor.
+    /// Returns false if this node has no such pin.
+    ///
+    pub fn link(&mut self, pin: &uuid::Uuid, target: Aid, target_pin: PinRef) -> bool {
+        let local_pin = match self.sends.get_mut(pin) {
+            Some(local_pin) => Some(local_pin),
+            None => self.inputs.get_mut(pin),
+        };
+        match local_pin {
+            Some(local_pin) => {
+                let link = uuid::Uuid::new_v4();
+                local_pin.link_nodes.insert(link, target);
+                local_pin.link_pins.insert(link, target_pin);
+                true
+            }
+            None => false,
+        }
+    }
+    ///
+    /// Sends a message on one of this node's send pins to every receive pin linked to it.
+    ///
+    pub fn emit(&self, context: &Context, send: &uuid::Uuid, message: Option<Message>) {
+        match self.sends.get(send) {
+            Some(send_pin) => {
+                for (link, target) in send_pin.link_nodes.iter() {
+                    match send_pin.link_pins.get(link).and_then(|target_pin| target_pin.pin) {
+                        Some(receiver) => {
+                            match target.send_new(NodeCommand::ReceiverMessage(context.aid.clone(), receiver.clone(), message.clone())) {
+                                Ok(()) => trace!("node actor {:?} sent message on pin {} to node actor {:?} pin {}", &context.aid, send, target, receiver),
+                                Err(e) => error!("node actor {:?} could not send message on pin {} to node actor {:?} pin {}: {}", &context.aid, send, target, receiver, e.to_string()),
                             }
                         }
+                        None => error!("node actor {:?} send pin {} is linked to node actor {:?} without a receive pin", &context.aid, send, target),
                     }
-                    // Is the datatype correct?
-                    if &*output_info.datatype == &*datatype {
-                        // Is there already a value?
-                        // TODO: Make this a setting on node pins.
-                        match output_value {
-                            // Send the value already there, effectively caching it.
-                            Some(output_value) => {
-                                send_input_output(
-                                    &context,
-                                    commander.clone(),
-                                    output.clone(),
-                                    input.clone(),
-                                    output_info.datatype.clone(),
-                                    Some(output_value.clone()),
-                                );
-                            }
-                            None => {
-                                let new_output_value = compute_output_value(&mut self, output_info.clone(), &context, parameter.clone());
-                                match new_output_value {
-                                    Ok(new_output_value) => {
-                                        let output_pin = self.outputs.get_mut(&output).unwrap();
-                                        output_pin.value = new_output_value.clone();
-                                        send_input_output(&context, commander.clone(), output.clone(), input.clone(), output_info.datatype.clone(), new_output_value.clone());
-                                    },
-                                    Err(e) => error!("could not calculate output value for node actor {:?} pin {} because of reason: {}", &context.aid, output, e)
-                                };
+                }
+                self.publish(context, EngineEventType::MessageSent(send.clone(), message));
+            }
+            None => {
+                error!("node actor {:?} does not have send pin with uuid of {}", &context.aid, send);
+                self.publish(context, EngineEventType::Error(format!("send pin {} does not exist", send)));
+            }
+        }
+    }
+    ///
+    /// Tells the node that the output being computed will get its value later, usually from other node actors.
+    /// Called by the process from `compute_output`, whose own result is then ignored.
+    /// The requests for the output wait until the value is given with `resolve`.
+    ///
+    pub fn defer(&mut self) {
+        self.deferring = true;
+    }
+    ///
+    /// Gives the value of an output whose compute was deferred, answering every request waiting on it.
+    ///
+    pub fn resolve(&mut self, context: &Context, output: &uuid::Uuid, value: Result<Option<Message>, String>) {
+        let (waiting, pending): (Vec<PendingRequest>, Vec<PendingRequest>) = self
+            .pending
+            .drain(..)
+            .partition(|request| request.deferred && request.pin == *output);
+        self.pending = pending;
+        let start = waiting.iter().map(|request| request.start).min().unwrap_or_else(std::time::Instant::now);
+        match value {
+            Ok(value) => {
+                if let Some(output_pin) = self.outputs.get_mut(output) {
+                    output_pin.value = value.clone();
+                }
+                self.record_compute(context, output, start, false, false);
+                for request in waiting {
+                    self.answer(context, &request.requestor, output, value.clone());
+                }
+                self.publish(context, EngineEventType::OutputComputed(output.clone(), value));
+            }
+            Err(e) => {
+                error!("could not calculate output value for node actor {:?} pin {} because of reason: {}", &context.aid, output, e);
+                self.record_compute(context, output, start, false, true);
+                for request in waiting {
+                    self.fail(context, &request.requestor, output, e.clone());
+                }
+                self.publish(context, EngineEventType::Error(e));
+            }
+        }
+    }
+    ///
+    /// Sends the value of a pin to whoever requested it.
+    ///
+    fn answer(&self, context: &Context, requestor: &Requestor, pin: &uuid::Uuid, value: Option<Message>) {
+        match requestor {
+            Requestor::Input(commander, input, _datatype) => {
+                let datatype = match self.outputs.get(pin).or_else(|| self.inputs.get(pin)) {
+                    Some(pin) => pin.info.datatype.clone(),
+                    None => String::from(ANY_DATATYPE),
+                };
+                send_input_output(context, commander.clone(), pin.clone(), input.clone(), datatype, value);
+            }
+            Requestor::Commander(commander) => {
+                match commander.send_new(NodeResponse::OutputPinValue(context.aid.clone(), pin.clone(), value)) {
+                    Err(e) => error!("node actor {} could not send output for pin {} back to requestor {}: {}", context.aid.clone(), pin.clone(), commander.clone(), e.to_string()),
+                    _ => {}
+                }
+            }
+        }
+    }
+    ///
+    /// Tells whoever requested the value of a pin that it could not be given.
+    ///
+    fn fail(&self, context: &Context, requestor: &Requestor, pin: &uuid::Uuid, error: String) {
+        let sent = match requestor {
+            Requestor::Input(commander, input, _datatype) => commander.send_new(NodeCommand::InputError(context.aid.clone(), input.clone(), error)),
+            Requestor::Commander(commander) => commander.send_new(NodeResponse::OutputPinError(context.aid.clone(), pin.clone(), error)),
+        };
+        if let Err(e) = sent {
+            error!("node actor {} could not send the error for pin {} back to its requestor: {}", context.aid.clone(), pin.clone(), e.to_string());
+        }
+    }
+    ///
+    /// Requests the value of every input link that does not have one yet from the linked node actors.
+    /// Returns whether any of them are still on their way.
+    ///
+    fn pull_inputs(&mut self, context: &Context) -> bool {
+        for input_pin in self.inputs.values() {
+            for (link, target) in input_pin.link_nodes.iter() {
+                if input_pin.link_value.contains_key(link) || self.pulling.contains(link) {
+                    continue;
+                }
+                match input_pin.link_pins.get(link).and_then(|target_pin| target_pin.pin) {
+                    Some(output) => {
+                        match target.send_new(NodeCommand::ComputeOutputToInput(context.aid.clone(), input_pin.info.uuid.clone(), output.clone(), input_pin.info.datatype.clone(), None)) {
+                            Ok(()) => {
+                                trace!("node actor {:?} pulled input pin {} from node actor {:?} pin {}", &context.aid, input_pin.info.uuid, target, output);
+                                self.pulling.insert(link.clone());
                             }
+                            Err(e) => error!("node actor {:?} could not pull input pin {} from node actor {:?} pin {}: {}", &context.aid, input_pin.info.uuid, target, output, e.to_string()),
                         }
-                    } else {
-                        error!("incorrect requested datatype from node actor {:?} pin {} to node actor {:?} pin {}", &context.aid, output_uuid, &commander, input);
                     }
+                    None => error!("node actor {:?} input pin {} is linked to node actor {:?} without an output pin", &context.aid, input_pin.info.uuid, target),
+                }
+            }
+        }
+        !self.pulling.is_empty()
+    }
+    ///
+    /// Answers a request for the value of a pin, computing it if it is not cached.
+    /// Linked inputs are pulled first, so the request may wait until they arrive.
+    /// Input pins can be requested too, which is how nodes within a graph read the graph's inputs.
+    ///
+    fn request(&mut self, context: &Context, requestor: Requestor, pin: uuid::Uuid, parameter: Option<Message>, start: std::time::Instant) {
+        // Gather the needed information prematurely, or the borrow checker will have a field day.
+        let (pin_info, pin_value, is_output) = match self.outputs.get(&pin) {
+            Some(output_pin) => (output_pin.info.clone(), output_pin.value.clone(), true),
+            None => match self.inputs.get(&pin) {
+                Some(input_pin) => (input_pin.info.clone(), None, false),
+                None => {
+                    error!("node actor {} does not have output pin with uuid of {}", &context.aid, pin);
+                    return self.fail(context, &requestor, &pin, format!("no output pin with uuid {}", pin));
+                }
+            },
+        };
+        // Is the datatype correct?
+        if let Requestor::Input(commander, input, datatype) = &requestor {
+            if !pin_info.accepts(datatype) {
+                error!("incorrect requested datatype from node actor {:?} pin {} to node actor {:?} pin {}", &context.aid, pin, commander, input);
+                return self.fail(context, &requestor, &pin, format!("pin {} does not give datatype {}", pin, datatype));
+            }
+        }
+        // Send the value already there, effectively caching it.
+        // TODO: Make this a setting on node pins.
+        if let Some(pin_value) = pin_value {
+            self.record_compute(context, &pin, start, true, false);
+            self.answer(context, &requestor, &pin, Some(pin_value.clone()));
+            self.publish(context, EngineEventType::OutputComputed(pin, Some(pin_value)));
+            return;
+        }
+        // Wait for the same output if it is already being computed.
+        let underway = self.pending.iter().any(|request| request.deferred && request.pin == pin);
+        if underway || self.pull_inputs(context) {
+            self.pending.push(PendingRequest { requestor, pin, parameter, deferred: underway, start });
+            return;
+        }
+        if !is_output {
+            let value = self.get_input_message(&pin);
+            self.answer(context, &requestor, &pin, value);
+            return;
+        }
+        self.deferring = false;
+        let new_output_value = compute_output_value(self, pin_info, context, parameter.clone());
+        if self.deferring {
+            self.deferring = false;
+            self.pending.push(PendingRequest { requestor, pin, parameter, deferred: true, start });
+            return;
+        }
+        match new_output_value {
+            Ok(new_output_value) => {
+                let output_pin = self.outputs.get_mut(&pin).unwrap();
+                output_pin.value = new_output_value.clone();
+                self.record_compute(context, &pin, start, false, false);
+                self.answer(context, &requestor, &pin, new_output_value.clone());
+                self.publish(context, EngineEventType::OutputComputed(pin, new_output_value));
+            }
+            Err(e) => {
+                error!("could not calculate output value for node actor {:?} pin {} because of reason: {}", &context.aid, pin, e);
+                self.record_compute(context, &pin, start, false, true);
+                self.fail(context, &requestor, &pin, e.clone());
+                self.publish(context, EngineEventType::Error(e));
+            }
+        }
+    }
+    ///
+    /// Hands a message sent to a receive pin to the process.
+    /// Linked inputs are pulled first, so the message may wait until they arrive.
+    /// Messages waiting already go first, keeping them in the order they were sent.
+    ///
+    fn receive(&mut self, context: &Context, receiver: uuid::Uuid, message: Option<Message>) {
+        if !self.receiving.is_empty() || self.pull_inputs(context) {
+            self.receiving.push(PendingReceive { receiver, message });
+            return;
+        }
+        let process = self.process.clone();
+        process.lock().unwrap().handle_receive(self, context, &receiver, &message);
+    }
+    ///
+    /// Handle messages sent by other actors.
+    ///
+    pub async fn handle(mut self, context: Context, message: Message) -> ActorResult<Self> {
+        if let Some(msg) = message.content_as::<NodeCommand>() {
+            if let Some(messages) = &self.messages {
+                messages.node.count(msg.name());
+            }
+            match &*msg {
+                // This is a received request to process an output if needed and send it to another node's input.
+                NodeCommand::ComputeOutputToInput(commander, input, output, datatype, parameter) => {
+                    self.request(
+                        &context,
+                        Requestor::Input(commander.clone(), input.clone(), datatype.clone()),
+                        output.clone(),
+                        parameter.clone(),
+                        std::time::Instant::now(),
+                    );
                 }
-                // This is a received request to process an output if needed and send them forward.
+                // This is a received request to process an output if needed and send it back.
                 NodeCommand::ComputeOutput(commander, output, parameter) => {
                     info!("node get output pin value");
-                    // Gather the needed information prematurely, or the borrow checker will have a field day.
-                    let output_info: PinInfo;
-                    let output_value: Option<Message>;
-                    // Reference to self, and the pin as well, must go out of scope.
-                    {
-                        let output_pin = self.outputs.get_mut(&output);
-                        match output_pin {
-                            // The pin exists. Get the requried information.
-                            Some(output_pin) => {
-                                output_info = output_pin.info.clone();
-                                output_value = output_pin.value.clone();
-                            }
-                            // The pin does not exist. Return after logging an error.
-                            None => {
-                                error!(
-                                    "node actor {} does not have outpin pin with uuid of {}",
-                                    &context.aid, output
-                                );
-                                return Ok(Status::done(self));
-                            }
-                        }
-                    }
-                    // Is there already a value?
-                    // TODO: Make this a setting on node pins.
-                    match output_value {
-                        // Send the value already there, effectively acting as a cached value.
-                        Some(output_value) => {
-                            match commander.send_new(NodeResponse::OutputPinValue(context.aid.clone(), output.clone(), Some(output_value.clone()))) {
-                                Err(e) => error!("node actor {} could not send output for pin {} back to requestor after cache {}: {}", context.aid.clone(), output.clone(), commander.clone(), e.to_string()),
-                                _ => {}
-                            }
-                        }
-                        None => {
-                            let new_output_value = compute_output_value(&mut self, output_info.clone(), &context, parameter.clone());
-                            match new_output_value {
-                                Ok(new_output_value) => {
-                                    let output_pin = self.outputs.get_mut(&output).unwrap();
-                                    output_pin.value = new_output_value.clone();
-                                    match commander.send_new(NodeResponse::OutputPinValue(context.aid.clone(), output.clone(), new_output_value.clone())) {
-                                        Err(e) => error!("node actor {} could not send output for pin {} back to requestor after calculate {}: {}", context.aid.clone(), output.clone(), commander.clone(), e.to_string()),
-                                        _ => {}
-                                    }
-                                },
-                                Err(e) => error!("could not calculate output value for node actor {:?} pin {} because of reason: {}", &context.aid, output, e.to_string())
-                            };
-                        }
-                    }
+                    self.request(
+                        &context,
+                        Requestor::Commander(commander.clone()),
+                        output.clone(),
+                        parameter.clone(),
+                        std::time::Instant::now(),
+                    );
                 }
                 // TODO: Add the reply for request of node value.
                 // This is a reply from a compute output request, setting the value of the input.
                 NodeCommand::InputValue(commander, input, datatype, message) => {
                     info!("node set input pin value");
+                    let mut pulled = false;
                     let ipin: Option<&mut Pin> = self.inputs.get_mut(&input);
-                    match ipin {
+                    let event = match ipin {
                         Some(ipin) => {
-                            if &*ipin.info.datatype == &*datatype {
-                                ipin.value = message.clone();
-                                if *commander == self.controller {
-                                    // TODO: Match and handle errors
-                                    let _ = commander.send_new(NodeResponse::InputPinSet);
+                            if ipin.info.accepts(datatype) {
+                                // A value pulled from a linked node belongs to that link, and anything else is the pin's own value.
+                                let pulling = &self.pulling;
+                                let link = ipin
+                                    .link_nodes
+                                    .iter()
+                                    .find(|(link, target)| *target == commander && pulling.contains(*link))
+                                    .map(|(link, _)| link.clone());
+                                match link {
+                                    Some(link) => {
+                                        ipin.link_value.insert(link, message.clone());
+                                        self.pulling.remove(&link);
+                                        pulled = true;
+                                    }
+                                    None => {
+                                        ipin.value = message.clone();
+                                        if *commander == self.controller {
+                                            // TODO: Match and handle errors
+                                            let _ = commander.send_new(NodeResponse::InputPinSet);
+                                        }
+                                    }
                                 }
+                                EngineEventType::InputPinSet(input.clone())
                             } else {
                                 error!(
                                     "incorrect datatype sent from actor {:?} to actor {:?}: pin {}",
                                     commander, &context.aid, ipin.info.uuid
                                 );
+                                EngineEventType::Error(format!("incorrect datatype {} sent to input pin {}", datatype, input))
                             }
                         }
-                        None => error!(
-                            "node actor {:?} does not have input pin with uuid of {}",
-                            &context.aid, input
-                        ),
+                        None => {
+                            error!(
+                                "node actor {:?} does not have input pin with uuid of {}",
+                                &context.aid, input
+                            );
+                            EngineEventType::Error(format!("input pin {} does not exist", input))
+                        }
                     };
+                    self.publish(&context, event);
+                    // Once every linked input has arrived, the requests waiting on them can go ahead.
+                    if pulled && self.pulling.is_empty() {
+                        for received in std::mem::take(&mut self.receiving) {
+                            self.receive(&context, received.receiver, received.message);
+                        }
+                        let (waiting, deferred): (Vec<PendingRequest>, Vec<PendingRequest>) =
+                            self.pending.drain(..).partition(|request| !request.deferred);
+                        self.pending = deferred;
+                        for request in waiting {
+                            self.request(&context, request.requestor, request.pin, request.parameter, request.start);
+                        }
+                    }
+                }
+                // This is a reply from a compute output request that failed, which fails every request waiting on linked inputs.
+                NodeCommand::InputError(commander, input, reason) => {
+                    let pulling = &self.pulling;
+                    let link = self.inputs.get(input).and_then(|ipin| {
+                        ipin.link_nodes
+                            .iter()
+                            .find(|(link, target)| *target == commander && pulling.contains(*link))
+                            .map(|(link, _)| link.clone())
+                    });
+                    match link {
+                        Some(link) => {
+                            self.pulling.remove(&link);
+                            let error = format!("input pin {} could not be pulled: {}", input, reason);
+                            error!("node actor {:?} {}", &context.aid, error);
+                            let (waiting, deferred): (Vec<PendingRequest>, Vec<PendingRequest>) =
+                                self.pending.drain(..).partition(|request| !request.deferred);
+                            self.pending = deferred;
+                            for request in waiting {
+                                self.fail(&context, &request.requestor, &request.pin, error.clone());
+                            }
+                            for received in self.receiving.drain(..) {
+                                warn!("node actor {:?} dropped the message sent to receive pin {}", &context.aid, received.receiver);
+                            }
+                            self.publish(&context, EngineEventType::Error(error));
+                        }
+                        None => warn!("node actor {:?} was sent an error for input pin {} that it did not pull: {}", &context.aid, input, reason),
+                    }
                 }
                 NodeCommand::ReceiverMessage(commander, receiver, message) => {
                     let _ = commander.send_new(NodeResponse::Received);
-                    let process = self.process.clone();
-                    process
-                        .lock()
-                        .unwrap()
-                        .handle_receive(&mut self, &context, &receiver, &message);
+                    self.receive(&context, receiver.clone(), message.clone());
                 }
                 NodeCommand::RequestProgress(requestor, output) => {
                     let progress = self
@@ -506,24 +1021,67 @@ impl Node {
                     let _ = requestor.send_new(NodeResponse::DatumRemoved);
                 }
                 NodeCommand::RefreshPins(requestor) => {
-                    let cat = self.catalogue.lock().unwrap();
-                    let (vinputs, voutputs) = self.process.lock().unwrap().get_io(&cat);
-                    let (vreceives, vsends) = self.process.lock().unwrap().get_rs(&cat);
-                    self.inputs = pin_vec_to_hashmap(vinputs);
-                    self.outputs = pin_vec_to_hashmap(voutputs);
-                    self.receives = pin_vec_to_hashmap(vreceives);
-                    self.sends = pin_vec_to_hashmap(vsends);
+                    let process = self.process.clone();
+                    process
+                        .lock()
+                        .unwrap()
+                        .handle_refresh(&mut self, &context);
+                    let ((vinputs, voutputs), (vreceives, vsends)) = {
+                        let cat = self.catalogue.lock().unwrap();
+                        let process = self.process.lock().unwrap();
+                        (process.get_io(&cat), process.get_rs(&cat))
+                    };
+                    self.inputs = refresh_pin_map(std::mem::take(&mut self.inputs), vinputs, false);
+                    self.outputs = refresh_pin_map(std::mem::take(&mut self.outputs), voutputs, true);
+                    self.receives = refresh_pin_map(std::mem::take(&mut self.receives), vreceives, false);
+                    self.sends = refresh_pin_map(std::mem::take(&mut self.sends), vsends, false);
+                    self.publish(&context, EngineEventType::PinsRefreshed);
                     let _ = requestor.send_new(NodeResponse::PinsRefreshed);
                 }
+                NodeCommand::LinkPin(requestor, pin, target, target_pin) => {
+                    let linked = self.link(pin, target.clone(), target_pin.clone());
+                    if !linked {
+                        error!("node actor {:?} does not have send or input pin with uuid of {}", &context.aid, pin);
+                    }
+                    let _ = requestor.send_new(NodeResponse::PinLinked(linked));
+                }
                 NodeCommand::StopWaitingForNewMessages => {
                     let _ = self.controller.send_new(super::engine::ControllerCommand::StopWaitingForNewMessages);
                 }
+                NodeCommand::Stop(requestor) => {
+                    trace!("node actor {:?} stopping at the request of {:?}", &context.aid, requestor);
+                    let process = self.process.clone();
+                    process
+                        .lock()
+                        .unwrap()
+                        .handle_stop(&mut self, &context);
+                    if let Some(metrics) = &self.metrics {
+                        metrics.lock().unwrap().remove_queue_depth(&self.info.uuid);
+                    }
+                    self.publish(&context, EngineEventType::NodeStopped);
+                    return Ok(Status::stop(self));
+                }
             };
         }
         else if let Some(msg) = message.content_as::<NodeResponse>() {
             match &*msg {
                 NodeResponse::OutputPinValue(_responder, pin_id, _value) => {
-                    warn!("bad logic: node actor {:?} has recieved a node response with the value of an output pin {:?} without corresponding input pin data", &context.aid, pin_id);
+                    // Nodes made of other nodes, like graphs, ask those nodes for their outputs and handle the values themselves.
+                    trace!("node actor {:?} has recieved a node response with the value of an output pin {:?}", &context.aid, pin_id);
+                    let process = self.process.clone();
+                    process
+                        .lock()
+                        .unwrap()
+                        .handle_message(&mut self, &context, &message);
+                }
+                NodeResponse::OutputPinError(_responder, pin_id, _reason) => {
+                    // Nodes made of other nodes handle their failures too, so they can fail whatever waits on them.
+                    trace!("node actor {:?} has recieved a node response with an error for an output pin {:?}", &context.aid, pin_id);
+                    let process = self.process.clone();
+                    process
+                        .lock()
+                        .unwrap()
+                        .handle_message(&mut self, &context, &message);
                 }
                 NodeResponse::InputPinSet => {
                     warn!("bad logic: node actor {:?} has recieved a node response indicating that a pin's value was set to something", &context.aid);
@@ -540,6 +1098,9 @@ impl Node {
                 NodeResponse::PinsRefreshed => {
                     trace!("node actor {:?} has recieved a node response indicating that another nodes pins were removed", &context.aid);
                 }
+                NodeResponse::PinLinked(linked) => {
+                    trace!("node actor {:?} has recieved a node response indicating that another nodes pin was linked: {}", &context.aid, linked);
+                }
             }
         }
         else {
@@ -549,6 +1110,7 @@ impl Node {
                 .unwrap()
                 .handle_message(&mut self, &context, &message);
         }
+        self.record_queue_depth();
         Ok(Status::done(self))
     }
 }
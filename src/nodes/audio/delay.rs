@@ -0,0 +1,132 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Adds echoes to audio by feeding it back through a delay line.
+/// The output is as long as the input, so echoes past the end are cut off.
+///
+#[derive(Default)]
+pub struct NodeAudioDelayV1 {}
+
+impl Nodeable for NodeAudioDelayV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Audio", "888bcadb-0a34-451d-af5b-586e93790397", "audio", false));
+        inputs.push(input_pin("Time", "ae1c87b0-9f7a-43f0-8f37-1fe051f05052", "f64", false));
+        inputs.push(input_pin("Feedback", "d6cdcfe6-fbbc-4c0d-b500-cdd3df62f4e7", "f64", false));
+        inputs.push(input_pin("Mix", "e6698787-01aa-44a6-ab56-bcd3b38fd6cb", "f64", false));
+        outputs.push(output_pin("Audio", "65bea74c-27aa-4a30-b2e1-dbee49936ea0", "audio"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_audio if id_audio == uuid::Uuid::parse_str("65bea74c-27aa-4a30-b2e1-dbee49936ea0").unwrap() => {
+                let audio = audio_input(node, "888bcadb-0a34-451d-af5b-586e93790397")?;
+                let get_f64 = |id: &str, default: f64| {
+                    node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                        .unwrap_or(default) as f32
+                };
+                let delay = (get_f64("ae1c87b0-9f7a-43f0-8f37-1fe051f05052", 0.25).max(0.0) * audio.sample_rate as f32).round() as usize;
+                if delay == 0 {
+                    return Err(String::from("delay time must be at least one sample"));
+                }
+                let feedback = get_f64("d6cdcfe6-fbbc-4c0d-b500-cdd3df62f4e7", 0.4).max(-0.99).min(0.99);
+                let mix = get_f64("e6698787-01aa-44a6-ab56-bcd3b38fd6cb", 0.5).max(0.0).min(1.0);
+                let delayed = audio.map_channels(|_, samples| {
+                    let mut line = vec![0.0f32; delay];
+                    let mut cursor = 0;
+                    process_in_blocks(samples, |_, input, output| {
+                        for (sample, out) in input.iter().zip(output.iter_mut()) {
+                            let echo = line[cursor];
+                            line[cursor] = sample + echo * feedback;
+                            cursor = (cursor + 1) % delay;
+                            *out = sample * (1.0 - mix) + echo * mix;
+                        }
+                    })
+                });
+                Ok(Some(Message::new(delayed)))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioDelayV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Audio Delay"),
+                    uuid: uuid::Uuid::parse_str("d7161dbe-41fb-48c9-b483-93919ec198ca").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Audio Delay"),
+            uuid: uuid::Uuid::parse_str("d7161dbe-41fb-48c9-b483-93919ec198ca").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("d7161dbe-41fb-48c9-b483-93919ec198ca").unwrap() {
+        return match version {
+            1 => Some(NodeAudioDelayV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
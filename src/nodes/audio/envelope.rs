@@ -0,0 +1,168 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Shapes the loudness of audio with an attack, decay, sustain and release envelope.
+/// The note is held for the gate time, then released, and audio past the end of the release is silent.
+///
+#[derive(Default)]
+pub struct NodeAudioEnvelopeV1 {}
+
+///
+/// The times in seconds and the sustain level of an envelope.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Adsr {
+    pub attack: f32,
+    pub decay: f32,
+    pub sustain: f32,
+    pub release: f32,
+}
+
+impl Adsr {
+    /// Gets the level of the envelope while the note is held.
+    fn held(&self, time: f32) -> f32 {
+        if time < self.attack {
+            return time / self.attack;
+        }
+        let time = time - self.attack;
+        if time < self.decay {
+            return 1.0 + (self.sustain - 1.0) * time / self.decay;
+        }
+        self.sustain
+    }
+    /// Gets the level of the envelope at a time, for a note held for the gate time.
+    pub fn level(&self, time: f32, gate: f32) -> f32 {
+        if time < gate {
+            return self.held(time);
+        }
+        let released = time - gate;
+        if released >= self.release {
+            return 0.0;
+        }
+        self.held(gate) * (1.0 - released / self.release)
+    }
+}
+
+impl Nodeable for NodeAudioEnvelopeV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Audio", "c702e3e8-576d-4013-b6d2-4c1f0e6ed39d", "audio", false));
+        inputs.push(input_pin("Attack", "7023602c-f1ba-4016-bed1-3b1aa696b03b", "f64", false));
+        inputs.push(input_pin("Decay", "0a7061a2-38ee-4775-b931-44d6c3a9b2ca", "f64", false));
+        inputs.push(input_pin("Sustain", "61b6e6b8-f038-44de-9a7f-051907c3e5c0", "f64", false));
+        inputs.push(input_pin("Release", "71bd21f8-c182-49f2-bac4-f7ba88fa885f", "f64", false));
+        inputs.push(input_pin("Gate", "956b35aa-2693-4b7c-a262-ce7bc34b856c", "f64", false));
+        outputs.push(output_pin("Audio", "4a51f7a5-3f8c-4532-9ecf-be5371354bfc", "audio"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_audio if id_audio == uuid::Uuid::parse_str("4a51f7a5-3f8c-4532-9ecf-be5371354bfc").unwrap() => {
+                let audio = audio_input(node, "c702e3e8-576d-4013-b6d2-4c1f0e6ed39d")?;
+                let get_f64 = |id: &str, default: f64| {
+                    node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                        .unwrap_or(default)
+                        .max(0.0) as f32
+                };
+                let envelope = Adsr {
+                    attack: get_f64("7023602c-f1ba-4016-bed1-3b1aa696b03b", 0.01),
+                    decay: get_f64("0a7061a2-38ee-4775-b931-44d6c3a9b2ca", 0.1),
+                    sustain: get_f64("61b6e6b8-f038-44de-9a7f-051907c3e5c0", 0.7),
+                    release: get_f64("71bd21f8-c182-49f2-bac4-f7ba88fa885f", 0.2),
+                };
+                let gate = get_f64("956b35aa-2693-4b7c-a262-ce7bc34b856c", (audio.duration() as f32 - envelope.release).max(0.0) as f64);
+                let rate = audio.sample_rate as f32;
+                let shaped = audio.map_channels(|_, samples| {
+                    process_in_blocks(samples, |start, input, output| {
+                        for (index, (sample, out)) in input.iter().zip(output.iter_mut()).enumerate() {
+                            *out = sample * envelope.level((start + index) as f32 / rate, gate);
+                        }
+                    })
+                });
+                Ok(Some(Message::new(shaped)))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioEnvelopeV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("ADSR Envelope"),
+                    uuid: uuid::Uuid::parse_str("2a893339-0b62-4fd6-beef-149680404463").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("ADSR Envelope"),
+            uuid: uuid::Uuid::parse_str("2a893339-0b62-4fd6-beef-149680404463").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("2a893339-0b62-4fd6-beef-149680404463").unwrap() {
+        return match version {
+            1 => Some(NodeAudioEnvelopeV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,176 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The kinds of filter, which let through the frequencies below, above, or around the cutoff.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterOp {
+    LowPass,
+    HighPass,
+    BandPass,
+}
+
+/// The name, graph UUID and operation of every audio filter node.
+pub const OPERATIONS: [(&str, &str, FilterOp); 3] = [
+    ("Low Pass Filter", "c0685742-d186-4560-ae7f-d3b86ff4ca2e", FilterOp::LowPass),
+    ("High Pass Filter", "97b76808-1900-4ced-9cc6-1f1a17179b29", FilterOp::HighPass),
+    ("Band Pass Filter", "b5be1701-3e1e-44eb-963c-2246910b52d0", FilterOp::BandPass),
+];
+
+const AUDIO: &str = "da7078db-d4d2-4b47-af52-27febccc8503";
+const CUTOFF: &str = "b76ebc57-ed04-4375-9323-5306c159a9cb";
+const RESONANCE: &str = "73ded0e8-328b-4671-a93a-88edabb1c122";
+const OUTPUT_AUDIO: &str = "14053f58-9eb6-49f8-b7d2-670e2bddf1d4";
+
+///
+/// A second order filter, using the coefficients from the Audio EQ Cookbook.
+/// It keeps the last two inputs and outputs, so one filter should be used per channel.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Biquad {
+    b: [f32; 3],
+    a: [f32; 2],
+    inputs: [f32; 2],
+    outputs: [f32; 2],
+}
+
+impl Biquad {
+    pub fn new(op: FilterOp, sample_rate: u32, cutoff: f32, resonance: f32) -> Self {
+        let w0 = 2.0 * std::f32::consts::PI * cutoff / sample_rate as f32;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * resonance.max(0.01));
+        let b = match op {
+            FilterOp::LowPass => [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            FilterOp::HighPass => [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+            FilterOp::BandPass => [alpha, 0.0, -alpha],
+        };
+        let a0 = 1.0 + alpha;
+        Self {
+            b: [b[0] / a0, b[1] / a0, b[2] / a0],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+            ..Self::default()
+        }
+    }
+    pub fn process(&mut self, input: f32) -> f32 {
+        let output = self.b[0] * input + self.b[1] * self.inputs[0] + self.b[2] * self.inputs[1]
+            - self.a[0] * self.outputs[0]
+            - self.a[1] * self.outputs[1];
+        self.inputs = [input, self.inputs[0]];
+        self.outputs = [output, self.outputs[0]];
+        output
+    }
+}
+
+///
+/// Filters audio with a resonant low, high or band pass filter.
+/// A resonance of about 0.7 is flat, and higher values ring at the cutoff.
+///
+pub struct NodeAudioFilterV1 {
+    pub op: FilterOp,
+}
+
+impl Nodeable for NodeAudioFilterV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Audio", AUDIO, "audio", false));
+        inputs.push(input_pin("Cutoff", CUTOFF, "f64", false));
+        inputs.push(input_pin("Resonance", RESONANCE, "f64", false));
+        outputs.push(output_pin("Audio", OUTPUT_AUDIO, "audio"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_AUDIO).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let audio = audio_input(node, AUDIO)?;
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default) as f32
+        };
+        let cutoff = get_f64(CUTOFF, 1000.0);
+        if cutoff <= 0.0 || cutoff >= audio.sample_rate as f32 / 2.0 {
+            return Err(format!(
+                "filter cutoff {} must be between zero and half the sample rate of {}",
+                cutoff, audio.sample_rate
+            ));
+        }
+        let resonance = get_f64(RESONANCE, std::f64::consts::FRAC_1_SQRT_2);
+        let filtered = audio.map_channels(|_, samples| {
+            let mut filter = Biquad::new(self.op, audio.sample_rate, cutoff, resonance);
+            process_in_blocks(samples, |_, input, output| {
+                for (sample, out) in input.iter().zip(output.iter_mut()) {
+                    *out = filter.process(*sample);
+                }
+            })
+        });
+        Ok(Some(Message::new(filtered)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioFilterV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: FilterOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeAudioFilterV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
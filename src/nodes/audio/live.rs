@@ -0,0 +1,188 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Plays audio on the default output device, and finishes once all of it has been played.
+/// Only built with the live-audio feature, so the rest of the audio nodes stay headless.
+///
+#[derive(Default)]
+pub struct NodeAudioPlayV1 {}
+
+///
+/// Plays audio on the default output device, blocking until it has finished.
+/// The audio is resampled to the device's rate, and its channels are spread over the device's channels.
+///
+pub fn play(audio: &AudioBuffer) -> Result<(), String> {
+    use cpal::traits::{DeviceTrait, EventLoopTrait, HostTrait};
+    use cpal::{StreamData, UnknownTypeOutputBuffer};
+
+    let host = cpal::default_host();
+    let device = match host.default_output_device() {
+        Some(device) => device,
+        None => return Err(String::from("no audio output device")),
+    };
+    let format = device
+        .default_output_format()
+        .map_err(|e| format!("could not get the audio output format: {}", e))?;
+    let audio = audio.resampled(format.sample_rate.0);
+    if audio.channels == 0 {
+        return Ok(());
+    }
+    let event_loop = std::sync::Arc::new(host.event_loop());
+    let stream = event_loop
+        .build_output_stream(&device, &format)
+        .map_err(|e| format!("could not open an audio output stream: {}", e))?;
+    event_loop
+        .play_stream(stream.clone())
+        .map_err(|e| format!("could not play the audio output stream: {}", e))?;
+
+    let (finished, done) = std::sync::mpsc::channel();
+    let channels = format.channels as usize;
+    let running = event_loop.clone();
+    // The event loop never returns, so its thread is left parked once the stream is destroyed.
+    std::thread::spawn(move || {
+        let mut frame = 0;
+        running.run(move |_, result| {
+            let next = |frame: usize, channel: usize| {
+                if frame >= audio.frames() {
+                    return 0.0;
+                }
+                audio.samples[frame * audio.channels as usize + channel % audio.channels as usize]
+            };
+            let written = match result {
+                Ok(StreamData::Output { buffer: UnknownTypeOutputBuffer::F32(mut buffer) }) => {
+                    for (index, sample) in buffer.iter_mut().enumerate() {
+                        *sample = next(frame + index / channels, index % channels);
+                    }
+                    buffer.len() / channels
+                }
+                Ok(StreamData::Output { buffer: UnknownTypeOutputBuffer::I16(mut buffer) }) => {
+                    for (index, sample) in buffer.iter_mut().enumerate() {
+                        *sample = (next(frame + index / channels, index % channels) * std::i16::MAX as f32) as i16;
+                    }
+                    buffer.len() / channels
+                }
+                Ok(StreamData::Output { buffer: UnknownTypeOutputBuffer::U16(mut buffer) }) => {
+                    for (index, sample) in buffer.iter_mut().enumerate() {
+                        let value = next(frame + index / channels, index % channels);
+                        *sample = ((value * 0.5 + 0.5) * std::u16::MAX as f32) as u16;
+                    }
+                    buffer.len() / channels
+                }
+                Ok(_) => 0,
+                Err(e) => {
+                    let _ = finished.send(Err(format!("audio output stream failed: {}", e)));
+                    return;
+                }
+            };
+            frame += written;
+            if frame >= audio.frames() {
+                let _ = finished.send(Ok(()));
+            }
+        });
+    });
+    let result = done
+        .recv()
+        .unwrap_or_else(|_| Err(String::from("audio output stream stopped")));
+    event_loop.destroy_stream(stream);
+    result
+}
+
+impl Nodeable for NodeAudioPlayV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Audio", "8e30b816-550c-4eab-a6d9-26168c37f096", "audio", false));
+        outputs.push(output_pin("Seconds", "cac2eb33-dbd2-47e6-ab8e-2a259503c0a3", "f64"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_seconds if id_seconds == uuid::Uuid::parse_str("cac2eb33-dbd2-47e6-ab8e-2a259503c0a3").unwrap() => {
+                let audio = audio_input(node, "8e30b816-550c-4eab-a6d9-26168c37f096")?;
+                play(&audio)?;
+                Ok(Some(Message::new(audio.duration())))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioPlayV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Play Audio"),
+                    uuid: uuid::Uuid::parse_str("7872aa01-c1c4-4e32-b0db-0ee29369fad9").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Play Audio"),
+            uuid: uuid::Uuid::parse_str("7872aa01-c1c4-4e32-b0db-0ee29369fad9").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("7872aa01-c1c4-4e32-b0db-0ee29369fad9").unwrap() {
+        return match version {
+            1 => Some(NodeAudioPlayV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
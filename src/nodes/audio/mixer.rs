@@ -0,0 +1,140 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Mixes any number of tracks together, scaled by a gain.
+/// Every track must have the same sample rate. The mix is as long as the longest track,
+/// and has as many channels as the track with the most, with mono tracks going to every channel.
+///
+#[derive(Default)]
+pub struct NodeAudioMixerV1 {}
+
+///
+/// Adds audio tracks together and scales the result by a gain.
+///
+pub fn mix(tracks: &[AudioBuffer], gain: f32) -> Result<AudioBuffer, String> {
+    let first = match tracks.first() {
+        Some(first) => first,
+        None => return Err(String::from("no audio to mix")),
+    };
+    if let Some(track) = tracks.iter().find(|track| track.sample_rate != first.sample_rate) {
+        return Err(format!(
+            "cannot mix audio at {} and {} samples per second",
+            first.sample_rate, track.sample_rate
+        ));
+    }
+    let channels = tracks.iter().map(|track| track.channels).max().unwrap_or(1);
+    let frames = tracks.iter().map(|track| track.frames()).max().unwrap_or(0);
+    let mut mixed = AudioBuffer::silent(first.sample_rate, channels, frames);
+    for track in tracks.iter().filter(|track| track.channels > 0) {
+        for frame in 0..track.frames() {
+            for channel in 0..channels as usize {
+                let source = frame * track.channels as usize + channel % track.channels as usize;
+                mixed.samples[frame * channels as usize + channel] += track.samples[source] * gain;
+            }
+        }
+    }
+    Ok(mixed)
+}
+
+impl Nodeable for NodeAudioMixerV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Tracks", "f0efd19d-18bc-4e74-8578-cf6149a2033c", "audio", true));
+        inputs.push(input_pin("Gain", "3970e4ee-b4fd-4bb8-a2cc-33ca42971c46", "f64", false));
+        outputs.push(output_pin("Audio", "6dc2a094-f7c1-40b9-bb58-63b27c4c8c2c", "audio"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_audio if id_audio == uuid::Uuid::parse_str("6dc2a094-f7c1-40b9-bb58-63b27c4c8c2c").unwrap() => {
+                let tracks = node.get_inputs::<AudioBuffer>(&uuid::Uuid::parse_str("f0efd19d-18bc-4e74-8578-cf6149a2033c").unwrap());
+                let gain = node
+                    .get_input::<f64>(&uuid::Uuid::parse_str("3970e4ee-b4fd-4bb8-a2cc-33ca42971c46").unwrap())
+                    .unwrap_or(1.0) as f32;
+                Ok(Some(Message::new(mix(&tracks, gain)?)))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioMixerV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Audio Mixer"),
+                    uuid: uuid::Uuid::parse_str("775b60dd-32d8-45ac-a974-b743c1d81b45").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Audio Mixer"),
+            uuid: uuid::Uuid::parse_str("775b60dd-32d8-45ac-a974-b743c1d81b45").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("775b60dd-32d8-45ac-a974-b743c1d81b45").unwrap() {
+        return match version {
+            1 => Some(NodeAudioMixerV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,275 @@
+pub mod delay;
+pub mod envelope;
+pub mod filter;
+#[cfg(feature = "live-audio")]
+pub mod live;
+pub mod mixer;
+pub mod oscillator;
+pub mod render;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut oscillator::register());
+    nodes.append(&mut filter::register());
+    nodes.push(envelope::register());
+    nodes.push(mixer::register());
+    nodes.push(delay::register());
+    nodes.push(render::register());
+    #[cfg(feature = "live-audio")]
+    nodes.push(live::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    #[cfg(feature = "live-audio")]
+    {
+        if let Some(node) = live::create(controller.clone(), catalogue.clone(), uuid, version, instance_id) {
+            return Some(node);
+        }
+    }
+    None
+    .or(oscillator::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(filter::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(envelope::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(mixer::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(delay::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(render::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The number of frames audio nodes work on at a time.
+pub const BLOCK_SIZE: usize = 256;
+/// The sample rate used when a node is not given one.
+pub const DEFAULT_SAMPLE_RATE: u32 = 44100;
+
+///
+/// A clip of audio held in memory, carried on pins with the "audio" datatype.
+/// Samples are nominally between minus one and one, and interleaved when there is more than one channel.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AudioBuffer {
+    pub sample_rate: u32,
+    pub channels: u16,
+    pub samples: Vec<f32>,
+}
+
+impl AudioBuffer {
+    /// Creates a buffer of silence.
+    pub fn silent(sample_rate: u32, channels: u16, frames: usize) -> Self {
+        Self {
+            sample_rate,
+            channels,
+            samples: vec![0.0; frames * channels as usize],
+        }
+    }
+    /// Creates a single channel buffer.
+    pub fn mono(sample_rate: u32, samples: Vec<f32>) -> Self {
+        Self {
+            sample_rate,
+            channels: 1,
+            samples,
+        }
+    }
+    /// Interleaves separate channels into one buffer, padding shorter channels with silence.
+    pub fn from_channels(sample_rate: u32, channels: &[Vec<f32>]) -> Self {
+        let frames = channels.iter().map(|channel| channel.len()).max().unwrap_or(0);
+        let mut buffer = Self::silent(sample_rate, channels.len() as u16, frames);
+        for (index, channel) in channels.iter().enumerate() {
+            for (frame, sample) in channel.iter().enumerate() {
+                buffer.samples[frame * channels.len() + index] = *sample;
+            }
+        }
+        buffer
+    }
+    /// The number of samples in each channel.
+    pub fn frames(&self) -> usize {
+        if self.channels == 0 {
+            return 0;
+        }
+        self.samples.len() / self.channels as usize
+    }
+    /// The length of the buffer in seconds.
+    pub fn duration(&self) -> f64 {
+        self.frames() as f64 / self.sample_rate.max(1) as f64
+    }
+    /// Gets the samples of one channel.
+    pub fn channel(&self, channel: usize) -> Vec<f32> {
+        self.samples
+            .iter()
+            .skip(channel)
+            .step_by(self.channels.max(1) as usize)
+            .copied()
+            .collect()
+    }
+    ///
+    /// Creates a new buffer by processing every channel on its own.
+    /// The function is given the channel index and its samples, so it can keep separate state for each channel.
+    ///
+    pub fn map_channels<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(usize, &[f32]) -> Vec<f32>,
+    {
+        let channels: Vec<Vec<f32>> = (0..self.channels as usize)
+            .map(|channel| f(channel, &self.channel(channel)))
+            .collect();
+        Self::from_channels(self.sample_rate, &channels)
+    }
+    ///
+    /// Changes the sample rate by blending between neighbouring samples.
+    ///
+    pub fn resampled(&self, sample_rate: u32) -> Self {
+        if sample_rate == self.sample_rate || self.frames() == 0 {
+            return Self {
+                sample_rate,
+                ..self.clone()
+            };
+        }
+        let ratio = self.sample_rate as f64 / sample_rate as f64;
+        let frames = (self.frames() as f64 / ratio).round() as usize;
+        let mut resampled = self.map_channels(|_, samples| {
+            (0..frames)
+                .map(|frame| {
+                    let position = frame as f64 * ratio;
+                    let index = position.floor() as usize;
+                    let fraction = (position - index as f64) as f32;
+                    let current = samples[index.min(samples.len() - 1)];
+                    let next = samples[(index + 1).min(samples.len() - 1)];
+                    current + (next - current) * fraction
+                })
+                .collect()
+        });
+        resampled.sample_rate = sample_rate;
+        resampled
+    }
+    /// Cuts the buffer down, or pads it with silence, to the given number of frames.
+    pub fn with_frames(&self, frames: usize) -> Self {
+        let mut buffer = self.clone();
+        buffer.samples.resize(frames * self.channels as usize, 0.0);
+        buffer
+    }
+    ///
+    /// Encodes the buffer as a 16 bit PCM WAV file.
+    ///
+    pub fn to_wav(&self) -> Vec<u8> {
+        let data_length = self.samples.len() * 2;
+        let block_align = self.channels as u32 * 2;
+        let mut wav = Vec::with_capacity(44 + data_length);
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&(36 + data_length as u32).to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes());
+        wav.extend_from_slice(&self.channels.to_le_bytes());
+        wav.extend_from_slice(&self.sample_rate.to_le_bytes());
+        wav.extend_from_slice(&(self.sample_rate * block_align).to_le_bytes());
+        wav.extend_from_slice(&(block_align as u16).to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&(data_length as u32).to_le_bytes());
+        for sample in self.samples.iter() {
+            let value = (sample.max(-1.0).min(1.0) * std::i16::MAX as f32).round() as i16;
+            wav.extend_from_slice(&value.to_le_bytes());
+        }
+        wav
+    }
+    ///
+    /// Decodes a WAV file of 16 bit PCM or 32 bit float samples.
+    ///
+    pub fn from_wav(wav: &[u8]) -> Result<Self, String> {
+        if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+            return Err(String::from("not a WAV file"));
+        }
+        let u16_at = |at: usize| u16::from_le_bytes([wav[at], wav[at + 1]]);
+        let u32_at = |at: usize| u32::from_le_bytes([wav[at], wav[at + 1], wav[at + 2], wav[at + 3]]);
+        let mut format = None;
+        let mut at = 12;
+        while at + 8 <= wav.len() {
+            let (id, length) = (&wav[at..at + 4], u32_at(at + 4) as usize);
+            let body = at + 8;
+            if body + length > wav.len() {
+                return Err(String::from("WAV chunk runs past the end of the file"));
+            }
+            if id == b"fmt " && length >= 16 {
+                format = Some((u16_at(body), u16_at(body + 2), u32_at(body + 4), u16_at(body + 14)));
+            } else if id == b"data" {
+                let (encoding, channels, sample_rate, bits) = match format {
+                    Some(format) => format,
+                    None => return Err(String::from("WAV data comes before its format")),
+                };
+                let data = &wav[body..body + length];
+                let samples = match (encoding, bits) {
+                    (1, 16) => data
+                        .chunks_exact(2)
+                        .map(|bytes| i16::from_le_bytes([bytes[0], bytes[1]]) as f32 / std::i16::MAX as f32)
+                        .collect(),
+                    (3, 32) => data
+                        .chunks_exact(4)
+                        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+                        .collect(),
+                    _ => return Err(format!("unsupported WAV encoding {} with {} bits", encoding, bits)),
+                };
+                return Ok(Self {
+                    sample_rate,
+                    channels,
+                    samples,
+                });
+            }
+            // Chunks are padded to an even length.
+            at = body + length + length % 2;
+        }
+        Err(String::from("WAV file has no data"))
+    }
+    /// Saves the buffer as a 16 bit PCM WAV file.
+    pub fn save_wav(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_wav()).map_err(|e| format!("could not save audio {}: {}", path.display(), e))
+    }
+    /// Loads a WAV file of 16 bit PCM or 32 bit float samples.
+    pub fn load_wav(path: &Path) -> Result<Self, String> {
+        let wav = std::fs::read(path).map_err(|e| format!("could not load audio {}: {}", path.display(), e))?;
+        Self::from_wav(&wav).map_err(|e| format!("could not load audio {}: {}", path.display(), e))
+    }
+}
+
+///
+/// Runs a process over samples, BLOCK_SIZE at a time, and gives back what it writes.
+/// The process is given the index of the first sample in the block, the input block, and the output block to fill.
+/// Processes that keep state, like oscillator phases or filter history, carry it from one block to the next.
+///
+pub fn process_in_blocks<F>(input: &[f32], mut process: F) -> Vec<f32>
+where
+    F: FnMut(usize, &[f32], &mut [f32]),
+{
+    let mut output = vec![0.0; input.len()];
+    for (block, (input, output)) in input.chunks(BLOCK_SIZE).zip(output.chunks_mut(BLOCK_SIZE)).enumerate() {
+        process(block * BLOCK_SIZE, input, output);
+    }
+    output
+}
+
+///
+/// Gets the audio on an input pin, which is an error if there is none.
+///
+pub fn audio_input(node: &Node, id: &str) -> Result<AudioBuffer, String> {
+    match node.get_input::<AudioBuffer>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(audio) => Ok(audio),
+        None => Err(format!("no audio on input pin {}", id)),
+    }
+}
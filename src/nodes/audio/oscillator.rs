@@ -0,0 +1,182 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use rand::Rng;
+
+///
+/// The waveforms an oscillator can make.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Saw,
+    Triangle,
+    Noise,
+}
+
+/// The name, graph UUID and waveform of every oscillator node.
+pub const OPERATIONS: [(&str, &str, Waveform); 5] = [
+    ("Sine Oscillator", "aa8946a0-8efd-43c2-88e9-1717ca44f85f", Waveform::Sine),
+    ("Square Oscillator", "b51ae3be-1f02-43bd-bcb7-6873e7309ec4", Waveform::Square),
+    ("Saw Oscillator", "9f22e3f2-681a-4829-be63-ef56989a27a1", Waveform::Saw),
+    ("Triangle Oscillator", "4345575c-638a-4962-9a04-09ee3c305280", Waveform::Triangle),
+    ("Noise Oscillator", "1f6b151c-a16e-48d6-beef-d982fb98be69", Waveform::Noise),
+];
+
+const FREQUENCY: &str = "e8332c90-58b0-4903-94d7-f04d4a9d1207";
+const AMPLITUDE: &str = "da611736-b79f-4cc5-b8e7-cd793a8e446e";
+const DURATION: &str = "757f68fa-b641-40bd-9449-b29ef88b1840";
+const SAMPLE_RATE: &str = "d8d3d76e-fa16-4575-b083-35999ff33a75";
+const SEED: &str = "7f51a73e-9fb5-43af-8e2e-73e6b98aca2b";
+const OUTPUT_AUDIO: &str = "9c740a3d-08b8-44ac-b617-018ab1f8d66a";
+
+impl Waveform {
+    /// Gets the value of the waveform at a phase between zero and one.
+    pub fn sample(self, phase: f32) -> f32 {
+        match self {
+            Waveform::Sine => (phase * std::f32::consts::PI * 2.0).sin(),
+            Waveform::Square => {
+                if phase < 0.5 {
+                    1.0
+                } else {
+                    -1.0
+                }
+            }
+            Waveform::Saw => phase * 2.0 - 1.0,
+            Waveform::Triangle => 1.0 - 4.0 * (phase - 0.5).abs(),
+            Waveform::Noise => 0.0,
+        }
+    }
+}
+
+///
+/// Generates a mono tone, or white noise, for a number of seconds.
+/// Noise is seeded, so the same seed always gives the same noise.
+///
+pub struct NodeAudioOscillatorV1 {
+    pub op: Waveform,
+}
+
+impl Nodeable for NodeAudioOscillatorV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        if self.op == Waveform::Noise {
+            inputs.push(input_pin("Seed", SEED, "u64", false));
+        } else {
+            inputs.push(input_pin("Frequency", FREQUENCY, "f64", false));
+        }
+        inputs.push(input_pin("Amplitude", AMPLITUDE, "f64", false));
+        inputs.push(input_pin("Duration", DURATION, "f64", false));
+        inputs.push(input_pin("Sample Rate", SAMPLE_RATE, "u64", false));
+        outputs.push(output_pin("Audio", OUTPUT_AUDIO, "audio"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_AUDIO).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+        };
+        let sample_rate = node
+            .get_input::<u64>(&uuid::Uuid::parse_str(SAMPLE_RATE).unwrap())
+            .unwrap_or(DEFAULT_SAMPLE_RATE as u64) as u32;
+        if sample_rate == 0 {
+            return Err(String::from("sample rate must be above zero"));
+        }
+        let duration = get_f64(DURATION, 1.0).max(0.0);
+        let amplitude = get_f64(AMPLITUDE, 0.5) as f32;
+        let frames = (duration * sample_rate as f64).round() as usize;
+        let samples = if self.op == Waveform::Noise {
+            let mut rng = match node.get_input::<u64>(&uuid::Uuid::parse_str(SEED).unwrap()) {
+                Some(seed) => crate::seed::seeded_rng(seed),
+                None => node.rng(&output_info.uuid),
+            };
+            process_in_blocks(&vec![0.0; frames], |_, _, output| {
+                for sample in output.iter_mut() {
+                    *sample = rng.gen_range(-1.0, 1.0) * amplitude;
+                }
+            })
+        } else {
+            let step = (get_f64(FREQUENCY, 440.0) / sample_rate as f64) as f32;
+            let mut phase = 0.0f32;
+            let waveform = self.op;
+            process_in_blocks(&vec![0.0; frames], |_, _, output| {
+                for sample in output.iter_mut() {
+                    *sample = waveform.sample(phase) * amplitude;
+                    phase = (phase + step).rem_euclid(1.0);
+                }
+            })
+        };
+        Ok(Some(Message::new(AudioBuffer::mono(sample_rate, samples))))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioOscillatorV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: Waveform,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeAudioOscillatorV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
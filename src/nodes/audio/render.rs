@@ -0,0 +1,134 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Renders audio offline to a WAV file, without needing an audio device.
+/// The audio is resampled to the sample rate, then cut or padded with silence to the length in seconds.
+/// Rendering happens when the path output is computed, and gives back the path that was written.
+///
+#[derive(Default)]
+pub struct NodeAudioRenderV1 {}
+
+///
+/// Fits audio to a sample rate and length, ready to be written out.
+///
+pub fn render(audio: &AudioBuffer, seconds: f64, sample_rate: u32) -> AudioBuffer {
+    audio
+        .resampled(sample_rate)
+        .with_frames((seconds.max(0.0) * sample_rate as f64).round() as usize)
+}
+
+impl Nodeable for NodeAudioRenderV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Audio", "4b053e16-64ff-4a8c-9454-c5ba669ac30c", "audio", false));
+        inputs.push(input_pin("Path", "c74ad962-befa-4d3a-8232-5016ad888577", "string", false));
+        inputs.push(input_pin("Seconds", "85d6ceb6-7863-4916-87c9-350c063682b9", "f64", false));
+        inputs.push(input_pin("Sample Rate", "ec3e81bd-8c37-4dc3-b7b1-f4f3729f8ece", "u64", false));
+        outputs.push(output_pin("Path", "fa3047c5-ff53-4407-8a92-068fdcdc96e6", "string"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_path if id_path == uuid::Uuid::parse_str("fa3047c5-ff53-4407-8a92-068fdcdc96e6").unwrap() => {
+                let audio = audio_input(node, "4b053e16-64ff-4a8c-9454-c5ba669ac30c")?;
+                let path = match node.get_input::<String>(&uuid::Uuid::parse_str("c74ad962-befa-4d3a-8232-5016ad888577").unwrap()) {
+                    Some(path) => std::path::PathBuf::from(path),
+                    None => return Err(String::from("no audio path given")),
+                };
+                let seconds = node
+                    .get_input::<f64>(&uuid::Uuid::parse_str("85d6ceb6-7863-4916-87c9-350c063682b9").unwrap())
+                    .unwrap_or_else(|| audio.duration());
+                let sample_rate = node
+                    .get_input::<u64>(&uuid::Uuid::parse_str("ec3e81bd-8c37-4dc3-b7b1-f4f3729f8ece").unwrap())
+                    .unwrap_or(audio.sample_rate as u64) as u32;
+                if sample_rate == 0 {
+                    return Err(String::from("sample rate must be above zero"));
+                }
+                render(&audio, seconds, sample_rate).save_wav(&path)?;
+                Ok(Some(Message::new(path.to_string_lossy().into_owned())))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeAudioRenderV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Render Audio"),
+                    uuid: uuid::Uuid::parse_str("693cbadd-b90e-4bd1-a81e-8de7bf31dcbe").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Render Audio"),
+            uuid: uuid::Uuid::parse_str("693cbadd-b90e-4bd1-a81e-8de7bf31dcbe").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("693cbadd-b90e-4bd1-a81e-8de7bf31dcbe").unwrap() {
+        return match version {
+            1 => Some(NodeAudioRenderV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,126 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+use crate::nodes::image::blend::BlendMode;
+
+///
+/// Blends a layer color over a base color with any of the image blend modes.
+/// The layer's alpha and the opacity control how much of the blend shows, and the base keeps its alpha.
+/// A normal blend at partial opacity mixes the two colors.
+///
+#[derive(Default)]
+pub struct NodeColorBlendV1 {}
+
+impl Nodeable for NodeColorBlendV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Base", "4f0b347b-67f7-4770-987e-a114e2396af9", "color", false));
+        inputs.push(input_pin("Layer", "46a18fe6-e138-46ea-8811-bec2cc964fda", "color", false));
+        inputs.push(input_pin("Mode", "1480071e-a7e3-4faa-a908-ab3dd4660f5f", "string", false));
+        inputs.push(input_pin("Opacity", "f944e6a4-6078-4eee-a3ee-a900da019b67", "f64", false));
+        outputs.push(output_pin("Color", "3ef1ff67-73bf-47bd-ba0e-78d3a4414a62", "color"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_color if id_color == uuid::Uuid::parse_str("3ef1ff67-73bf-47bd-ba0e-78d3a4414a62").unwrap() => {
+                let base = color_input(node, "4f0b347b-67f7-4770-987e-a114e2396af9")?;
+                let layer = color_input(node, "46a18fe6-e138-46ea-8811-bec2cc964fda")?;
+                let mode_name = node
+                    .get_input::<String>(&uuid::Uuid::parse_str("1480071e-a7e3-4faa-a908-ab3dd4660f5f").unwrap())
+                    .unwrap_or_else(|| String::from("normal"));
+                let mode = match BlendMode::parse(&mode_name) {
+                    Some(mode) => mode,
+                    None => return Err(format!("unknown blend mode {}", mode_name)),
+                };
+                let opacity = node
+                    .get_input::<f64>(&uuid::Uuid::parse_str("f944e6a4-6078-4eee-a3ee-a900da019b67").unwrap())
+                    .unwrap_or(1.0) as f32;
+                let amount = layer.a * opacity;
+                let blend = |base: f32, layer: f32| base + (mode.apply(base, layer) - base) * amount;
+                let blended = Color::new(blend(base.r, layer.r), blend(base.g, layer.g), blend(base.b, layer.b), base.a);
+                Ok(Some(Message::new(blended)))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeColorBlendV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Blend Colors"),
+                    uuid: uuid::Uuid::parse_str("14199e6e-7d92-4918-a863-0268e905898b").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Blend Colors"),
+            uuid: uuid::Uuid::parse_str("14199e6e-7d92-4918-a863-0268e905898b").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("14199e6e-7d92-4918-a863-0268e905898b").unwrap() {
+        return match version {
+            1 => Some(NodeColorBlendV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,143 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The ways a color can be built.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConstructOp {
+    Rgb,
+    Hsv,
+    Hsl,
+    Hex,
+}
+
+/// The name, graph UUID and operation of every color construction node.
+pub const OPERATIONS: [(&str, &str, ConstructOp); 4] = [
+    ("Color From RGB", "cda84845-481f-4d4f-8479-ffa4a703a58e", ConstructOp::Rgb),
+    ("Color From HSV", "57cc2c68-0ba8-4bf6-aaaa-a6c3bef7472e", ConstructOp::Hsv),
+    ("Color From HSL", "66262e63-4683-45df-b516-d8f3fef80c36", ConstructOp::Hsl),
+    ("Color From Hex", "6e09d2c4-2963-4d77-859b-fa500c31f6f5", ConstructOp::Hex),
+];
+
+/// The three component pins, named for the color model of each operation.
+const COMPONENTS: [&str; 3] = ["a8c2625f-ed44-46c4-93b5-b52ef9be4c3d", "f5ab509e-a6eb-478c-aa61-1e876b27874a", "79755023-3e9c-474c-87da-3c3e72277314"];
+const ALPHA: &str = "a671ec10-e4b5-40cd-874d-f30a8e49b2a6";
+const HEX: &str = "8d003a86-e7d7-41d4-a5b3-2211e7d81744";
+const OUTPUT_COLOR: &str = "faff9fe1-7e7a-4f3b-9739-165bfa716524";
+
+///
+/// Builds a color from red, green and blue, from hue, saturation and value or lightness, or from a hex code.
+/// Components are between zero and one, with hues in turns, and missing components are zero, or opaque for alpha.
+///
+pub struct NodeColorConstructV1 {
+    pub op: ConstructOp,
+}
+
+impl Nodeable for NodeColorConstructV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let outputs = vec![output_pin("Color", OUTPUT_COLOR, "color")];
+        let names = match self.op {
+            ConstructOp::Rgb => ["Red", "Green", "Blue"],
+            ConstructOp::Hsv => ["Hue", "Saturation", "Value"],
+            ConstructOp::Hsl => ["Hue", "Saturation", "Lightness"],
+            ConstructOp::Hex => {
+                inputs.push(input_pin("Hex", HEX, "string", false));
+                return (inputs, outputs);
+            }
+        };
+        for (name, id) in names.iter().zip(COMPONENTS.iter()) {
+            inputs.push(input_pin(name, id, "f64", false));
+        }
+        inputs.push(input_pin("Alpha", ALPHA, "f64", false));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_COLOR).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default) as f32
+        };
+        let [x, y, z] = [get_f64(COMPONENTS[0], 0.0), get_f64(COMPONENTS[1], 0.0), get_f64(COMPONENTS[2], 0.0)];
+        let alpha = get_f64(ALPHA, 1.0);
+        let color = match self.op {
+            ConstructOp::Rgb => Color::new(x, y, z, alpha),
+            ConstructOp::Hsv => Color::from_hsv(x, y, z, alpha),
+            ConstructOp::Hsl => Color::from_hsl(x, y, z, alpha),
+            ConstructOp::Hex => match node.get_input::<String>(&uuid::Uuid::parse_str(HEX).unwrap()) {
+                Some(code) => Color::from_hex(&code)?,
+                None => return Err(String::from("no color code given")),
+            },
+        };
+        Ok(Some(Message::new(color)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeColorConstructV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ConstructOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeColorConstructV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,154 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The ways a color can be taken apart or re-encoded.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConvertOp {
+    ToRgb,
+    ToHsv,
+    ToHsl,
+    ToHex,
+    SrgbToLinear,
+    LinearToSrgb,
+}
+
+/// The name, graph UUID and operation of every color conversion node.
+pub const OPERATIONS: [(&str, &str, ConvertOp); 6] = [
+    ("Color To RGB", "635b5d5e-201b-4a2d-91f5-247bee725fcb", ConvertOp::ToRgb),
+    ("Color To HSV", "624abf83-f42c-4a92-80c7-f6081bb5f977", ConvertOp::ToHsv),
+    ("Color To HSL", "02701e38-6435-48c6-9e8e-f732a35c712c", ConvertOp::ToHsl),
+    ("Color To Hex", "d4be8a42-9faf-40ec-9a74-93c81fa9b705", ConvertOp::ToHex),
+    ("sRGB To Linear", "ecad0164-cf9a-4528-a292-db70d547da3a", ConvertOp::SrgbToLinear),
+    ("Linear To sRGB", "cd8bb742-dd98-4aa9-a355-6c1a1d0b83d3", ConvertOp::LinearToSrgb),
+];
+
+const COLOR: &str = "35e076b4-ae7f-4bea-8b13-6c40dcba825e";
+/// The three component output pins, named for the color model of each operation.
+const COMPONENTS: [&str; 3] = ["d25d88ab-5655-477d-bd83-edc05fe99ec5", "a3c2e79b-e816-490e-a9d1-07ca28c998fc", "203b5067-90fa-468b-9a2c-cbf65e56c5b2"];
+const OUTPUT_ALPHA: &str = "bbfe2f36-b896-4c61-9543-b374fe597274";
+const OUTPUT_HEX: &str = "1ca7b29d-5154-418c-8cef-575be91883c8";
+const OUTPUT_COLOR: &str = "1988010c-507e-4bab-9464-e7fa5671107e";
+
+///
+/// Splits a color into red, green and blue, hue, saturation and value or lightness, or a hex code,
+/// or converts it between sRGB encoding and linear light.
+///
+pub struct NodeColorConvertV1 {
+    pub op: ConvertOp,
+}
+
+impl Nodeable for NodeColorConvertV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Color", COLOR, "color", false));
+        let names = match self.op {
+            ConvertOp::ToRgb => ["Red", "Green", "Blue"],
+            ConvertOp::ToHsv => ["Hue", "Saturation", "Value"],
+            ConvertOp::ToHsl => ["Hue", "Saturation", "Lightness"],
+            ConvertOp::ToHex => {
+                outputs.push(output_pin("Hex", OUTPUT_HEX, "string"));
+                return (inputs, outputs);
+            }
+            ConvertOp::SrgbToLinear | ConvertOp::LinearToSrgb => {
+                outputs.push(output_pin("Color", OUTPUT_COLOR, "color"));
+                return (inputs, outputs);
+            }
+        };
+        for (name, id) in names.iter().zip(COMPONENTS.iter()) {
+            outputs.push(output_pin(name, id, "f64"));
+        }
+        outputs.push(output_pin("Alpha", OUTPUT_ALPHA, "f64"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let color = color_input(node, COLOR)?;
+        let components = match self.op {
+            ConvertOp::ToRgb => [color.r, color.g, color.b],
+            ConvertOp::ToHsv => color.hsv(),
+            ConvertOp::ToHsl => color.hsl(),
+            ConvertOp::ToHex => return Ok(Some(Message::new(color.to_hex()))),
+            ConvertOp::SrgbToLinear => return Ok(Some(Message::new(color.to_linear()))),
+            ConvertOp::LinearToSrgb => return Ok(Some(Message::new(color.to_srgb()))),
+        };
+        if output_info.uuid == uuid::Uuid::parse_str(OUTPUT_ALPHA).unwrap() {
+            return Ok(Some(Message::new(color.a as f64)));
+        }
+        let index = COMPONENTS
+            .iter()
+            .position(|id| output_info.uuid == uuid::Uuid::parse_str(id).unwrap())
+            .unwrap_or(0);
+        Ok(Some(Message::new(components[index] as f64)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeColorConvertV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ConvertOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeColorConvertV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
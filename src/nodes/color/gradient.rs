@@ -0,0 +1,181 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Whether a gradient is being built, sampled at a value, or used to color an image.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientOp {
+    Create,
+    Sample,
+    Map,
+}
+
+/// The name, graph UUID and operation of every gradient node.
+pub const OPERATIONS: [(&str, &str, GradientOp); 3] = [
+    ("Color Gradient", "25c36d30-f770-41e8-aea8-ae3a3e1b060d", GradientOp::Create),
+    ("Sample Gradient", "e7958bd5-ca2d-4ce9-a1b2-264a0fa72e8c", GradientOp::Sample),
+    ("Gradient Map", "0cce16a8-e7c2-4387-9f7a-720a775b5a95", GradientOp::Map),
+];
+
+const COLORS: &str = "7b69cd02-a47f-4911-adfe-668d942a2696";
+const POSITIONS: &str = "7d084f2d-4dae-4062-b96e-732ff7a45f98";
+const PALETTE: &str = "db2dd2e4-32d7-4a52-b14e-e83ba39a6c5d";
+const GRADIENT: &str = "8849fda5-89f1-4a07-8fd6-bf73c92f2d67";
+const VALUE: &str = "ce83f531-5c1d-4a1a-bd1c-f5fae8b8336c";
+const IMAGE: &str = "a00a3ba1-e0a8-4793-bf16-2f181ec84828";
+const MIN: &str = "d38f28e8-0681-4bfc-aa13-a735a2e0ad59";
+const MAX: &str = "63a27953-4783-43ae-9dd9-ea7817d064b0";
+const OUTPUT_GRADIENT: &str = "eeadfe80-783e-40d7-b051-5ae563328f36";
+const OUTPUT_COLOR: &str = "8ed49a3c-8258-4c8e-98b6-e6100e872f97";
+const OUTPUT_IMAGE: &str = "c20c396c-09b2-4d73-bc3f-c36e564183de";
+
+///
+/// Builds a gradient from keyframes, gets the color at a value along one, or colors an image with one.
+/// Keyframes are the colors, followed by any palette, placed at the positions given in order,
+/// and spread evenly between zero and one when there are not enough positions.
+/// Gradient maps use the brightness of each pixel between the min and max as the value, which suits heightmaps.
+///
+pub struct NodeColorGradientV1 {
+    pub op: GradientOp,
+}
+
+impl Nodeable for NodeColorGradientV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            GradientOp::Create => {
+                inputs.push(input_pin("Colors", COLORS, "color", true));
+                inputs.push(input_pin("Positions", POSITIONS, "f64", true));
+                inputs.push(input_pin("Palette", PALETTE, "palette", false));
+                outputs.push(output_pin("Gradient", OUTPUT_GRADIENT, "gradient"));
+            }
+            GradientOp::Sample => {
+                inputs.push(input_pin("Gradient", GRADIENT, "gradient", false));
+                inputs.push(input_pin("Value", VALUE, "f64", false));
+                outputs.push(output_pin("Color", OUTPUT_COLOR, "color"));
+            }
+            GradientOp::Map => {
+                inputs.push(input_pin("Gradient", GRADIENT, "gradient", false));
+                inputs.push(input_pin("Image", IMAGE, "image", false));
+                inputs.push(input_pin("Min", MIN, "f64", false));
+                inputs.push(input_pin("Max", MAX, "f64", false));
+                outputs.push(output_pin("Image", OUTPUT_IMAGE, "image"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        match self.op {
+            GradientOp::Create => {
+                let mut colors = node.get_inputs::<Color>(&uuid::Uuid::parse_str(COLORS).unwrap());
+                if let Some(palette) = node.get_input::<Vec<Color>>(&uuid::Uuid::parse_str(PALETTE).unwrap()) {
+                    colors.extend(palette);
+                }
+                if colors.is_empty() {
+                    return Err(String::from("no colors for the gradient"));
+                }
+                let positions = node.get_inputs::<f64>(&uuid::Uuid::parse_str(POSITIONS).unwrap());
+                let gradient = if positions.len() >= colors.len() {
+                    Gradient::new(positions.iter().map(|position| *position as f32).zip(colors).collect())
+                } else {
+                    Gradient::even(&colors)
+                };
+                Ok(Some(Message::new(gradient)))
+            }
+            GradientOp::Sample => {
+                let gradient = gradient_input(node, GRADIENT)?;
+                let value = node
+                    .get_input::<f64>(&uuid::Uuid::parse_str(VALUE).unwrap())
+                    .unwrap_or(0.0);
+                Ok(Some(Message::new(gradient.sample(value as f32))))
+            }
+            GradientOp::Map => {
+                let gradient = gradient_input(node, GRADIENT)?;
+                let image = crate::nodes::image::image_input(node, IMAGE)?;
+                let get_f64 = |id: &str, default: f64| {
+                    node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                        .unwrap_or(default) as f32
+                };
+                let (min, max) = (get_f64(MIN, 0.0), get_f64(MAX, 1.0));
+                let range = if max == min { 1.0 } else { max - min };
+                let mapped = image.map(|pixel| {
+                    let brightness = 0.2126 * pixel[0] + 0.7152 * pixel[1] + 0.0722 * pixel[2];
+                    let color = gradient.sample((brightness - min) / range);
+                    [color.r, color.g, color.b, color.a * pixel[3]]
+                });
+                Ok(Some(Message::new(mapped)))
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeColorGradientV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: GradientOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeColorGradientV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,222 @@
+pub mod blend;
+pub mod construct;
+pub mod convert;
+pub mod gradient;
+pub mod palette;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut construct::register());
+    nodes.append(&mut convert::register());
+    nodes.append(&mut palette::register());
+    nodes.append(&mut gradient::register());
+    nodes.push(blend::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(construct::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(convert::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(palette::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(gradient::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(blend::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use bracket_color::prelude::{HSV, RGB};
+use serde::{Deserialize, Serialize};
+
+///
+/// A color with alpha, carried on pins with the "color" datatype.
+/// Channels are between zero and one and sRGB encoded, unless a color has been converted to linear.
+/// Hues, as used by HSV and HSL, are in turns between zero and one, like bracket-color.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Default for Color {
+    fn default() -> Self {
+        Self::new(0.0, 0.0, 0.0, 1.0)
+    }
+}
+
+impl Color {
+    pub fn new(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+    pub fn from_rgb(rgb: RGB, a: f32) -> Self {
+        Self::new(rgb.r, rgb.g, rgb.b, a)
+    }
+    pub fn rgb(&self) -> RGB {
+        RGB::from_f32(self.r, self.g, self.b)
+    }
+    /// Creates a color from an image pixel.
+    pub fn from_pixel(pixel: [f32; 4]) -> Self {
+        Self::new(pixel[0], pixel[1], pixel[2], pixel[3])
+    }
+    /// Gives back the color as an image pixel.
+    pub fn pixel(&self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+    pub fn from_hsv(h: f32, s: f32, v: f32, a: f32) -> Self {
+        Self::from_rgb(HSV::from_f32(h.rem_euclid(1.0), s, v).to_rgb(), a)
+    }
+    /// Gives back the hue, saturation and value of the color.
+    pub fn hsv(&self) -> [f32; 3] {
+        let hsv = self.rgb().to_hsv();
+        [hsv.h, hsv.s, hsv.v]
+    }
+    pub fn from_hsl(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let v = l + s * l.min(1.0 - l);
+        let s = if v == 0.0 { 0.0 } else { 2.0 * (1.0 - l / v) };
+        Self::from_hsv(h, s, v, a)
+    }
+    /// Gives back the hue, saturation and lightness of the color.
+    pub fn hsl(&self) -> [f32; 3] {
+        let [h, s, v] = self.hsv();
+        let l = v * (1.0 - s / 2.0);
+        let s = if l == 0.0 || l == 1.0 { 0.0 } else { (v - l) / l.min(1.0 - l) };
+        [h, s, l]
+    }
+    ///
+    /// Reads a color from an HTML style hex code, like #ff8000.
+    /// Eight digit codes carry alpha in the last two digits.
+    ///
+    pub fn from_hex(code: &str) -> Result<Self, String> {
+        let code = code.trim();
+        let digits = code.trim_start_matches('#');
+        let (rgb, alpha) = match digits.len() {
+            6 => (digits, None),
+            8 => (&digits[0..6], Some(&digits[6..8])),
+            _ => return Err(format!("color code {} must have six or eight hex digits", code)),
+        };
+        let rgb = RGB::from_hex(format!("#{}", rgb)).map_err(|e| format!("invalid color code {}: {:?}", code, e))?;
+        let a = match alpha {
+            Some(alpha) => u8::from_str_radix(alpha, 16).map_err(|e| format!("invalid color code {}: {}", code, e))? as f32 / 255.0,
+            None => 1.0,
+        };
+        Ok(Self::from_rgb(rgb, a))
+    }
+    /// Writes the color as a hex code, which includes alpha when the color is not opaque.
+    pub fn to_hex(&self) -> String {
+        let byte = |value: f32| (value.max(0.0).min(1.0) * 255.0).round() as u8;
+        let mut code = format!("#{:02x}{:02x}{:02x}", byte(self.r), byte(self.g), byte(self.b));
+        if byte(self.a) != 255 {
+            code.push_str(&format!("{:02x}", byte(self.a)));
+        }
+        code
+    }
+    /// Changes every color channel, leaving alpha alone.
+    pub fn map<F>(&self, f: F) -> Self
+    where
+        F: Fn(f32) -> f32,
+    {
+        Self::new(f(self.r), f(self.g), f(self.b), self.a)
+    }
+    /// Converts an sRGB encoded color to linear light.
+    pub fn to_linear(&self) -> Self {
+        self.map(|value| {
+            if value <= 0.04045 {
+                value / 12.92
+            } else {
+                ((value + 0.055) / 1.055).powf(2.4)
+            }
+        })
+    }
+    /// Converts a linear light color to sRGB encoding.
+    pub fn to_srgb(&self) -> Self {
+        self.map(|value| {
+            if value <= 0.003_130_8 {
+                value * 12.92
+            } else {
+                1.055 * value.powf(1.0 / 2.4) - 0.055
+            }
+        })
+    }
+    /// Blends towards another color, including alpha, by an amount between zero and one.
+    pub fn lerp(&self, other: &Color, amount: f32) -> Self {
+        Self::from_rgb(self.rgb().lerp(other.rgb(), amount), self.a + (other.a - self.a) * amount)
+    }
+}
+
+///
+/// A ramp of colors, carried on pins with the "gradient" datatype.
+/// Each keyframe places a color at a position, and colors between keyframes are blended.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Gradient {
+    /// Keyframe positions and colors, ordered by position.
+    pub keys: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    /// Creates a gradient from keyframes in any order.
+    pub fn new(mut keys: Vec<(f32, Color)>) -> Self {
+        keys.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+        Self { keys }
+    }
+    /// Creates a gradient with the colors spread evenly between zero and one.
+    pub fn even(colors: &[Color]) -> Self {
+        let last = colors.len().saturating_sub(1).max(1) as f32;
+        Self::new(colors.iter().enumerate().map(|(index, color)| (index as f32 / last, *color)).collect())
+    }
+    ///
+    /// Gets the color at a position, which is the first or last color past either end.
+    /// A gradient without keyframes is transparent.
+    ///
+    pub fn sample(&self, position: f32) -> Color {
+        let (first, last) = match (self.keys.first(), self.keys.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Color::new(0.0, 0.0, 0.0, 0.0),
+        };
+        if position <= first.0 {
+            return first.1;
+        }
+        if position >= last.0 {
+            return last.1;
+        }
+        let next = self.keys.iter().position(|(key, _)| *key > position).unwrap_or(self.keys.len() - 1);
+        let (start, end) = (self.keys[next - 1], self.keys[next]);
+        start.1.lerp(&end.1, (position - start.0) / (end.0 - start.0))
+    }
+}
+
+///
+/// Gets the color on an input pin, which is an error if there is none.
+///
+pub fn color_input(node: &Node, id: &str) -> Result<Color, String> {
+    match node.get_input::<Color>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(color) => Ok(color),
+        None => Err(format!("no color on input pin {}", id)),
+    }
+}
+
+///
+/// Gets the gradient on an input pin, which is an error if there is none.
+///
+pub fn gradient_input(node: &Node, id: &str) -> Result<Gradient, String> {
+    match node.get_input::<Gradient>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(gradient) => Ok(gradient),
+        None => Err(format!("no gradient on input pin {}", id)),
+    }
+}
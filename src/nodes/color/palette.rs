@@ -0,0 +1,173 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use rand::Rng;
+
+///
+/// The ways a palette can be generated.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PaletteOp {
+    Analogous,
+    Complementary,
+    Random,
+}
+
+/// The name, graph UUID and operation of every palette node.
+pub const OPERATIONS: [(&str, &str, PaletteOp); 3] = [
+    ("Analogous Palette", "e6fb8307-486b-4a3f-b3b8-c93f45880b82", PaletteOp::Analogous),
+    ("Complementary Palette", "653103b5-97bd-41ff-9dc5-f4ebe18e2068", PaletteOp::Complementary),
+    ("Random Palette", "fe23653d-3ac0-4eee-88e1-9d178249c904", PaletteOp::Random),
+];
+
+const COLOR: &str = "d143d70c-3fba-4a9f-8cda-6c26570a2757";
+const COUNT: &str = "3431a8f2-a6b2-49d1-889a-3e9c3315cc4b";
+const SPREAD: &str = "77069c4e-0ece-4292-994c-e65c8c13493c";
+const SEED: &str = "d295ba6c-6afd-4035-8cfb-c216b60dbdc0";
+const SATURATION: &str = "cd249ed5-7e46-441b-90a5-e5595c64df8a";
+const VALUE: &str = "d1c37f40-1295-49df-ab96-009a1803a3c2";
+const OUTPUT_PALETTE: &str = "db4e3173-5460-438b-89dd-0f9a547bbff1";
+
+///
+/// Gives back a color with its hue turned by an amount in turns.
+///
+pub fn rotate_hue(color: &Color, turns: f32) -> Color {
+    let [h, s, v] = color.hsv();
+    Color::from_hsv(h + turns, s, v, color.a)
+}
+
+///
+/// Generates a list of colors, carried on pins with the "palette" datatype.
+/// Analogous palettes are centered on the color with neighbouring hues the spread apart, in turns.
+/// Complementary palettes are the color and the one opposite it, and random palettes pick hues from the seed.
+///
+pub struct NodeColorPaletteV1 {
+    pub op: PaletteOp,
+}
+
+impl Nodeable for NodeColorPaletteV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            PaletteOp::Analogous => {
+                inputs.push(input_pin("Color", COLOR, "color", false));
+                inputs.push(input_pin("Count", COUNT, "u64", false));
+                inputs.push(input_pin("Spread", SPREAD, "f64", false));
+            }
+            PaletteOp::Complementary => inputs.push(input_pin("Color", COLOR, "color", false)),
+            PaletteOp::Random => {
+                inputs.push(input_pin("Seed", SEED, "u64", false));
+                inputs.push(input_pin("Count", COUNT, "u64", false));
+                inputs.push(input_pin("Saturation", SATURATION, "f64", false));
+                inputs.push(input_pin("Value", VALUE, "f64", false));
+            }
+        }
+        outputs.push(output_pin("Palette", OUTPUT_PALETTE, "palette"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_PALETTE).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default) as f32
+        };
+        let count = node
+            .get_input::<u64>(&uuid::Uuid::parse_str(COUNT).unwrap())
+            .unwrap_or(5) as usize;
+        let palette: Vec<Color> = match self.op {
+            PaletteOp::Analogous => {
+                let color = color_input(node, COLOR)?;
+                let spread = get_f64(SPREAD, 1.0 / 12.0);
+                let middle = count.saturating_sub(1) as f32 / 2.0;
+                (0..count)
+                    .map(|index| rotate_hue(&color, (index as f32 - middle) * spread))
+                    .collect()
+            }
+            PaletteOp::Complementary => {
+                let color = color_input(node, COLOR)?;
+                vec![color, rotate_hue(&color, 0.5)]
+            }
+            PaletteOp::Random => {
+                let saturation = get_f64(SATURATION, 0.65);
+                let value = get_f64(VALUE, 0.85);
+                let mut rng = match node.get_input::<u64>(&uuid::Uuid::parse_str(SEED).unwrap()) {
+                    Some(seed) => crate::seed::seeded_rng(seed),
+                    None => node.rng(&output_info.uuid),
+                };
+                (0..count)
+                    .map(|_| Color::from_hsv(rng.gen_range(0.0, 1.0), saturation, value, 1.0))
+                    .collect()
+            }
+        };
+        Ok(Some(Message::new(palette)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeColorPaletteV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: PaletteOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeColorPaletteV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,113 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Sends on one of two pins depending on a received condition.
+/// The value input is sent along if it has one, and otherwise the condition itself is.
+///
+#[derive(Default)]
+pub struct NodeFlowBranchV1 {}
+
+impl Nodeable for NodeFlowBranchV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let inputs = vec![input_pin("Value", "768962c8-c590-4c2e-8b3a-27710ed3a30d", ANY_DATATYPE, false)];
+        (inputs, Vec::new())
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut receives = Vec::new();
+        let mut sends = Vec::new();
+        receives.push(receive_pin("Condition", "1d70c396-3a03-4eab-be66-bb72b6d2c37b", "bool"));
+        sends.push(send_pin("True", "e8d83ebd-af5f-4e2c-bdd4-5ae0e1a6a66c", ANY_DATATYPE));
+        sends.push(send_pin("False", "9d4b97c2-9c18-4943-90e2-2a1d27a5c0fe", ANY_DATATYPE));
+        (receives, sends)
+    }
+
+    fn compute_output(
+        &mut self,
+        _node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        Err(format!("output pin with uuid {}", output_info.uuid))
+    }
+
+    fn handle_receive(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        message: &Option<Message>,
+    ) {
+        if receiver != &uuid::Uuid::parse_str("1d70c396-3a03-4eab-be66-bb72b6d2c37b").unwrap() {
+            return report_receive_error(node, context, format!("no receive pin with uuid {}", receiver));
+        }
+        let condition = match message.as_ref().and_then(|message| message.content_as::<bool>()) {
+            Some(condition) => *condition,
+            None => return report_receive_error(node, context, String::from("branch condition was not a bool")),
+        };
+        let value = node.get_input_message(&uuid::Uuid::parse_str("768962c8-c590-4c2e-8b3a-27710ed3a30d").unwrap());
+        let send = if condition { "e8d83ebd-af5f-4e2c-bdd4-5ae0e1a6a66c" } else { "9d4b97c2-9c18-4943-90e2-2a1d27a5c0fe" };
+        node.emit(context, &uuid::Uuid::parse_str(send).unwrap(), value.or_else(|| message.clone()));
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeFlowBranchV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Branch"),
+                    uuid: uuid::Uuid::parse_str("0914cee4-9617-40b9-9808-9c9c0b20f4ed").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Branch"),
+            uuid: uuid::Uuid::parse_str("0914cee4-9617-40b9-9808-9c9c0b20f4ed").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("0914cee4-9617-40b9-9808-9c9c0b20f4ed").unwrap() {
+        return match version {
+            1 => Some(NodeFlowBranchV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
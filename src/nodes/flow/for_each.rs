@@ -0,0 +1,119 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Goes through its items each time it receives a message, sending the index and then the item for every one,
+/// and the number of items once done.
+/// Items are the values of the expandable input, where arrays of numbers, booleans or strings are split into their items.
+///
+#[derive(Default)]
+pub struct NodeFlowForEachV1 {}
+
+impl Nodeable for NodeFlowForEachV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let inputs = vec![input_pin("Items", "73a17db9-78bc-4297-b07e-a701549051fd", ANY_DATATYPE, true)];
+        (inputs, Vec::new())
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut receives = Vec::new();
+        let mut sends = Vec::new();
+        receives.push(receive_pin("Run", "b0d0382e-06cd-4165-a6c9-a81b64632b46", ANY_DATATYPE));
+        sends.push(send_pin("Index", "114bb257-3183-4233-bf9c-0b211edeee9d", "u64"));
+        sends.push(send_pin("Item", "1dbe6a3a-b438-4502-9303-f345a5d6af13", ANY_DATATYPE));
+        sends.push(send_pin("Done", "357781fb-590c-46ca-a9ea-0a5a1b4040be", "u64"));
+        (receives, sends)
+    }
+
+    fn compute_output(
+        &mut self,
+        _node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        Err(format!("output pin with uuid {}", output_info.uuid))
+    }
+
+    fn handle_receive(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+        if receiver != &uuid::Uuid::parse_str("b0d0382e-06cd-4165-a6c9-a81b64632b46").unwrap() {
+            return report_receive_error(node, context, format!("no receive pin with uuid {}", receiver));
+        }
+        let items: Vec<Message> = node
+            .get_input_messages(&uuid::Uuid::parse_str("73a17db9-78bc-4297-b07e-a701549051fd").unwrap())
+            .iter()
+            .flat_map(split_items)
+            .collect();
+        let (index_pin, item_pin) = (uuid::Uuid::parse_str("114bb257-3183-4233-bf9c-0b211edeee9d").unwrap(), uuid::Uuid::parse_str("1dbe6a3a-b438-4502-9303-f345a5d6af13").unwrap());
+        for (index, item) in items.iter().enumerate() {
+            node.emit(context, &index_pin, Some(Message::new(index as u64)));
+            node.emit(context, &item_pin, Some(item.clone()));
+        }
+        node.emit(context, &uuid::Uuid::parse_str("357781fb-590c-46ca-a9ea-0a5a1b4040be").unwrap(), Some(Message::new(items.len() as u64)));
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeFlowForEachV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("For Each"),
+                    uuid: uuid::Uuid::parse_str("0eb49e0c-3ee5-4a7f-a08f-2f6c9b6a0df7").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("For Each"),
+            uuid: uuid::Uuid::parse_str("0eb49e0c-3ee5-4a7f-a08f-2f6c9b6a0df7").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("0eb49e0c-3ee5-4a7f-a08f-2f6c9b6a0df7").unwrap() {
+        return match version {
+            1 => Some(NodeFlowForEachV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
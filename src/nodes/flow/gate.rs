@@ -0,0 +1,117 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Passes messages through only while it is open.
+/// The gate starts open, and is opened, closed or toggled by messages to those receive pins.
+///
+#[derive(Default)]
+pub struct NodeFlowGateV1 {
+    pub closed: bool,
+}
+
+impl Nodeable for NodeFlowGateV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut receives = Vec::new();
+        let mut sends = Vec::new();
+        receives.push(receive_pin("Enter", "7c38dd8b-6a68-488a-8d43-4fe2620dd8b1", ANY_DATATYPE));
+        receives.push(receive_pin("Open", "4b4e5960-acc8-4abf-96dc-9b083f00ba46", ANY_DATATYPE));
+        receives.push(receive_pin("Close", "6dff8f38-6734-4725-9964-6eb7c4453cc2", ANY_DATATYPE));
+        receives.push(receive_pin("Toggle", "3cd10ed3-2f8b-44b2-a144-2c65dfe582ad", ANY_DATATYPE));
+        sends.push(send_pin("Exit", "8c584f71-aeb6-4ddb-959d-5265c0a05a60", ANY_DATATYPE));
+        (receives, sends)
+    }
+
+    fn compute_output(
+        &mut self,
+        _node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        Err(format!("output pin with uuid {}", output_info.uuid))
+    }
+
+    fn handle_receive(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        message: &Option<Message>,
+    ) {
+        match receiver {
+            id_enter if id_enter == &uuid::Uuid::parse_str("7c38dd8b-6a68-488a-8d43-4fe2620dd8b1").unwrap() => {
+                if !self.closed {
+                    node.emit(context, &uuid::Uuid::parse_str("8c584f71-aeb6-4ddb-959d-5265c0a05a60").unwrap(), message.clone());
+                }
+            }
+            id_open if id_open == &uuid::Uuid::parse_str("4b4e5960-acc8-4abf-96dc-9b083f00ba46").unwrap() => self.closed = false,
+            id_close if id_close == &uuid::Uuid::parse_str("6dff8f38-6734-4725-9964-6eb7c4453cc2").unwrap() => self.closed = true,
+            id_toggle if id_toggle == &uuid::Uuid::parse_str("3cd10ed3-2f8b-44b2-a144-2c65dfe582ad").unwrap() => self.closed = !self.closed,
+            _ => report_receive_error(node, context, format!("no receive pin with uuid {}", receiver)),
+        }
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeFlowGateV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self::default();
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Gate"),
+                    uuid: uuid::Uuid::parse_str("80998e1b-02d0-4668-b0ae-11fc92b40b55").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Gate"),
+            uuid: uuid::Uuid::parse_str("80998e1b-02d0-4668-b0ae-11fc92b40b55").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("80998e1b-02d0-4668-b0ae-11fc92b40b55").unwrap() {
+        return match version {
+            1 => Some(NodeFlowGateV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,78 @@
+pub mod branch;
+pub mod for_each;
+pub mod gate;
+pub mod repeat;
+pub mod select;
+pub mod sequence;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.push(branch::register());
+    nodes.append(&mut select::register());
+    nodes.push(repeat::register());
+    nodes.push(for_each::register());
+    nodes.push(sequence::register());
+    nodes.push(gate::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::event::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(branch::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(select::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(repeat::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(for_each::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(sequence::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(gate::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use log::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Splits a message into items if it holds an array of the given type.
+fn split<T>(message: &Message) -> Option<Vec<Message>>
+where
+    T: Clone + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+    message
+        .content_as::<Vec<T>>()
+        .map(|items| items.iter().cloned().map(Message::new).collect())
+}
+
+///
+/// Splits a message holding an array of numbers, booleans or strings into a message per item.
+/// Any other message is a single item, since its type cannot be known.
+///
+pub fn split_items(message: &Message) -> Vec<Message> {
+    split::<f64>(message)
+        .or_else(|| split::<i64>(message))
+        .or_else(|| split::<u64>(message))
+        .or_else(|| split::<bool>(message))
+        .or_else(|| split::<String>(message))
+        .unwrap_or_else(|| vec![message.clone()])
+}
+
+///
+/// Logs a problem reacting to a received message and publishes it as an error event,
+/// since receives have no caller to hand an error back to.
+///
+pub fn report_receive_error(node: &Node, context: &Context, error: String) {
+    error!("node actor {:?} could not handle received message: {}", &context.aid, error);
+    node.publish(context, EngineEventType::Error(error));
+}
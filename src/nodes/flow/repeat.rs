@@ -0,0 +1,124 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+/// The most iterations a single run of a loop can have, as the whole loop runs within the node actor.
+pub const MAX_ITERATIONS: u64 = 1_000_000;
+
+///
+/// Counts through a loop each time it receives a message, sending the index of every iteration and then the count once done.
+/// A received u64 is the number of iterations, and anything else uses the count input.
+/// Counts over `MAX_ITERATIONS` are rejected.
+///
+#[derive(Default)]
+pub struct NodeFlowLoopV1 {}
+
+impl Nodeable for NodeFlowLoopV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let inputs = vec![input_pin("Count", "5ec1d59e-54af-4958-9107-36fe14ebd9f2", "u64", false)];
+        (inputs, Vec::new())
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut receives = Vec::new();
+        let mut sends = Vec::new();
+        receives.push(receive_pin("Run", "0a886c43-6557-4dd2-a0fc-2f608dc8ad24", ANY_DATATYPE));
+        sends.push(send_pin("Index", "cdbfbcf2-56ee-4cc0-b261-ffb1781df369", "u64"));
+        sends.push(send_pin("Done", "25158796-31ed-4aaf-a24c-b70f426b9f87", "u64"));
+        (receives, sends)
+    }
+
+    fn compute_output(
+        &mut self,
+        _node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        Err(format!("output pin with uuid {}", output_info.uuid))
+    }
+
+    fn handle_receive(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        message: &Option<Message>,
+    ) {
+        if receiver != &uuid::Uuid::parse_str("0a886c43-6557-4dd2-a0fc-2f608dc8ad24").unwrap() {
+            return report_receive_error(node, context, format!("no receive pin with uuid {}", receiver));
+        }
+        let count = match message.as_ref().and_then(|message| message.content_as::<u64>()) {
+            Some(count) => *count,
+            None => node
+                .get_input::<u64>(&uuid::Uuid::parse_str("5ec1d59e-54af-4958-9107-36fe14ebd9f2").unwrap())
+                .unwrap_or(0),
+        };
+        if count > MAX_ITERATIONS {
+            return report_receive_error(node, context, format!("loop count {} is more than the most of {}", count, MAX_ITERATIONS));
+        }
+        let index_pin = uuid::Uuid::parse_str("cdbfbcf2-56ee-4cc0-b261-ffb1781df369").unwrap();
+        for index in 0..count {
+            node.emit(context, &index_pin, Some(Message::new(index)));
+        }
+        node.emit(context, &uuid::Uuid::parse_str("25158796-31ed-4aaf-a24c-b70f426b9f87").unwrap(), Some(Message::new(count)));
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeFlowLoopV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Loop"),
+                    uuid: uuid::Uuid::parse_str("25f60946-1af3-47b0-902a-89a78727fc09").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Loop"),
+            uuid: uuid::Uuid::parse_str("25f60946-1af3-47b0-902a-89a78727fc09").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("25f60946-1af3-47b0-902a-89a78727fc09").unwrap() {
+        return match version {
+            1 => Some(NodeFlowLoopV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
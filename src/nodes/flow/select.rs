@@ -0,0 +1,144 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Whether a value is picked by its position or by a condition.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectOp {
+    Index,
+    Condition,
+}
+
+/// The name, graph UUID and operation of every selection node.
+pub const OPERATIONS: [(&str, &str, SelectOp); 2] = [
+    ("Select", "bc92fd47-0d1f-4679-9b08-b10d9777590c", SelectOp::Index),
+    ("Select If", "50fc7ba7-da96-41b0-9b89-6cea20e5d9e8", SelectOp::Condition),
+];
+
+const INDEX: &str = "862ac183-5772-42ef-b52e-d3e6f3265edd";
+const OPTIONS: &str = "0cb97392-660f-4e27-b404-6a071eb35908";
+const CONDITION: &str = "bf8f1a03-93cf-41ef-8655-a102f49adfa3";
+const IF_TRUE: &str = "074997a3-8328-4b74-b9d1-cf6b78bdabd5";
+const IF_FALSE: &str = "4b0eb04c-ec5a-438e-8b3d-845794cdf90f";
+const OUTPUT_VALUE: &str = "11b4bb99-df9e-4967-8806-916198ab8948";
+
+///
+/// Picks one of its inputs, of any datatype, as its output.
+/// Select counts from zero through the options, and select if picks the true or false input by the condition.
+///
+pub struct NodeFlowSelectV1 {
+    pub op: SelectOp,
+}
+
+impl Nodeable for NodeFlowSelectV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            SelectOp::Index => {
+                inputs.push(input_pin("Index", INDEX, "u64", false));
+                inputs.push(input_pin("Options", OPTIONS, ANY_DATATYPE, true));
+            }
+            SelectOp::Condition => {
+                inputs.push(input_pin("Condition", CONDITION, "bool", false));
+                inputs.push(input_pin("True", IF_TRUE, ANY_DATATYPE, false));
+                inputs.push(input_pin("False", IF_FALSE, ANY_DATATYPE, false));
+            }
+        }
+        outputs.push(output_pin("Value", OUTPUT_VALUE, ANY_DATATYPE));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_VALUE).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        match self.op {
+            SelectOp::Index => {
+                let index = node
+                    .get_input::<u64>(&uuid::Uuid::parse_str(INDEX).unwrap())
+                    .unwrap_or(0) as usize;
+                let options = node.get_input_messages(&uuid::Uuid::parse_str(OPTIONS).unwrap());
+                match options.get(index) {
+                    Some(option) => Ok(Some(option.clone())),
+                    None => Err(format!("cannot select option {} out of {}", index, options.len())),
+                }
+            }
+            SelectOp::Condition => {
+                let condition = node
+                    .get_input::<bool>(&uuid::Uuid::parse_str(CONDITION).unwrap())
+                    .unwrap_or(false);
+                let picked = if condition { IF_TRUE } else { IF_FALSE };
+                match node.get_input_messages(&uuid::Uuid::parse_str(picked).unwrap()).pop() {
+                    Some(value) => Ok(Some(value)),
+                    None => Err(format!("no value to select when the condition is {}", condition)),
+                }
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeFlowSelectV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: SelectOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeFlowSelectV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
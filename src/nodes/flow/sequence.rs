@@ -0,0 +1,112 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Passes each received message on to its send pins in order, one after the other.
+///
+#[derive(Default)]
+pub struct NodeFlowSequenceV1 {}
+
+/// The send pins of a sequence, in the order they are sent on.
+const STEPS: [(&str, &str); 4] = [
+    ("First", "905a4aba-12d4-4374-a965-a9bf89af4f02"),
+    ("Second", "cf518a11-111a-4395-af95-6211e4899462"),
+    ("Third", "3b0b7347-9c0d-41b6-89f7-1e4fe68f325f"),
+    ("Fourth", "fde47b6f-95fb-477f-93f8-7f271b6246cd"),
+];
+
+impl Nodeable for NodeFlowSequenceV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let receives = vec![receive_pin("Run", "01cdfbfa-15a9-4149-a377-ce6d55823571", ANY_DATATYPE)];
+        let sends = STEPS.iter().map(|(name, id)| send_pin(name, id, ANY_DATATYPE)).collect();
+        (receives, sends)
+    }
+
+    fn compute_output(
+        &mut self,
+        _node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        Err(format!("output pin with uuid {}", output_info.uuid))
+    }
+
+    fn handle_receive(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        message: &Option<Message>,
+    ) {
+        if receiver != &uuid::Uuid::parse_str("01cdfbfa-15a9-4149-a377-ce6d55823571").unwrap() {
+            return report_receive_error(node, context, format!("no receive pin with uuid {}", receiver));
+        }
+        for (_, id) in STEPS.iter() {
+            node.emit(context, &uuid::Uuid::parse_str(id).unwrap(), message.clone());
+        }
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeFlowSequenceV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Sequence"),
+                    uuid: uuid::Uuid::parse_str("e08edb9d-faec-49d4-a4e0-374feaa81367").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Sequence"),
+            uuid: uuid::Uuid::parse_str("e08edb9d-faec-49d4-a4e0-374feaa81367").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("e08edb9d-faec-49d4-a4e0-374feaa81367").unwrap() {
+        return match version {
+            1 => Some(NodeFlowSequenceV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
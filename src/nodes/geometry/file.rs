@@ -0,0 +1,312 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use std::path::Path;
+
+///
+/// Whether a mesh file is being read or written.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MeshFileOp {
+    Load,
+    Save,
+}
+
+/// The name, graph UUID and operation of every mesh file node.
+pub const OPERATIONS: [(&str, &str, MeshFileOp); 2] = [
+    ("Load Mesh", "9c7a57f7-bca1-4488-81d6-b7375c15f518", MeshFileOp::Load),
+    ("Save Mesh", "530bb9c8-7098-4ed0-beb7-873079fa4d33", MeshFileOp::Save),
+];
+
+const PATH: &str = "f0a2c8ab-3192-4528-861b-eb6aa59f4a9c";
+const MESH: &str = "efa05aed-6366-440f-addb-fa7efd3a7a0f";
+const OUTPUT_MESH: &str = "5f824da5-f9b0-4363-8c57-87002a81b096";
+const OUTPUT_PATH: &str = "9c3fbd05-d3a0-46ff-82e9-b198fd651341";
+
+/// The glTF numbers for a float and unsigned int component, and for vertex and index buffer views.
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+///
+/// Writes a mesh as a glTF 2.0 file with a single node.
+/// A .glb path gets a binary file with the buffer inside, while anything else gets JSON with the buffer in a .bin file next to it.
+///
+pub fn write_gltf(mesh: &Mesh, path: &Path) -> Result<(), String> {
+    let is_binary = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map_or(false, |extension| extension.eq_ignore_ascii_case("glb"));
+    let count = mesh.vertex_count();
+    if count == 0 || mesh.indices.is_empty() {
+        return Err(format!("cannot save mesh {} without any triangles", path.display()));
+    }
+    if mesh.normals.len() != count || mesh.uvs.len() != count {
+        return Err(String::from("every vertex of a mesh needs a normal and a UV to be saved"));
+    }
+    if let Some(index) = mesh.indices.iter().find(|index| **index as usize >= count) {
+        return Err(format!("mesh index {} is past its {} vertices", index, count));
+    }
+
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut views = Vec::new();
+    let mut add_view = |values: &mut dyn Iterator<Item = f32>, target: u32| {
+        let offset = buffer.len();
+        for value in values {
+            buffer.extend_from_slice(&value.to_le_bytes());
+        }
+        views.push(serde_json::json!({
+            "buffer": 0,
+            "byteOffset": offset,
+            "byteLength": buffer.len() - offset,
+            "target": target,
+        }));
+    };
+    add_view(&mut mesh.positions.iter().flat_map(|v| v.iter().copied()), ARRAY_BUFFER);
+    add_view(&mut mesh.normals.iter().flat_map(|v| v.iter().copied()), ARRAY_BUFFER);
+    add_view(&mut mesh.uvs.iter().flat_map(|v| v.iter().copied()), ARRAY_BUFFER);
+    let offset = buffer.len();
+    for index in mesh.indices.iter() {
+        buffer.extend_from_slice(&index.to_le_bytes());
+    }
+    views.push(serde_json::json!({
+        "buffer": 0,
+        "byteOffset": offset,
+        "byteLength": buffer.len() - offset,
+        "target": ELEMENT_ARRAY_BUFFER,
+    }));
+
+    let (min, max) = mesh.bounds();
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let bin_name = format!("{}.bin", name);
+    let mut buffer_info = serde_json::json!({ "byteLength": buffer.len() });
+    if !is_binary {
+        buffer_info["uri"] = serde_json::Value::from(bin_name.clone());
+    }
+    let document = serde_json::json!({
+        "asset": { "version": "2.0", "generator": "proc_flow" },
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0, "name": name }],
+        "meshes": [{
+            "name": name,
+            "primitives": [{
+                "attributes": { "POSITION": 0, "NORMAL": 1, "TEXCOORD_0": 2 },
+                "indices": 3,
+                "mode": 4,
+            }],
+        }],
+        "buffers": [buffer_info],
+        "bufferViews": views,
+        "accessors": [
+            { "bufferView": 0, "componentType": FLOAT, "count": count, "type": "VEC3", "min": min, "max": max },
+            { "bufferView": 1, "componentType": FLOAT, "count": count, "type": "VEC3" },
+            { "bufferView": 2, "componentType": FLOAT, "count": count, "type": "VEC2" },
+            { "bufferView": 3, "componentType": UNSIGNED_INT, "count": mesh.indices.len(), "type": "SCALAR" },
+        ],
+    });
+    let json = serde_json::to_vec(&document).map_err(|e| format!("could not write glTF for {}: {}", path.display(), e))?;
+    let write_error = |e: std::io::Error| format!("could not save mesh {}: {}", path.display(), e);
+
+    if !is_binary {
+        std::fs::write(path.with_file_name(&bin_name), &buffer).map_err(write_error)?;
+        return std::fs::write(path, &json).map_err(write_error);
+    }
+    // Both chunks of a binary file are padded to four bytes, the JSON with spaces and the buffer with zeros.
+    let mut json = json;
+    while json.len() % 4 != 0 {
+        json.push(b' ');
+    }
+    while buffer.len() % 4 != 0 {
+        buffer.push(0);
+    }
+    let length = 12 + 8 + json.len() + 8 + buffer.len();
+    let mut glb = Vec::with_capacity(length);
+    glb.extend_from_slice(b"glTF");
+    glb.extend_from_slice(&2u32.to_le_bytes());
+    glb.extend_from_slice(&(length as u32).to_le_bytes());
+    glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    glb.extend_from_slice(b"JSON");
+    glb.extend_from_slice(&json);
+    glb.extend_from_slice(&(buffer.len() as u32).to_le_bytes());
+    glb.extend_from_slice(b"BIN\0");
+    glb.extend_from_slice(&buffer);
+    std::fs::write(path, &glb).map_err(write_error)
+}
+
+///
+/// Reads every triangle primitive in the default scene of a glTF or GLB file into one mesh, with node transforms applied.
+/// Files without a default scene use their first scene, and missing normals are computed.
+///
+pub fn read_gltf(path: &Path) -> Result<Mesh, String> {
+    let (document, buffers, _) =
+        gltf::import(path).map_err(|e| format!("could not load mesh {}: {}", path.display(), e))?;
+    let scene = match document.default_scene().or_else(|| document.scenes().next()) {
+        Some(scene) => scene,
+        None => return Err(format!("could not load mesh {}: the file has no scenes", path.display())),
+    };
+    let mut mesh = Mesh::new();
+    let mut needs_normals = false;
+    let mut pending: Vec<(gltf::Node, Matrix4<f64>)> = scene.nodes().map(|node| (node, Matrix4::identity())).collect();
+    while let Some((node, parent)) = pending.pop() {
+        let local = node.transform().matrix();
+        let transform = parent * Matrix4::from_fn(|row, column| local[column][row] as f64);
+        for child in node.children() {
+            pending.push((child, transform));
+        }
+        let node_mesh = match node.mesh() {
+            Some(node_mesh) => node_mesh,
+            None => continue,
+        };
+        for primitive in node_mesh.primitives() {
+            if primitive.mode() != gltf::mesh::Mode::Triangles {
+                continue;
+            }
+            let reader = primitive.reader(|buffer| Some(&buffers[buffer.index()]));
+            let positions: Vec<[f32; 3]> = match reader.read_positions() {
+                Some(positions) => positions.collect(),
+                None => continue,
+            };
+            let count = positions.len();
+            let normals: Vec<[f32; 3]> = match reader.read_normals() {
+                Some(normals) => normals.collect(),
+                None => {
+                    needs_normals = true;
+                    vec![[0.0, 1.0, 0.0]; count]
+                }
+            };
+            let uvs: Vec<[f32; 2]> = match reader.read_tex_coords(0) {
+                Some(uvs) => uvs.into_f32().collect(),
+                None => vec![[0.0, 0.0]; count],
+            };
+            let indices: Vec<u32> = match reader.read_indices() {
+                Some(indices) => indices.into_u32().collect(),
+                None => (0..count as u32).collect(),
+            };
+            let part = Mesh {
+                positions,
+                normals,
+                uvs,
+                indices,
+            };
+            mesh.append(&part.transformed(&transform));
+        }
+    }
+    if needs_normals {
+        mesh.compute_normals();
+    }
+    Ok(mesh)
+}
+
+///
+/// Loads a mesh from a glTF or GLB file, or saves one to a file.
+/// Saving happens when the path output is computed, and gives back the path that was written.
+///
+pub struct NodeGeometryFileV1 {
+    pub op: MeshFileOp,
+}
+
+impl Nodeable for NodeGeometryFileV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            MeshFileOp::Load => {
+                inputs.push(input_pin("Path", PATH, "string", false));
+                outputs.push(output_pin("Mesh", OUTPUT_MESH, "mesh"));
+            }
+            MeshFileOp::Save => {
+                inputs.push(input_pin("Mesh", MESH, "mesh", false));
+                inputs.push(input_pin("Path", PATH, "string", false));
+                outputs.push(output_pin("Path", OUTPUT_PATH, "string"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let path = match node.get_input::<String>(&uuid::Uuid::parse_str(PATH).unwrap()) {
+            Some(path) => path,
+            None => return Err(String::from("no mesh path given")),
+        };
+        match self.op {
+            MeshFileOp::Load => Ok(Some(Message::new(read_gltf(&node.resolve_asset(&path)?)?))),
+            MeshFileOp::Save => {
+                write_gltf(&mesh_input(node, MESH)?, std::path::Path::new(&path))?;
+                Ok(Some(Message::new(path)))
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeGeometryFileV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: MeshFileOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeGeometryFileV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
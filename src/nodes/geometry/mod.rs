@@ -0,0 +1,163 @@
+pub mod file;
+pub mod modify;
+pub mod primitive;
+pub mod terrain;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut primitive::register());
+    nodes.append(&mut modify::register());
+    nodes.append(&mut file::register());
+    nodes.push(terrain::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(primitive::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(modify::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(file::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(terrain::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use nalgebra::{Matrix4, Point3, Vector3};
+use serde::{Deserialize, Serialize};
+
+///
+/// An indexed triangle mesh, carried on pins with the "mesh" datatype.
+/// Every vertex has a position, normal and UV, so the three vertex lists are always the same length.
+/// Triangles wind counterclockwise when seen from the front, as in glTF.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub uvs: Vec<[f32; 2]>,
+    /// Three vertex indices per triangle.
+    pub indices: Vec<u32>,
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn vertex_count(&self) -> usize {
+        self.positions.len()
+    }
+    pub fn triangle_count(&self) -> usize {
+        self.indices.len() / 3
+    }
+    /// Adds a vertex and gives back its index.
+    pub fn push_vertex(&mut self, position: [f32; 3], normal: [f32; 3], uv: [f32; 2]) -> u32 {
+        self.positions.push(position);
+        self.normals.push(normal);
+        self.uvs.push(uv);
+        (self.positions.len() - 1) as u32
+    }
+    pub fn push_triangle(&mut self, a: u32, b: u32, c: u32) {
+        self.indices.extend_from_slice(&[a, b, c]);
+    }
+    /// Adds all the vertices and triangles of another mesh to this one.
+    pub fn append(&mut self, other: &Mesh) {
+        let offset = self.positions.len() as u32;
+        self.positions.extend_from_slice(&other.positions);
+        self.normals.extend_from_slice(&other.normals);
+        self.uvs.extend_from_slice(&other.uvs);
+        self.indices.extend(other.indices.iter().map(|index| index + offset));
+    }
+    ///
+    /// Creates a copy of the mesh with a transform applied.
+    /// Normals use the inverse transpose, and triangles are flipped if the transform mirrors the mesh.
+    ///
+    pub fn transformed(&self, transform: &Matrix4<f64>) -> Self {
+        let normal_transform = transform
+            .fixed_slice::<nalgebra::U3, nalgebra::U3>(0, 0)
+            .into_owned()
+            .try_inverse()
+            .unwrap_or_else(nalgebra::Matrix3::identity)
+            .transpose();
+        let mut mesh = self.clone();
+        for position in mesh.positions.iter_mut() {
+            let point = transform.transform_point(&Point3::new(
+                position[0] as f64,
+                position[1] as f64,
+                position[2] as f64,
+            ));
+            *position = [point.x as f32, point.y as f32, point.z as f32];
+        }
+        for normal in mesh.normals.iter_mut() {
+            let direction = normal_transform * Vector3::new(normal[0] as f64, normal[1] as f64, normal[2] as f64);
+            let direction = direction.try_normalize(std::f64::EPSILON).unwrap_or(direction);
+            *normal = [direction.x as f32, direction.y as f32, direction.z as f32];
+        }
+        if transform.fixed_slice::<nalgebra::U3, nalgebra::U3>(0, 0).determinant() < 0.0 {
+            for triangle in mesh.indices.chunks_mut(3) {
+                triangle.swap(1, 2);
+            }
+        }
+        mesh
+    }
+    ///
+    /// Replaces the normals with smooth ones, averaged from the triangles around each vertex by their area.
+    ///
+    pub fn compute_normals(&mut self) {
+        let mut normals = vec![Vector3::<f32>::zeros(); self.positions.len()];
+        for triangle in self.indices.chunks(3) {
+            if triangle.len() < 3 {
+                continue;
+            }
+            let corner = |i: usize| Vector3::from(self.positions[triangle[i] as usize]);
+            let face = (corner(1) - corner(0)).cross(&(corner(2) - corner(0)));
+            for index in triangle {
+                normals[*index as usize] += face;
+            }
+        }
+        self.normals = normals
+            .iter()
+            .map(|normal| {
+                let normal = normal.try_normalize(std::f32::EPSILON).unwrap_or_else(Vector3::y);
+                [normal.x, normal.y, normal.z]
+            })
+            .collect();
+    }
+    /// Gives back the smallest and largest corners of the box around every vertex.
+    pub fn bounds(&self) -> ([f32; 3], [f32; 3]) {
+        if self.positions.is_empty() {
+            return ([0.0; 3], [0.0; 3]);
+        }
+        let mut min = [std::f32::MAX; 3];
+        let mut max = [std::f32::MIN; 3];
+        for position in self.positions.iter() {
+            for axis in 0..3 {
+                min[axis] = min[axis].min(position[axis]);
+                max[axis] = max[axis].max(position[axis]);
+            }
+        }
+        (min, max)
+    }
+}
+
+///
+/// Gets the mesh on an input pin, which is an error if there is none.
+///
+pub fn mesh_input(node: &Node, id: &str) -> Result<Mesh, String> {
+    match node.get_input::<Mesh>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(mesh) => Ok(mesh),
+        None => Err(format!("no mesh on input pin {}", id)),
+    }
+}
@@ -0,0 +1,140 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The operations that change or combine existing meshes.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ModifyOp {
+    Transform,
+    Merge,
+    RecomputeNormals,
+}
+
+/// The name, graph UUID and operation of every mesh modifying node.
+pub const OPERATIONS: [(&str, &str, ModifyOp); 3] = [
+    ("Transform Mesh", "ec02186d-9b18-4d32-8c88-6d5e2ac4e306", ModifyOp::Transform),
+    ("Merge Meshes", "4ca46205-2c89-444b-8603-b1d691bdc671", ModifyOp::Merge),
+    ("Recompute Normals", "5cbb1c63-4bc7-4000-b6d9-3ea3aadfb5a5", ModifyOp::RecomputeNormals),
+];
+
+const MESH: &str = "65dfc245-da5d-4c71-83e5-4d86b778d794";
+const MESHES: &str = "5543e65f-854b-4def-8a06-c67477bef197";
+const MATRIX: &str = "476e4b9d-0719-44a5-bbc6-7ae670c23f16";
+const OUTPUT_MESH: &str = "00e4ead5-d00f-4beb-a1a1-6ed348d5a2a4";
+
+///
+/// Transforms a mesh by a matrix, merges many meshes into one, or smooths the normals of a mesh.
+///
+pub struct NodeGeometryModifyV1 {
+    pub op: ModifyOp,
+}
+
+impl Nodeable for NodeGeometryModifyV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            ModifyOp::Transform => {
+                inputs.push(input_pin("Mesh", MESH, "mesh", false));
+                inputs.push(input_pin("Matrix", MATRIX, "mat4", false));
+            }
+            ModifyOp::Merge => inputs.push(input_pin("Meshes", MESHES, "mesh", true)),
+            ModifyOp::RecomputeNormals => inputs.push(input_pin("Mesh", MESH, "mesh", false)),
+        }
+        outputs.push(output_pin("Mesh", OUTPUT_MESH, "mesh"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_MESH).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let mesh = match self.op {
+            ModifyOp::Transform => {
+                let matrix = node
+                    .get_input::<Matrix4<f64>>(&uuid::Uuid::parse_str(MATRIX).unwrap())
+                    .unwrap_or_else(Matrix4::identity);
+                mesh_input(node, MESH)?.transformed(&matrix)
+            }
+            ModifyOp::Merge => {
+                let mut merged = Mesh::new();
+                for mesh in node.get_inputs::<Mesh>(&uuid::Uuid::parse_str(MESHES).unwrap()).iter() {
+                    merged.append(mesh);
+                }
+                merged
+            }
+            ModifyOp::RecomputeNormals => {
+                let mut mesh = mesh_input(node, MESH)?;
+                mesh.compute_normals();
+                mesh
+            }
+        };
+        Ok(Some(Message::new(mesh)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeGeometryModifyV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ModifyOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeGeometryModifyV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
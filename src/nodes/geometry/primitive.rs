@@ -0,0 +1,306 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The primitive shapes that can be generated.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PrimitiveOp {
+    Plane,
+    Grid,
+    Cube,
+    Sphere,
+    Cylinder,
+}
+
+/// The name, graph UUID and operation of every primitive mesh node.
+pub const OPERATIONS: [(&str, &str, PrimitiveOp); 5] = [
+    ("Plane Mesh", "f6c3610e-a637-43f5-932f-76c5afcaf597", PrimitiveOp::Plane),
+    ("Grid Mesh", "d706fefa-93f3-41db-8207-49d93e273873", PrimitiveOp::Grid),
+    ("Cube Mesh", "3d5f69ae-d1a3-4028-bb8c-21b9e0849c6f", PrimitiveOp::Cube),
+    ("Sphere Mesh", "f52fd2b4-cc5a-4a81-a88c-1ec42614ec13", PrimitiveOp::Sphere),
+    ("Cylinder Mesh", "d5787a87-4d0c-4ac2-8138-8605b8697bff", PrimitiveOp::Cylinder),
+];
+
+const WIDTH: &str = "2308feff-f978-43e4-bacd-1f500816b1db";
+const DEPTH: &str = "46a5116f-758d-4bef-a356-fdd9d049debf";
+const COLUMNS: &str = "584ff32d-2832-4a82-b1bf-591f49e9d1b9";
+const ROWS: &str = "90f87c23-375c-42f1-9ace-1d04aad6762e";
+const SIZE: &str = "b4fdff13-6ec1-416c-844a-2d85b64d6f86";
+const RADIUS: &str = "6c1d2e8e-af95-46e6-931a-89d3ddfd6066";
+const HEIGHT: &str = "db775b00-667e-4c81-a5a8-a551db7ffda9";
+const SEGMENTS: &str = "5d11147e-f7d6-41ff-8a15-cf1fd244a1e3";
+const RINGS: &str = "dbf43c80-b9a3-45c8-b30d-1e342ebaf3d5";
+const OUTPUT_MESH: &str = "0193f116-230d-4bc9-b9bc-3f096635a91c";
+
+///
+/// Creates a flat grid on the XZ plane, centered on the origin and facing up.
+/// Vertices are ordered by column, then row, so vertex x, z is at index x + z * (columns + 1).
+///
+pub fn grid(width: f32, depth: f32, columns: u32, rows: u32) -> Mesh {
+    let mut mesh = Mesh::new();
+    for row in 0..=rows {
+        for column in 0..=columns {
+            let (u, v) = (column as f32 / columns as f32, row as f32 / rows as f32);
+            mesh.push_vertex([(u - 0.5) * width, 0.0, (v - 0.5) * depth], [0.0, 1.0, 0.0], [u, v]);
+        }
+    }
+    for row in 0..rows {
+        for column in 0..columns {
+            let corner = column + row * (columns + 1);
+            let below = corner + columns + 1;
+            mesh.push_triangle(corner, below, corner + 1);
+            mesh.push_triangle(corner + 1, below, below + 1);
+        }
+    }
+    mesh
+}
+
+///
+/// Creates a flat plane of two triangles on the XZ plane, centered on the origin and facing up.
+///
+pub fn plane(width: f32, depth: f32) -> Mesh {
+    grid(width, depth, 1, 1)
+}
+
+///
+/// Creates a cube centered on the origin, with separate vertices per face so the edges stay sharp.
+///
+pub fn cube(size: f32) -> Mesh {
+    // The normal, then two axes across the face whose cross product is the normal.
+    let faces: [([f32; 3], [f32; 3], [f32; 3]); 6] = [
+        ([1.0, 0.0, 0.0], [0.0, 0.0, -1.0], [0.0, 1.0, 0.0]),
+        ([-1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]),
+        ([0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, -1.0]),
+        ([0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]),
+        ([0.0, 0.0, 1.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+        ([0.0, 0.0, -1.0], [-1.0, 0.0, 0.0], [0.0, 1.0, 0.0]),
+    ];
+    let half = size / 2.0;
+    let mut mesh = Mesh::new();
+    for (normal, across, up) in faces.iter() {
+        let first = mesh.vertex_count() as u32;
+        for (s, t) in [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].iter() {
+            let mut position = [0.0; 3];
+            for axis in 0..3 {
+                position[axis] = half * (normal[axis] + s * across[axis] + t * up[axis]);
+            }
+            mesh.push_vertex(position, *normal, [(s + 1.0) / 2.0, (1.0 - t) / 2.0]);
+        }
+        mesh.push_triangle(first, first + 1, first + 2);
+        mesh.push_triangle(first, first + 2, first + 3);
+    }
+    mesh
+}
+
+///
+/// Creates a UV sphere centered on the origin, with the poles on the Y axis.
+///
+pub fn sphere(radius: f32, segments: u32, rings: u32) -> Mesh {
+    let mut mesh = Mesh::new();
+    for ring in 0..=rings {
+        let v = ring as f32 / rings as f32;
+        let theta = v * std::f32::consts::PI;
+        for segment in 0..=segments {
+            let u = segment as f32 / segments as f32;
+            let phi = u * std::f32::consts::PI * 2.0;
+            let normal = [theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin()];
+            mesh.push_vertex([normal[0] * radius, normal[1] * radius, normal[2] * radius], normal, [u, v]);
+        }
+    }
+    for ring in 0..rings {
+        for segment in 0..segments {
+            let corner = segment + ring * (segments + 1);
+            let below = corner + segments + 1;
+            // The triangles touching the poles would have no area.
+            if ring != 0 {
+                mesh.push_triangle(corner, corner + 1, below);
+            }
+            if ring != rings - 1 {
+                mesh.push_triangle(below, corner + 1, below + 1);
+            }
+        }
+    }
+    mesh
+}
+
+///
+/// Creates a capped cylinder centered on the origin, standing along the Y axis.
+///
+pub fn cylinder(radius: f32, height: f32, segments: u32) -> Mesh {
+    let half = height / 2.0;
+    let mut mesh = Mesh::new();
+    let around = |segment: u32| {
+        let phi = segment as f32 / segments as f32 * std::f32::consts::PI * 2.0;
+        (phi.cos(), phi.sin())
+    };
+    for segment in 0..=segments {
+        let (x, z) = around(segment);
+        let u = segment as f32 / segments as f32;
+        mesh.push_vertex([x * radius, half, z * radius], [x, 0.0, z], [u, 0.0]);
+        mesh.push_vertex([x * radius, -half, z * radius], [x, 0.0, z], [u, 1.0]);
+    }
+    for segment in 0..segments {
+        let top = segment * 2;
+        mesh.push_triangle(top, top + 2, top + 1);
+        mesh.push_triangle(top + 1, top + 2, top + 3);
+    }
+    for (y, facing) in [(half, 1.0), (-half, -1.0)].iter() {
+        let center = mesh.push_vertex([0.0, *y, 0.0], [0.0, *facing, 0.0], [0.5, 0.5]);
+        for segment in 0..segments {
+            let (x, z) = around(segment);
+            mesh.push_vertex([x * radius, *y, z * radius], [0.0, *facing, 0.0], [0.5 + x * 0.5, 0.5 + z * 0.5]);
+        }
+        for segment in 0..segments {
+            let current = center + 1 + segment;
+            let next = center + 1 + (segment + 1) % segments;
+            if *facing > 0.0 {
+                mesh.push_triangle(center, next, current);
+            } else {
+                mesh.push_triangle(center, current, next);
+            }
+        }
+    }
+    mesh
+}
+
+///
+/// Generates a primitive mesh.
+///
+pub struct NodeGeometryPrimitiveV1 {
+    pub op: PrimitiveOp,
+}
+
+impl Nodeable for NodeGeometryPrimitiveV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            PrimitiveOp::Plane | PrimitiveOp::Grid => {
+                inputs.push(input_pin("Width", WIDTH, "f64", false));
+                inputs.push(input_pin("Depth", DEPTH, "f64", false));
+                if self.op == PrimitiveOp::Grid {
+                    inputs.push(input_pin("Columns", COLUMNS, "u64", false));
+                    inputs.push(input_pin("Rows", ROWS, "u64", false));
+                }
+            }
+            PrimitiveOp::Cube => inputs.push(input_pin("Size", SIZE, "f64", false)),
+            PrimitiveOp::Sphere => {
+                inputs.push(input_pin("Radius", RADIUS, "f64", false));
+                inputs.push(input_pin("Segments", SEGMENTS, "u64", false));
+                inputs.push(input_pin("Rings", RINGS, "u64", false));
+            }
+            PrimitiveOp::Cylinder => {
+                inputs.push(input_pin("Radius", RADIUS, "f64", false));
+                inputs.push(input_pin("Height", HEIGHT, "f64", false));
+                inputs.push(input_pin("Segments", SEGMENTS, "u64", false));
+            }
+        }
+        outputs.push(output_pin("Mesh", OUTPUT_MESH, "mesh"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_MESH).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default) as f32
+        };
+        let get_count = |id: &str, default: u64, min: u64| {
+            let count = node.get_input::<u64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default);
+            if count < min {
+                return Err(format!("need at least {} for input pin {}, got {}", min, id, count));
+            }
+            Ok(count as u32)
+        };
+        let mesh = match self.op {
+            PrimitiveOp::Plane => plane(get_f64(WIDTH, 1.0), get_f64(DEPTH, 1.0)),
+            PrimitiveOp::Grid => grid(
+                get_f64(WIDTH, 1.0),
+                get_f64(DEPTH, 1.0),
+                get_count(COLUMNS, 10, 1)?,
+                get_count(ROWS, 10, 1)?,
+            ),
+            PrimitiveOp::Cube => cube(get_f64(SIZE, 1.0)),
+            PrimitiveOp::Sphere => sphere(
+                get_f64(RADIUS, 0.5),
+                get_count(SEGMENTS, 32, 3)?,
+                get_count(RINGS, 16, 2)?,
+            ),
+            PrimitiveOp::Cylinder => cylinder(
+                get_f64(RADIUS, 0.5),
+                get_f64(HEIGHT, 1.0),
+                get_count(SEGMENTS, 32, 3)?,
+            ),
+        };
+        Ok(Some(Message::new(mesh)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeGeometryPrimitiveV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: PrimitiveOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeGeometryPrimitiveV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,141 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use crate::nodes::image::{image_input, Image};
+
+///
+/// Turns a heightmap into a terrain mesh, using the red channel as the height.
+/// Every pixel becomes a vertex, so the terrain has one less column and row of quads than the image has pixels.
+///
+#[derive(Default)]
+pub struct NodeGeometryTerrainV1 {}
+
+///
+/// Displaces a grid by a heightmap, spreading the image over the width and depth and scaling its red channel by the height.
+/// The top of the image is at the back of the terrain, towards negative Z.
+///
+pub fn terrain_from_height(heightmap: &Image, width: f32, depth: f32, height: f32) -> Result<Mesh, String> {
+    if heightmap.width < 2 || heightmap.height < 2 {
+        return Err(format!(
+            "a heightmap needs at least 2x2 pixels to make a terrain, got {}x{}",
+            heightmap.width, heightmap.height
+        ));
+    }
+    let mut mesh = super::primitive::grid(width, depth, heightmap.width - 1, heightmap.height - 1);
+    for (position, pixel) in mesh.positions.iter_mut().zip(heightmap.pixels.iter()) {
+        position[1] = pixel[0] * height;
+    }
+    mesh.compute_normals();
+    Ok(mesh)
+}
+
+impl Nodeable for NodeGeometryTerrainV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Heightmap", "bab458f1-c036-4ad1-adc8-e08a47cc368b", "image", false));
+        inputs.push(input_pin("Width", "a028392b-7ec1-40f6-9a10-03c5ac920cec", "f64", false));
+        inputs.push(input_pin("Depth", "b275a03e-3288-471f-889b-9c6b01f7fc02", "f64", false));
+        inputs.push(input_pin("Height", "14360f3a-58be-4efb-9fdc-0ae9d07e78c4", "f64", false));
+        outputs.push(output_pin("Mesh", "19c70073-cc92-426b-8ff0-67047c4c1216", "mesh"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_mesh if id_mesh == uuid::Uuid::parse_str("19c70073-cc92-426b-8ff0-67047c4c1216").unwrap() => {
+                let heightmap = image_input(node, "bab458f1-c036-4ad1-adc8-e08a47cc368b")?;
+                let get_f64 = |id: &str, default: f64| {
+                    node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                        .unwrap_or(default) as f32
+                };
+                let mesh = terrain_from_height(
+                    &heightmap,
+                    get_f64("a028392b-7ec1-40f6-9a10-03c5ac920cec", heightmap.width as f64 - 1.0),
+                    get_f64("b275a03e-3288-471f-889b-9c6b01f7fc02", heightmap.height as f64 - 1.0),
+                    get_f64("14360f3a-58be-4efb-9fdc-0ae9d07e78c4", 1.0),
+                )?;
+                Ok(Some(Message::new(mesh)))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeGeometryTerrainV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Heightmap Terrain"),
+                    uuid: uuid::Uuid::parse_str("c0a87009-2d40-42be-ba3c-997a0952bab9").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Heightmap Terrain"),
+            uuid: uuid::Uuid::parse_str("c0a87009-2d40-42be-ba3c-997a0952bab9").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("c0a87009-2d40-42be-ba3c-997a0952bab9").unwrap() {
+        return match version {
+            1 => Some(NodeGeometryTerrainV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,189 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The ways a layer can be blended over a base image.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Add,
+    Subtract,
+    Difference,
+    Darken,
+    Lighten,
+}
+
+impl BlendMode {
+    /// Reads a blend mode from its lowercase name.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "normal" => Some(BlendMode::Normal),
+            "multiply" => Some(BlendMode::Multiply),
+            "screen" => Some(BlendMode::Screen),
+            "overlay" => Some(BlendMode::Overlay),
+            "add" => Some(BlendMode::Add),
+            "subtract" => Some(BlendMode::Subtract),
+            "difference" => Some(BlendMode::Difference),
+            "darken" => Some(BlendMode::Darken),
+            "lighten" => Some(BlendMode::Lighten),
+            _ => None,
+        }
+    }
+    /// Blends a single channel of the layer over the base.
+    pub fn apply(&self, base: f32, layer: f32) -> f32 {
+        match self {
+            BlendMode::Normal => layer,
+            BlendMode::Multiply => base * layer,
+            BlendMode::Screen => 1.0 - (1.0 - base) * (1.0 - layer),
+            BlendMode::Overlay => {
+                if base < 0.5 {
+                    2.0 * base * layer
+                } else {
+                    1.0 - 2.0 * (1.0 - base) * (1.0 - layer)
+                }
+            }
+            BlendMode::Add => base + layer,
+            BlendMode::Subtract => base - layer,
+            BlendMode::Difference => (base - layer).abs(),
+            BlendMode::Darken => base.min(layer),
+            BlendMode::Lighten => base.max(layer),
+        }
+    }
+}
+
+///
+/// Blends a layer over a base image of the same size.
+/// The layer's alpha and the opacity control how much of the blend shows, and the base keeps its alpha.
+///
+#[derive(Default)]
+pub struct NodeImageBlendV1 {}
+
+impl Nodeable for NodeImageBlendV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Base", "265b5627-6c52-4842-9b54-a8353952cf11", "image", false));
+        inputs.push(input_pin("Layer", "68892ccd-dd37-4a4a-b9bd-66a6706bcfb3", "image", false));
+        inputs.push(input_pin("Mode", "32a12593-0083-4780-84c4-04e4311d2495", "string", false));
+        inputs.push(input_pin("Opacity", "767dd1da-13fa-44ec-8805-3a91445b85ee", "f64", false));
+        outputs.push(output_pin("Image", "e74de3bc-2ef0-4afb-b259-cf76b40414c6", "image"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_image if id_image == uuid::Uuid::parse_str("e74de3bc-2ef0-4afb-b259-cf76b40414c6").unwrap() => {
+                let base = image_input(node, "265b5627-6c52-4842-9b54-a8353952cf11")?;
+                let layer = image_input(node, "68892ccd-dd37-4a4a-b9bd-66a6706bcfb3")?;
+                if base.width != layer.width || base.height != layer.height {
+                    return Err(format!(
+                        "cannot blend a {}x{} layer over a {}x{} base",
+                        layer.width, layer.height, base.width, base.height
+                    ));
+                }
+                let mode_name = node
+                    .get_input::<String>(&uuid::Uuid::parse_str("32a12593-0083-4780-84c4-04e4311d2495").unwrap())
+                    .unwrap_or_else(|| String::from("normal"));
+                let mode = match BlendMode::parse(&mode_name) {
+                    Some(mode) => mode,
+                    None => return Err(format!("unknown blend mode {}", mode_name)),
+                };
+                let opacity = node
+                    .get_input::<f64>(&uuid::Uuid::parse_str("767dd1da-13fa-44ec-8805-3a91445b85ee").unwrap())
+                    .unwrap_or(1.0) as f32;
+                let mut blended = base.clone();
+                for (pixel, layer_pixel) in blended.pixels.iter_mut().zip(layer.pixels.iter()) {
+                    let amount = layer_pixel[3] * opacity;
+                    for channel in 0..3 {
+                        let value = mode.apply(pixel[channel], layer_pixel[channel]);
+                        pixel[channel] += (value - pixel[channel]) * amount;
+                    }
+                }
+                Ok(Some(Message::new(blended)))
+            }
+            _ => Err(format!("no output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeImageBlendV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Blend Images"),
+                    uuid: uuid::Uuid::parse_str("eb23644c-d88d-4f40-89ab-8a31e68506d2").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Blend Images"),
+            uuid: uuid::Uuid::parse_str("eb23644c-d88d-4f40-89ab-8a31e68506d2").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("eb23644c-d88d-4f40-89ab-8a31e68506d2").unwrap() {
+        return match version {
+            1 => Some(NodeImageBlendV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
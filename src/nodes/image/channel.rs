@@ -0,0 +1,163 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Whether an image is being split into channels or channels are being merged into an image.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChannelOp {
+    Split,
+    Merge,
+}
+
+/// The name, graph UUID and operation of every image channel node.
+pub const OPERATIONS: [(&str, &str, ChannelOp); 2] = [
+    ("Split Channels", "7bd4d587-0f63-4b40-a1eb-2b3d304dc8af", ChannelOp::Split),
+    ("Merge Channels", "c4ab1e5f-9b36-4f07-a7ea-8fb64966a242", ChannelOp::Merge),
+];
+
+const IMAGE: &str = "8d612d58-df6b-437c-88e7-4f57e7cf5b6c";
+const OUTPUT_IMAGE: &str = "5b2eb61e-de89-4b5f-8902-9932b15903c5";
+/// The red, green, blue and alpha pins, which are inputs when merging and outputs when splitting.
+const CHANNELS: [(&str, &str); 4] = [
+    ("Red", "7006f45f-5877-4f7e-9cb1-e7cd18d5cc62"),
+    ("Green", "c571a6c5-1f52-4007-bbc9-362eb48f38e6"),
+    ("Blue", "d3dc7c64-c35f-4556-9502-3cac79a000a9"),
+    ("Alpha", "a69913cd-9273-464f-bdb8-a8ab0f3bb8f3"),
+];
+
+///
+/// Splits an image into a grayscale image per channel, or merges grayscale images back into one.
+/// Merging reads the red channel of each input, and missing inputs are black, or opaque for alpha.
+///
+pub struct NodeImageChannelV1 {
+    pub op: ChannelOp,
+}
+
+impl Nodeable for NodeImageChannelV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            ChannelOp::Split => {
+                inputs.push(input_pin("Image", IMAGE, "image", false));
+                for (name, id) in CHANNELS.iter() {
+                    outputs.push(output_pin(name, id, "image"));
+                }
+            }
+            ChannelOp::Merge => {
+                for (name, id) in CHANNELS.iter() {
+                    inputs.push(input_pin(name, id, "image", false));
+                }
+                outputs.push(output_pin("Image", OUTPUT_IMAGE, "image"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("no output pin with uuid {}", output_info.uuid));
+        }
+        match self.op {
+            ChannelOp::Split => {
+                let image = image_input(node, IMAGE)?;
+                let channel = CHANNELS
+                    .iter()
+                    .position(|(_, id)| uuid::Uuid::parse_str(id).unwrap() == output_info.uuid)
+                    .unwrap();
+                Ok(Some(Message::new(image.map(|pixel| {
+                    let value = pixel[channel];
+                    [value, value, value, 1.0]
+                }))))
+            }
+            ChannelOp::Merge => {
+                let channels: Vec<Option<Image>> = CHANNELS
+                    .iter()
+                    .map(|(_, id)| node.get_input::<Image>(&uuid::Uuid::parse_str(id).unwrap()))
+                    .collect();
+                let (width, height) = match channels.iter().flatten().next() {
+                    Some(image) => (image.width, image.height),
+                    None => return Err(String::from("no channels to merge")),
+                };
+                if channels.iter().flatten().any(|image| image.width != width || image.height != height) {
+                    return Err(String::from("cannot merge channels of different sizes"));
+                }
+                let mut merged = Image::filled(width, height, [0.0, 0.0, 0.0, 1.0])?;
+                for (channel, image) in channels.iter().enumerate() {
+                    if let Some(image) = image {
+                        for (pixel, source) in merged.pixels.iter_mut().zip(image.pixels.iter()) {
+                            pixel[channel] = source[0];
+                        }
+                    }
+                }
+                Ok(Some(Message::new(merged)))
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeImageChannelV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ChannelOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeImageChannelV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,118 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Turns a noise grid into a grayscale image, such as a heightmap.
+/// Values from min to max become black to white, and only the first layer of 3D grids is used.
+///
+#[derive(Default)]
+pub struct NodeImageNoiseToImageV1 {}
+
+impl Nodeable for NodeImageNoiseToImageV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Grid", "67133568-1d59-48a3-84a9-bd9a1b8691e0", "noise_grid", false));
+        inputs.push(input_pin("Min", "b4b8513e-681d-49ae-80f7-7770e0d3a75d", "f64", false));
+        inputs.push(input_pin("Max", "f7cafb9d-b8ad-461e-ab58-9cc4c06a9341", "f64", false));
+        outputs.push(output_pin("Image", "e8fa4e7c-d516-480e-97bf-d7d96da02f72", "image"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_image if id_image == uuid::Uuid::parse_str("e8fa4e7c-d516-480e-97bf-d7d96da02f72").unwrap() => {
+                let grid = match node.get_input::<NoiseGrid>(&uuid::Uuid::parse_str("67133568-1d59-48a3-84a9-bd9a1b8691e0").unwrap()) {
+                    Some(grid) => grid,
+                    None => return Err(String::from("no noise grid to turn into an image")),
+                };
+                let get_f64 = |id: &str, default: f64| {
+                    node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                        .unwrap_or(default)
+                };
+                let min = get_f64("b4b8513e-681d-49ae-80f7-7770e0d3a75d", -1.0) as f32;
+                let max = get_f64("f7cafb9d-b8ad-461e-ab58-9cc4c06a9341", 1.0) as f32;
+                Ok(Some(Message::new(Image::from_noise_grid(&grid, min, max)?)))
+            }
+            _ => Err(format!("no output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeImageNoiseToImageV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Noise To Image"),
+                    uuid: uuid::Uuid::parse_str("ca12bc32-a9de-4c70-b89c-3fedacc2633b").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Noise To Image"),
+            uuid: uuid::Uuid::parse_str("ca12bc32-a9de-4c70-b89c-3fedacc2633b").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("ca12bc32-a9de-4c70-b89c-3fedacc2633b").unwrap() {
+        return match version {
+            1 => Some(NodeImageNoiseToImageV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
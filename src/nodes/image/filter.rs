@@ -0,0 +1,252 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The filters that change a single image.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterOp {
+    Resize,
+    Blur,
+    Levels,
+}
+
+/// The name, graph UUID and operation of every image filter node.
+pub const OPERATIONS: [(&str, &str, FilterOp); 3] = [
+    ("Resize Image", "d6029c3c-bfb1-4ee5-a15f-e4ea8458c35f", FilterOp::Resize),
+    ("Blur Image", "91150b1d-f8ae-4333-8a32-8789ebac639c", FilterOp::Blur),
+    ("Image Levels", "55caddbe-bcc3-4fb6-a992-5a7ef583f350", FilterOp::Levels),
+];
+
+const IMAGE: &str = "59ef9818-7356-446d-aa60-581af7918854";
+const WIDTH: &str = "c9e66049-b8c4-4534-a235-2f15a186c9ca";
+const HEIGHT: &str = "81d8d1ac-e883-4118-bb3c-a54e4ec2fbdc";
+const RADIUS: &str = "129f94bf-666e-4553-b0c6-a0d3cf6fa93a";
+const IN_BLACK: &str = "8a26c31a-cc93-48f0-8b57-33e214ad5221";
+const IN_WHITE: &str = "8a37757d-9994-4885-8996-a46bf7f2d7db";
+const GAMMA: &str = "552e92ee-debc-47cd-99ec-48f789785493";
+const OUT_BLACK: &str = "e23b9a88-74ae-4cf3-af76-c6716f839b6a";
+const OUT_WHITE: &str = "5b5c2266-4e85-4be9-982c-855828999fcd";
+const OUTPUT_IMAGE: &str = "dc7168e5-2023-4210-b205-37929d90015a";
+
+///
+/// Resizes an image with bilinear filtering.
+///
+pub fn resize(image: &Image, width: u32, height: u32) -> Result<Image, String> {
+    let mut resized = Image::filled(width, height, [0.0; 4])?;
+    let scale_x = image.width as f32 / width as f32;
+    let scale_y = image.height as f32 / height as f32;
+    for y in 0..height {
+        for x in 0..width {
+            let source_x = (x as f32 + 0.5) * scale_x - 0.5;
+            let source_y = (y as f32 + 0.5) * scale_y - 0.5;
+            let (left, top) = (source_x.floor(), source_y.floor());
+            let (fx, fy) = (source_x - left, source_y - top);
+            let (left, top) = (left as i64, top as i64);
+            let corners = [
+                (image.get_clamped(left, top), (1.0 - fx) * (1.0 - fy)),
+                (image.get_clamped(left + 1, top), fx * (1.0 - fy)),
+                (image.get_clamped(left, top + 1), (1.0 - fx) * fy),
+                (image.get_clamped(left + 1, top + 1), fx * fy),
+            ];
+            let mut pixel = [0.0; 4];
+            for (corner, weight) in corners.iter() {
+                for channel in 0..4 {
+                    pixel[channel] += corner[channel] * weight;
+                }
+            }
+            resized.set(x, y, pixel);
+        }
+    }
+    Ok(resized)
+}
+
+///
+/// Blurs an image with a gaussian kernel, where the radius is the standard deviation in pixels.
+/// Pixels past the edges repeat the edge pixels.
+///
+pub fn blur(image: &Image, radius: f32) -> Image {
+    if radius <= 0.0 {
+        return image.clone();
+    }
+    let reach = (radius * 3.0).ceil() as i64;
+    let mut kernel: Vec<f32> = (-reach..=reach)
+        .map(|offset| (-((offset * offset) as f32) / (2.0 * radius * radius)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+    for weight in kernel.iter_mut() {
+        *weight /= total;
+    }
+    let pass = |source: &Image, horizontal: bool| {
+        let mut target = source.clone();
+        for y in 0..source.height {
+            for x in 0..source.width {
+                let mut pixel = [0.0; 4];
+                for (i, weight) in kernel.iter().enumerate() {
+                    let offset = i as i64 - reach;
+                    let sample = if horizontal {
+                        source.get_clamped(x as i64 + offset, y as i64)
+                    } else {
+                        source.get_clamped(x as i64, y as i64 + offset)
+                    };
+                    for channel in 0..4 {
+                        pixel[channel] += sample[channel] * weight;
+                    }
+                }
+                target.set(x, y, pixel);
+            }
+        }
+        target
+    };
+    pass(&pass(image, true), false)
+}
+
+///
+/// Remaps the color channels of an image from an input range to an output range, with a gamma curve in between.
+/// Alpha is left alone.
+///
+pub fn levels(image: &Image, in_black: f32, in_white: f32, gamma: f32, out_black: f32, out_white: f32) -> Image {
+    let in_range = if in_white == in_black { 1.0 } else { in_white - in_black };
+    let inverse_gamma = if gamma <= 0.0 { 1.0 } else { 1.0 / gamma };
+    image.map(|pixel| {
+        let mut leveled = pixel;
+        for channel in leveled.iter_mut().take(3) {
+            let value = ((*channel - in_black) / in_range).max(0.0).min(1.0);
+            *channel = out_black + value.powf(inverse_gamma) * (out_white - out_black);
+        }
+        leveled
+    })
+}
+
+///
+/// Resizes, blurs, or adjusts the levels of an image.
+///
+pub struct NodeImageFilterV1 {
+    pub op: FilterOp,
+}
+
+impl Nodeable for NodeImageFilterV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Image", IMAGE, "image", false));
+        match self.op {
+            FilterOp::Resize => {
+                inputs.push(input_pin("Width", WIDTH, "u64", false));
+                inputs.push(input_pin("Height", HEIGHT, "u64", false));
+            }
+            FilterOp::Blur => inputs.push(input_pin("Radius", RADIUS, "f64", false)),
+            FilterOp::Levels => {
+                inputs.push(input_pin("In Black", IN_BLACK, "f64", false));
+                inputs.push(input_pin("In White", IN_WHITE, "f64", false));
+                inputs.push(input_pin("Gamma", GAMMA, "f64", false));
+                inputs.push(input_pin("Out Black", OUT_BLACK, "f64", false));
+                inputs.push(input_pin("Out White", OUT_WHITE, "f64", false));
+            }
+        }
+        outputs.push(output_pin("Image", OUTPUT_IMAGE, "image"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_IMAGE).unwrap() {
+            return Err(format!("no output pin with uuid {}", output_info.uuid));
+        }
+        let image = image_input(node, IMAGE)?;
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+        };
+        let get_u64 = |id: &str, default: u64| {
+            node.get_input::<u64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+        };
+        let filtered = match self.op {
+            FilterOp::Resize => {
+                let width = get_u64(WIDTH, image.width as u64);
+                let height = get_u64(HEIGHT, image.height as u64);
+                if width == 0 || height == 0 {
+                    return Err(format!("cannot resize an image to {}x{}", width, height));
+                }
+                // Checked before narrowing, so that sizes past u32 are rejected rather than cut short.
+                image_size(width, height)?;
+                resize(&image, width as u32, height as u32)?
+            }
+            FilterOp::Blur => blur(&image, get_f64(RADIUS, 1.0) as f32),
+            FilterOp::Levels => levels(
+                &image,
+                get_f64(IN_BLACK, 0.0) as f32,
+                get_f64(IN_WHITE, 1.0) as f32,
+                get_f64(GAMMA, 1.0) as f32,
+                get_f64(OUT_BLACK, 0.0) as f32,
+                get_f64(OUT_WHITE, 1.0) as f32,
+            ),
+        };
+        Ok(Some(Message::new(filtered)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeImageFilterV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: FilterOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeImageFilterV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
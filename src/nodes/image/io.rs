@@ -0,0 +1,143 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Whether an image file is being read or written.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ImageIoOp {
+    Load,
+    Save,
+}
+
+/// The name, graph UUID and operation of every image file node.
+pub const OPERATIONS: [(&str, &str, ImageIoOp); 2] = [
+    ("Load Image", "5603cbaf-8854-4339-a31b-53951893a853", ImageIoOp::Load),
+    ("Save Image", "274222df-f14c-41e8-9ab1-e58bbdf5134f", ImageIoOp::Save),
+];
+
+const PATH: &str = "6900c419-9048-475b-9f43-8102d01fae45";
+const IMAGE: &str = "568bd5fd-d2f5-48af-9b3b-ede2b26f7f9f";
+const OUTPUT_IMAGE: &str = "21d044e4-3990-4216-a8bb-c8e3b7903c26";
+const OUTPUT_WIDTH: &str = "6720c7a7-fcd2-4d39-87e7-13cea86c5e9b";
+const OUTPUT_HEIGHT: &str = "44747671-13d3-4f8e-86b6-d131e440356a";
+const OUTPUT_PATH: &str = "22f4c637-4185-4626-8321-8c7e31d1c839";
+
+///
+/// Loads an image from a file, or saves one to a file.
+/// PNG and JPEG are supported, along with anything else the image crate reads, but OpenEXR is not.
+/// Saving happens when the path output is computed, and gives back the path that was written.
+///
+pub struct NodeImageIoV1 {
+    pub op: ImageIoOp,
+}
+
+impl Nodeable for NodeImageIoV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            ImageIoOp::Load => {
+                inputs.push(input_pin("Path", PATH, "string", false));
+                outputs.push(output_pin("Image", OUTPUT_IMAGE, "image"));
+                outputs.push(output_pin("Width", OUTPUT_WIDTH, "u64"));
+                outputs.push(output_pin("Height", OUTPUT_HEIGHT, "u64"));
+            }
+            ImageIoOp::Save => {
+                inputs.push(input_pin("Image", IMAGE, "image", false));
+                inputs.push(input_pin("Path", PATH, "string", false));
+                outputs.push(output_pin("Path", OUTPUT_PATH, "string"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("no output pin with uuid {}", output_info.uuid));
+        }
+        let path = match node.get_input::<String>(&uuid::Uuid::parse_str(PATH).unwrap()) {
+            Some(path) => path,
+            None => return Err(String::from("no image path given")),
+        };
+        match self.op {
+            ImageIoOp::Load => {
+                let image = Image::load(&node.resolve_asset(&path)?)?;
+                match output_info.uuid {
+                    id if id == uuid::Uuid::parse_str(OUTPUT_WIDTH).unwrap() => Ok(Some(Message::new(image.width as u64))),
+                    id if id == uuid::Uuid::parse_str(OUTPUT_HEIGHT).unwrap() => Ok(Some(Message::new(image.height as u64))),
+                    _ => Ok(Some(Message::new(image))),
+                }
+            }
+            ImageIoOp::Save => {
+                image_input(node, IMAGE)?.save(std::path::Path::new(&path))?;
+                Ok(Some(Message::new(path)))
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeImageIoV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ImageIoOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeImageIoV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,197 @@
+pub mod blend;
+pub mod channel;
+pub mod convert;
+pub mod filter;
+pub mod io;
+pub mod normal;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut io::register());
+    nodes.append(&mut filter::register());
+    nodes.append(&mut channel::register());
+    nodes.push(blend::register());
+    nodes.push(normal::register());
+    nodes.push(convert::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(io::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(filter::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(channel::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(blend::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(normal::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(convert::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use super::noise::NoiseGrid;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The most pixels an image can hold, which is 256 MiB of pixels.
+pub const MAX_IMAGE_PIXELS: usize = 1 << 24;
+
+///
+/// Gives the number of pixels in an image of the given size, or an error if it would be more than `MAX_IMAGE_PIXELS`.
+///
+pub fn image_size(width: u64, height: u64) -> Result<usize, String> {
+    match width.checked_mul(height) {
+        Some(size) if size <= MAX_IMAGE_PIXELS as u64 => Ok(size as usize),
+        _ => Err(format!(
+            "image of {} by {} is larger than the most of {} pixels",
+            width, height, MAX_IMAGE_PIXELS
+        )),
+    }
+}
+
+///
+/// An image held in memory, carried on pins with the "image" datatype.
+/// Pixels are red, green, blue and alpha, nominally between zero and one, so heightmaps keep their precision.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    /// Pixels ordered by x, then y, starting at the top left.
+    pub pixels: Vec<[f32; 4]>,
+}
+
+impl Image {
+    /// Creates an image filled with a single pixel value, or an error if it would be larger than `MAX_IMAGE_PIXELS`.
+    pub fn filled(width: u32, height: u32, pixel: [f32; 4]) -> Result<Self, String> {
+        let size = image_size(width as u64, height as u64)?;
+        Ok(Self {
+            width,
+            height,
+            pixels: vec![pixel; size],
+        })
+    }
+    pub fn index(&self, x: u32, y: u32) -> usize {
+        (x + y * self.width) as usize
+    }
+    pub fn get(&self, x: u32, y: u32) -> [f32; 4] {
+        self.pixels[self.index(x, y)]
+    }
+    pub fn set(&mut self, x: u32, y: u32, pixel: [f32; 4]) {
+        let index = self.index(x, y);
+        self.pixels[index] = pixel;
+    }
+    /// Gets a pixel, using the nearest edge pixel for coordinates outside of the image.
+    pub fn get_clamped(&self, x: i64, y: i64) -> [f32; 4] {
+        let x = x.max(0).min(self.width as i64 - 1) as u32;
+        let y = y.max(0).min(self.height as i64 - 1) as u32;
+        self.get(x, y)
+    }
+    /// Creates a new image of the same size by changing every pixel.
+    pub fn map<F>(&self, f: F) -> Self
+    where
+        F: Fn([f32; 4]) -> [f32; 4],
+    {
+        Self {
+            width: self.width,
+            height: self.height,
+            pixels: self.pixels.iter().map(|pixel| f(*pixel)).collect(),
+        }
+    }
+    ///
+    /// Turns the first layer of a noise grid into a grayscale image.
+    /// Values between min and max are mapped to between black and white.
+    ///
+    pub fn from_noise_grid(grid: &NoiseGrid, min: f32, max: f32) -> Result<Self, String> {
+        let range = if max == min { 1.0 } else { max - min };
+        let mut image = Self::filled(grid.width as u32, grid.height as u32, [0.0, 0.0, 0.0, 1.0])?;
+        for y in 0..grid.height {
+            for x in 0..grid.width {
+                let value = ((grid.get(x, y, 0) - min) / range).max(0.0).min(1.0);
+                image.set(x as u32, y as u32, [value, value, value, 1.0]);
+            }
+        }
+        Ok(image)
+    }
+    ///
+    /// Loads an image file, in any format the image crate can read.
+    ///
+    pub fn load(path: &Path) -> Result<Self, String> {
+        check_supported(path)?;
+        let loaded = image::open(path)
+            .map_err(|e| format!("could not load image {}: {}", path.display(), e))?
+            .to_rgba16();
+        let (width, height) = loaded.dimensions();
+        Ok(Self {
+            width,
+            height,
+            pixels: loaded
+                .pixels()
+                .map(|pixel| {
+                    let mut channels = [0.0; 4];
+                    for (channel, value) in channels.iter_mut().zip(pixel.0.iter()) {
+                        *channel = *value as f32 / std::u16::MAX as f32;
+                    }
+                    channels
+                })
+                .collect(),
+        })
+    }
+    ///
+    /// Saves the image to a file, picking the format from the extension.
+    /// PNG keeps 16 bits per channel, while formats without alpha or 16 bit support, like JPEG, are saved as 8 bit RGB.
+    ///
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        check_supported(path)?;
+        let format = image::ImageFormat::from_path(path)
+            .map_err(|e| format!("could not save image {}: {}", path.display(), e))?;
+        let raw: Vec<u16> = self
+            .pixels
+            .iter()
+            .flat_map(|pixel| pixel.iter())
+            .map(|value| (value.max(0.0).min(1.0) * std::u16::MAX as f32).round() as u16)
+            .collect();
+        let buffer = image::ImageBuffer::<image::Rgba<u16>, Vec<u16>>::from_raw(self.width, self.height, raw)
+            .ok_or_else(|| format!("image pixels do not match its size of {}x{}", self.width, self.height))?;
+        let dynamic = image::DynamicImage::ImageRgba16(buffer);
+        let result = match format {
+            image::ImageFormat::Png => dynamic.save(path),
+            image::ImageFormat::Jpeg => image::DynamicImage::ImageRgb8(dynamic.to_rgb8()).save(path),
+            _ => image::DynamicImage::ImageRgba8(dynamic.to_rgba8()).save(path),
+        };
+        result.map_err(|e| format!("could not save image {}: {}", path.display(), e))
+    }
+}
+
+/// Gives back an error for image formats that cannot be read or written.
+fn check_supported(path: &Path) -> Result<(), String> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("exr") => Err(format!(
+            "could not use image {}: OpenEXR images are not supported",
+            path.display()
+        )),
+        _ => Ok(()),
+    }
+}
+
+///
+/// Gets the image on an input pin, which is an error if there is none.
+///
+pub fn image_input(node: &Node, id: &str) -> Result<Image, String> {
+    match node.get_input::<Image>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(image) => Ok(image),
+        None => Err(format!("no image on input pin {}", id)),
+    }
+}
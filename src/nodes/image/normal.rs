@@ -0,0 +1,140 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Creates a tangent space normal map from a heightmap, using the red channel as the height.
+/// Green points up the image, as in OpenGL, and the strength scales how steep slopes are.
+///
+#[derive(Default)]
+pub struct NodeImageNormalV1 {}
+
+///
+/// Computes a normal map from the red channel of a heightmap with a Sobel filter.
+///
+pub fn normal_from_height(height: &Image, strength: f32) -> Result<Image, String> {
+    let mut normals = Image::filled(height.width, height.height, [0.5, 0.5, 1.0, 1.0])?;
+    let sample = |x: i64, y: i64| height.get_clamped(x, y)[0];
+    for y in 0..height.height as i64 {
+        for x in 0..height.width as i64 {
+            let dx = (sample(x + 1, y - 1) + 2.0 * sample(x + 1, y) + sample(x + 1, y + 1))
+                - (sample(x - 1, y - 1) + 2.0 * sample(x - 1, y) + sample(x - 1, y + 1));
+            let dy = (sample(x - 1, y + 1) + 2.0 * sample(x, y + 1) + sample(x + 1, y + 1))
+                - (sample(x - 1, y - 1) + 2.0 * sample(x, y - 1) + sample(x + 1, y - 1));
+            let normal = [-dx * strength, dy * strength, 1.0];
+            let length = (normal[0] * normal[0] + normal[1] * normal[1] + normal[2] * normal[2]).sqrt();
+            normals.set(
+                x as u32,
+                y as u32,
+                [
+                    normal[0] / length * 0.5 + 0.5,
+                    normal[1] / length * 0.5 + 0.5,
+                    normal[2] / length * 0.5 + 0.5,
+                    1.0,
+                ],
+            );
+        }
+    }
+    Ok(normals)
+}
+
+impl Nodeable for NodeImageNormalV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Height", "078beec9-0354-4858-be58-646e9574b459", "image", false));
+        inputs.push(input_pin("Strength", "1da041c1-b09f-4cb9-85dc-3fdf84fe6ecf", "f64", false));
+        outputs.push(output_pin("Normal Map", "6127b999-b0ce-4793-bf0a-62a883f0d421", "image"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_normal if id_normal == uuid::Uuid::parse_str("6127b999-b0ce-4793-bf0a-62a883f0d421").unwrap() => {
+                let height = image_input(node, "078beec9-0354-4858-be58-646e9574b459")?;
+                let strength = node
+                    .get_input::<f64>(&uuid::Uuid::parse_str("1da041c1-b09f-4cb9-85dc-3fdf84fe6ecf").unwrap())
+                    .unwrap_or(1.0) as f32;
+                Ok(Some(Message::new(normal_from_height(&height, strength)?)))
+            }
+            _ => Err(format!("no output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeImageNormalV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Normal Map From Height"),
+                    uuid: uuid::Uuid::parse_str("6c88477e-058b-46f3-b762-2c61d585b126").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Normal Map From Height"),
+            uuid: uuid::Uuid::parse_str("6c88477e-058b-46f3-b762-2c61d585b126").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("6c88477e-058b-46f3-b762-2c61d585b126").unwrap() {
+        return match version {
+            1 => Some(NodeImageNormalV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
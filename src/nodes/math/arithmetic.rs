@@ -0,0 +1,193 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The operations that the arithmetic nodes do on floating point numbers.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArithmeticOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    Minimum,
+    Maximum,
+    Negate,
+    Absolute,
+    Floor,
+    Ceiling,
+    Round,
+    SquareRoot,
+}
+
+/// The name, graph UUID and operation of every arithmetic node.
+pub const OPERATIONS: [(&str, &str, ArithmeticOp); 14] = [
+    ("Add", "6d6388bb-cdda-4af0-abe1-7a18a48cd39f", ArithmeticOp::Add),
+    ("Subtract", "bc354069-355e-4a9d-9d67-695e525797eb", ArithmeticOp::Subtract),
+    ("Multiply", "d6c94883-48b5-4927-b40d-1c3ebc4caa40", ArithmeticOp::Multiply),
+    ("Divide", "eaa4f1e9-197e-4913-bd92-b5144f4d32b4", ArithmeticOp::Divide),
+    ("Modulo", "dd61d964-f8a3-4074-baf6-7efc8a8f3a16", ArithmeticOp::Modulo),
+    ("Power", "596703e5-ed31-4a71-8322-667fd9a9a19f", ArithmeticOp::Power),
+    ("Minimum", "69ed99af-e43b-4bc3-bc5d-c07e9d399f91", ArithmeticOp::Minimum),
+    ("Maximum", "e19a5a1a-0721-4ae6-a21b-8a391522c001", ArithmeticOp::Maximum),
+    ("Negate", "6de2887a-85c4-40f1-ae59-45d92e3aa11f", ArithmeticOp::Negate),
+    ("Absolute", "5fd99250-a30c-40bb-8f0e-b81c38d102a4", ArithmeticOp::Absolute),
+    ("Floor", "e44af15e-559c-4c2a-9fe6-4e64abda2e8d", ArithmeticOp::Floor),
+    ("Ceiling", "72423aea-df70-463c-a3ec-deedd325712c", ArithmeticOp::Ceiling),
+    ("Round", "77fc9826-96d6-4a68-915d-76f4276ed553", ArithmeticOp::Round),
+    ("Square Root", "7acf8e3b-516b-4fc8-bb76-115d4c21ef4e", ArithmeticOp::SquareRoot),
+];
+
+const VALUES: &str = "e239fc7c-13cd-4743-8591-13fe062fd499";
+const A: ScalarInput = ("A", "496f9404-e512-49e5-aaf0-8cc7f10b5a70", 0.0);
+const B: ScalarInput = ("B", "df14f6ec-55a2-4f79-9714-1d22283b3c20", 0.0);
+const RESULT: &str = "f76fcf19-8d4e-4672-9745-4c48419e5644";
+
+impl ArithmeticOp {
+    /// Whether or not the operation works on any number of values from a single expandable pin.
+    pub fn is_variadic(&self) -> bool {
+        match self {
+            ArithmeticOp::Add | ArithmeticOp::Multiply | ArithmeticOp::Minimum | ArithmeticOp::Maximum => true,
+            _ => false,
+        }
+    }
+    /// The scalar inputs of the operation, if it is not variadic.
+    pub fn inputs(&self) -> Vec<ScalarInput> {
+        match self {
+            ArithmeticOp::Subtract | ArithmeticOp::Divide | ArithmeticOp::Modulo | ArithmeticOp::Power => vec![A, B],
+            _ if self.is_variadic() => Vec::new(),
+            _ => vec![A],
+        }
+    }
+    /// Applies the operation to values given in the order of its inputs.
+    pub fn apply(&self, values: &[f64]) -> Result<f64, String> {
+        let result = match self {
+            ArithmeticOp::Add => values.iter().sum(),
+            ArithmeticOp::Multiply => values.iter().product(),
+            ArithmeticOp::Minimum | ArithmeticOp::Maximum if values.is_empty() => {
+                return Err(format!("{:?} needs at least one value", self));
+            }
+            ArithmeticOp::Minimum => values.iter().cloned().fold(std::f64::INFINITY, f64::min),
+            ArithmeticOp::Maximum => values.iter().cloned().fold(std::f64::NEG_INFINITY, f64::max),
+            ArithmeticOp::Divide | ArithmeticOp::Modulo if values[1] == 0.0 => {
+                return Err(format!("{:?} by zero", self));
+            }
+            ArithmeticOp::Subtract => values[0] - values[1],
+            ArithmeticOp::Divide => values[0] / values[1],
+            ArithmeticOp::Modulo => values[0].rem_euclid(values[1]),
+            ArithmeticOp::Power => values[0].powf(values[1]),
+            ArithmeticOp::Negate => -values[0],
+            ArithmeticOp::Absolute => values[0].abs(),
+            ArithmeticOp::Floor => values[0].floor(),
+            ArithmeticOp::Ceiling => values[0].ceil(),
+            ArithmeticOp::Round => values[0].round(),
+            ArithmeticOp::SquareRoot => values[0].sqrt(),
+        };
+        if result.is_nan() && !values.iter().any(|value| value.is_nan()) {
+            return Err(format!("{:?} of {:?} is not a number", self, values));
+        }
+        Ok(result)
+    }
+}
+
+///
+/// Does arithmetic on floating point numbers.
+/// Adding, multiplying, and taking the minimum or maximum work on any number of values.
+///
+pub struct NodeMathArithmeticV1 {
+    pub op: ArithmeticOp,
+}
+
+impl Nodeable for NodeMathArithmeticV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        if self.op.is_variadic() {
+            inputs.push(input_pin("Values", VALUES, "f64", true));
+        }
+        for (name, id, _) in self.op.inputs() {
+            inputs.push(input_pin(name, id, "f64", false));
+        }
+        outputs.push(output_pin("Result", RESULT, "f64"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(RESULT).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let values = if self.op.is_variadic() {
+            node.get_inputs::<f64>(&uuid::Uuid::parse_str(VALUES).unwrap())
+        } else {
+            scalar_inputs(node, &self.op.inputs())
+        };
+        self.op.apply(&values).map(|result| Some(Message::new(result)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeMathArithmeticV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ArithmeticOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeMathArithmeticV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
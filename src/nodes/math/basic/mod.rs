@@ -1 +0,0 @@
-pub mod add;
\ No newline at end of file
@@ -0,0 +1,144 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The ways the comparison nodes compare two floating point numbers.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComparisonOp {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// The name, graph UUID and operation of every comparison node.
+pub const OPERATIONS: [(&str, &str, ComparisonOp); 6] = [
+    ("Equal", "d6369fb0-5391-4ad5-8fd2-cddfe244c2bf", ComparisonOp::Equal),
+    ("Not Equal", "5b83ab30-bc1d-4829-acc9-cbf0c096f804", ComparisonOp::NotEqual),
+    ("Less Than", "15a5faef-54dc-4a10-b2e8-bcab79d1d0d2", ComparisonOp::Less),
+    ("Less Than Or Equal", "300b6ae5-e0be-41fd-a98c-f2c4f76dab1b", ComparisonOp::LessOrEqual),
+    ("Greater Than", "fbee1b7d-438b-45d5-9d69-0ec96cf5cba1", ComparisonOp::Greater),
+    ("Greater Than Or Equal", "b531b22f-5050-4ec9-8a1f-cac31ebc7d91", ComparisonOp::GreaterOrEqual),
+];
+
+const A: ScalarInput = ("A", "133d0d0b-debe-4361-be2a-c6dc9e8a0aa6", 0.0);
+const B: ScalarInput = ("B", "ae7e001a-f64e-4291-9210-4115cbdff893", 0.0);
+const TOLERANCE: ScalarInput = ("Tolerance", "5e1fdf56-dd84-4c73-a911-eac0d35c72bb", 0.0);
+const RESULT: &str = "7358c2ba-9130-43f4-9f9e-cfa8140ff986";
+
+impl ComparisonOp {
+    /// The scalar inputs of the operation.
+    /// Only equality checks have a tolerance.
+    pub fn inputs(&self) -> Vec<ScalarInput> {
+        match self {
+            ComparisonOp::Equal | ComparisonOp::NotEqual => vec![A, B, TOLERANCE],
+            _ => vec![A, B],
+        }
+    }
+    /// Applies the operation to values given in the order of its inputs.
+    pub fn apply(&self, values: &[f64]) -> bool {
+        match self {
+            ComparisonOp::Equal => (values[0] - values[1]).abs() <= values[2].abs(),
+            ComparisonOp::NotEqual => (values[0] - values[1]).abs() > values[2].abs(),
+            ComparisonOp::Less => values[0] < values[1],
+            ComparisonOp::LessOrEqual => values[0] <= values[1],
+            ComparisonOp::Greater => values[0] > values[1],
+            ComparisonOp::GreaterOrEqual => values[0] >= values[1],
+        }
+    }
+}
+
+///
+/// Compares two floating point numbers, giving back a boolean.
+///
+pub struct NodeMathComparisonV1 {
+    pub op: ComparisonOp,
+}
+
+impl Nodeable for NodeMathComparisonV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        for (name, id, _) in self.op.inputs() {
+            inputs.push(input_pin(name, id, "f64", false));
+        }
+        outputs.push(output_pin("Result", RESULT, "bool"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(RESULT).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let values = scalar_inputs(node, &self.op.inputs());
+        Ok(Some(Message::new(self.op.apply(&values))))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeMathComparisonV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: ComparisonOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeMathComparisonV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
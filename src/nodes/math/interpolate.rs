@@ -0,0 +1,161 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The operations that keep or move numbers within ranges.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterpolateOp {
+    Clamp,
+    Lerp,
+    InverseLerp,
+    Remap,
+    Smoothstep,
+}
+
+/// The name, graph UUID and operation of every interpolation node.
+pub const OPERATIONS: [(&str, &str, InterpolateOp); 5] = [
+    ("Clamp", "104d459e-9254-4346-9599-768292f572d7", InterpolateOp::Clamp),
+    ("Lerp", "1c7bafc7-a2dc-4b88-a7be-37cb42d49080", InterpolateOp::Lerp),
+    ("Inverse Lerp", "56b9c15e-c221-4048-a08e-0b54c1e5ef3b", InterpolateOp::InverseLerp),
+    ("Remap", "8c31f36d-fd54-4235-b8da-f3e53f78b82e", InterpolateOp::Remap),
+    ("Smoothstep", "122015a4-5e8e-4f19-99b0-7b05ae9521e8", InterpolateOp::Smoothstep),
+];
+
+const VALUE: ScalarInput = ("Value", "5c89326b-81c6-4db7-a97c-08e9774c3635", 0.0);
+const MIN: ScalarInput = ("Min", "cbf1357c-d529-42b1-a98a-5f88dcd5fdfc", 0.0);
+const MAX: ScalarInput = ("Max", "3b18dd08-269d-4f8e-b347-557493327575", 1.0);
+const A: ScalarInput = ("A", "e78c425f-ce0f-4f06-b034-153ac836afed", 0.0);
+const B: ScalarInput = ("B", "027d0c71-9821-4b45-87d8-9189cad3c729", 1.0);
+const T: ScalarInput = ("T", "ba014b52-d61f-4855-aba2-3c25f2bc1f8b", 0.0);
+const FROM_MIN: ScalarInput = ("From Min", "7a56f40a-06e0-4aac-b7f3-3b8c5687a3ce", 0.0);
+const FROM_MAX: ScalarInput = ("From Max", "d396e805-45f2-418b-93dc-4a77fcd55d56", 1.0);
+const TO_MIN: ScalarInput = ("To Min", "5d07e56c-5a17-4650-8aea-a0bb7fd9d3ef", 0.0);
+const TO_MAX: ScalarInput = ("To Max", "327c4777-7c53-4cd6-a3b5-d810a7289e13", 1.0);
+const RESULT: &str = "448abaa0-ca15-4024-ac9e-2ebd4e45fb5e";
+
+impl InterpolateOp {
+    /// The scalar inputs of the operation.
+    pub fn inputs(&self) -> Vec<ScalarInput> {
+        match self {
+            InterpolateOp::Clamp => vec![VALUE, MIN, MAX],
+            InterpolateOp::Lerp => vec![A, B, T],
+            InterpolateOp::InverseLerp => vec![A, B, VALUE],
+            InterpolateOp::Remap => vec![VALUE, FROM_MIN, FROM_MAX, TO_MIN, TO_MAX],
+            InterpolateOp::Smoothstep => vec![MIN, MAX, VALUE],
+        }
+    }
+    /// Applies the operation to values given in the order of its inputs.
+    pub fn apply(&self, values: &[f64]) -> Result<f64, String> {
+        let inverse_lerp = |a: f64, b: f64, value: f64| {
+            if a == b {
+                Err(format!("{:?} over an empty range", self))
+            } else {
+                Ok((value - a) / (b - a))
+            }
+        };
+        match self {
+            InterpolateOp::Clamp => Ok(values[0].max(values[1]).min(values[2])),
+            InterpolateOp::Lerp => Ok(values[0] + (values[1] - values[0]) * values[2]),
+            InterpolateOp::InverseLerp => inverse_lerp(values[0], values[1], values[2]),
+            InterpolateOp::Remap => inverse_lerp(values[1], values[2], values[0])
+                .map(|t| values[3] + (values[4] - values[3]) * t),
+            InterpolateOp::Smoothstep => inverse_lerp(values[0], values[1], values[2]).map(|t| {
+                let t = t.max(0.0).min(1.0);
+                t * t * (3.0 - 2.0 * t)
+            }),
+        }
+    }
+}
+
+///
+/// Clamps, interpolates or remaps floating point numbers.
+///
+pub struct NodeMathInterpolateV1 {
+    pub op: InterpolateOp,
+}
+
+impl Nodeable for NodeMathInterpolateV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        for (name, id, _) in self.op.inputs() {
+            inputs.push(input_pin(name, id, "f64", false));
+        }
+        outputs.push(output_pin("Result", RESULT, "f64"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(RESULT).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let values = scalar_inputs(node, &self.op.inputs());
+        self.op.apply(&values).map(|result| Some(Message::new(result)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeMathInterpolateV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: InterpolateOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeMathInterpolateV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,181 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use crate::nodes::*;
+use axiom::prelude::*;
+use nalgebra::{Matrix4, Point3, Vector3};
+
+///
+/// The operations that the matrix nodes do on 4x4 transformation matrices.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatrixOp {
+    ComposeTransform,
+    Multiply,
+    Invert,
+    Transpose,
+    TransformPoint,
+    TransformDirection,
+}
+
+/// The name, graph UUID and operation of every matrix node.
+pub const OPERATIONS: [(&str, &str, MatrixOp); 6] = [
+    ("Compose Transform", "570ccdbb-88f3-4a69-a8de-a0258695a6d6", MatrixOp::ComposeTransform),
+    ("Matrix Multiply", "53342acc-5d66-4ed2-9b80-e73c80ed80ac", MatrixOp::Multiply),
+    ("Matrix Invert", "1613c737-51e1-4e1a-a0d7-f60ad45be7ea", MatrixOp::Invert),
+    ("Matrix Transpose", "382e600b-6de7-48dd-9e07-792544557cd7", MatrixOp::Transpose),
+    ("Transform Point", "4d34c3d2-bcef-484f-ae47-bcd9409760c1", MatrixOp::TransformPoint),
+    ("Transform Direction", "bff9a7d6-4c52-47d1-b035-257cbc096aa9", MatrixOp::TransformDirection),
+];
+
+const TRANSLATION: &str = "db876fdf-dff8-4351-8398-320c281f78c7";
+const ROTATION: &str = "d738df38-fc82-4a02-92fe-cd61cd3fa2bb";
+const SCALE: &str = "0ead2984-7816-4b30-9e95-8ba435ede764";
+const MATRIX: &str = "eee9ecbc-b9ef-4a02-8377-d42efaafdb54";
+const MATRICES: &str = "29794dcd-e302-4786-a51b-d9365ce5c958";
+const VECTOR: &str = "04eeef24-5a3f-4abc-ade8-c60f001efd85";
+const RESULT_MATRIX: &str = "5f6c89ae-e1fb-403f-be60-db09d2daafb5";
+const RESULT_VECTOR: &str = "1ff15120-ee14-4e27-82ee-9647b0bbe65e";
+
+///
+/// Builds, combines and applies 4x4 transformation matrices.
+/// Matrices are sent as nalgebra matrices with the "mat4" datatype, and vectors with the "vec3" datatype.
+///
+pub struct NodeMathMatrixV1 {
+    pub op: MatrixOp,
+}
+
+impl Nodeable for NodeMathMatrixV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            MatrixOp::ComposeTransform => {
+                inputs.push(input_pin("Translation", TRANSLATION, "vec3", false));
+                inputs.push(input_pin("Rotation", ROTATION, "vec3", false));
+                inputs.push(input_pin("Scale", SCALE, "vec3", false));
+            }
+            MatrixOp::Multiply => inputs.push(input_pin("Matrices", MATRICES, "mat4", true)),
+            MatrixOp::Invert | MatrixOp::Transpose => {
+                inputs.push(input_pin("Matrix", MATRIX, "mat4", false))
+            }
+            MatrixOp::TransformPoint | MatrixOp::TransformDirection => {
+                inputs.push(input_pin("Matrix", MATRIX, "mat4", false));
+                inputs.push(input_pin("Vector", VECTOR, "vec3", false));
+            }
+        }
+        match self.op {
+            MatrixOp::TransformPoint | MatrixOp::TransformDirection => {
+                outputs.push(output_pin("Result", RESULT_VECTOR, "vec3"))
+            }
+            _ => outputs.push(output_pin("Result", RESULT_MATRIX, "mat4")),
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let vector = |id: &str, default: Vector3<f64>| {
+            node.get_input::<Vector3<f64>>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+        };
+        let matrix = || {
+            node.get_input::<Matrix4<f64>>(&uuid::Uuid::parse_str(MATRIX).unwrap())
+                .unwrap_or_else(Matrix4::identity)
+        };
+        let message = match self.op {
+            MatrixOp::ComposeTransform => {
+                let translation = vector(TRANSLATION, Vector3::zeros());
+                let rotation = vector(ROTATION, Vector3::zeros());
+                let scale = vector(SCALE, Vector3::repeat(1.0));
+                Message::new(
+                    Matrix4::new_translation(&translation)
+                        * Matrix4::from_euler_angles(rotation.x, rotation.y, rotation.z)
+                        * Matrix4::new_nonuniform_scaling(&scale),
+                )
+            }
+            MatrixOp::Multiply => Message::new(
+                node.get_inputs::<Matrix4<f64>>(&uuid::Uuid::parse_str(MATRICES).unwrap())
+                    .iter()
+                    .fold(Matrix4::identity(), |product, value| product * value),
+            ),
+            MatrixOp::Invert => match matrix().try_inverse() {
+                Some(inverse) => Message::new(inverse),
+                None => return Err(String::from("cannot invert a singular matrix")),
+            },
+            MatrixOp::Transpose => Message::new(matrix().transpose()),
+            MatrixOp::TransformPoint => Message::new(
+                matrix()
+                    .transform_point(&Point3::from(vector(VECTOR, Vector3::zeros())))
+                    .coords,
+            ),
+            MatrixOp::TransformDirection => {
+                Message::new(matrix().transform_vector(&vector(VECTOR, Vector3::zeros())))
+            }
+        };
+        Ok(Some(message))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeMathMatrixV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: MatrixOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeMathMatrixV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -1 +1,58 @@
-pub mod basic;
\ No newline at end of file
+pub mod arithmetic;
+pub mod comparison;
+pub mod interpolate;
+pub mod matrix;
+pub mod trig;
+pub mod vector;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut arithmetic::register());
+    nodes.append(&mut comparison::register());
+    nodes.append(&mut trig::register());
+    nodes.append(&mut interpolate::register());
+    nodes.append(&mut vector::register());
+    nodes.append(&mut matrix::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(arithmetic::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(comparison::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(trig::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(interpolate::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(vector::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(matrix::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+/// A scalar input of a math node: its name, its pin id, and the value used when it is not set.
+pub type ScalarInput = (&'static str, &'static str, f64);
+
+///
+/// Gets the values of scalar inputs in the order they were given, using their defaults where they are not set.
+///
+pub fn scalar_inputs(node: &Node, inputs: &[ScalarInput]) -> Vec<f64> {
+    inputs
+        .iter()
+        .map(|(_, id, default)| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(*default)
+        })
+        .collect()
+}
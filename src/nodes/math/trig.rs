@@ -0,0 +1,156 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The trigonometric operations, all working in radians.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TrigOp {
+    Sine,
+    Cosine,
+    Tangent,
+    Arcsine,
+    Arccosine,
+    Arctangent,
+    Arctangent2,
+    DegreesToRadians,
+    RadiansToDegrees,
+}
+
+/// The name, graph UUID and operation of every trigonometry node.
+pub const OPERATIONS: [(&str, &str, TrigOp); 9] = [
+    ("Sine", "15e06fd8-c9d4-4b10-a298-d136d106ebf9", TrigOp::Sine),
+    ("Cosine", "1925b61f-6be1-4a92-9e0c-6e5184fc4be7", TrigOp::Cosine),
+    ("Tangent", "4dda6a77-cb7f-48cd-be71-6bac0e46b05a", TrigOp::Tangent),
+    ("Arcsine", "aec50a67-d77e-4285-a454-8d793ef1cfb8", TrigOp::Arcsine),
+    ("Arccosine", "f9d64190-7e6a-4b0a-8c11-8dcf60af342b", TrigOp::Arccosine),
+    ("Arctangent", "25950c02-11bf-43e1-aebe-5f4295771072", TrigOp::Arctangent),
+    ("Arctangent 2", "0bde22ed-31ab-4a56-a24e-359858eb8f14", TrigOp::Arctangent2),
+    ("Degrees To Radians", "c34db98d-9d7d-424d-a875-f26dae5f0aee", TrigOp::DegreesToRadians),
+    ("Radians To Degrees", "7931b9e9-03e6-4923-abd0-9342b6efaf94", TrigOp::RadiansToDegrees),
+];
+
+const VALUE: ScalarInput = ("Value", "24b29853-a326-4840-b94c-6ad027a42dc1", 0.0);
+const Y: ScalarInput = ("Y", "6e0e98f0-db60-47a0-8d00-f281d9466b09", 0.0);
+const X: ScalarInput = ("X", "0029f9ac-d23d-4bfb-8caf-d60756c97945", 1.0);
+const RESULT: &str = "b79ccf7d-eda4-4974-8862-083b84ada5a1";
+
+impl TrigOp {
+    /// The scalar inputs of the operation.
+    pub fn inputs(&self) -> Vec<ScalarInput> {
+        match self {
+            TrigOp::Arctangent2 => vec![Y, X],
+            _ => vec![VALUE],
+        }
+    }
+    /// Applies the operation to values given in the order of its inputs.
+    pub fn apply(&self, values: &[f64]) -> Result<f64, String> {
+        let result = match self {
+            TrigOp::Sine => values[0].sin(),
+            TrigOp::Cosine => values[0].cos(),
+            TrigOp::Tangent => values[0].tan(),
+            TrigOp::Arcsine => values[0].asin(),
+            TrigOp::Arccosine => values[0].acos(),
+            TrigOp::Arctangent => values[0].atan(),
+            TrigOp::Arctangent2 => values[0].atan2(values[1]),
+            TrigOp::DegreesToRadians => values[0].to_radians(),
+            TrigOp::RadiansToDegrees => values[0].to_degrees(),
+        };
+        if result.is_nan() && !values.iter().any(|value| value.is_nan()) {
+            return Err(format!("{:?} of {:?} is not a number", self, values));
+        }
+        Ok(result)
+    }
+}
+
+///
+/// Applies a trigonometric function to a floating point number.
+///
+pub struct NodeMathTrigV1 {
+    pub op: TrigOp,
+}
+
+impl Nodeable for NodeMathTrigV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        for (name, id, _) in self.op.inputs() {
+            inputs.push(input_pin(name, id, "f64", false));
+        }
+        outputs.push(output_pin("Result", RESULT, "f64"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(RESULT).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let values = scalar_inputs(node, &self.op.inputs());
+        self.op.apply(&values).map(|result| Some(Message::new(result)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeMathTrigV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: TrigOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeMathTrigV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
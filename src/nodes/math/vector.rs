@@ -0,0 +1,205 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+use nalgebra::Vector3;
+
+///
+/// The operations that the vector nodes do on three dimensional vectors.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum VectorOp {
+    Compose,
+    Decompose,
+    Add,
+    Subtract,
+    Scale,
+    Dot,
+    Cross,
+    Normalize,
+    Length,
+    Distance,
+    Lerp,
+}
+
+/// The name, graph UUID and operation of every vector node.
+pub const OPERATIONS: [(&str, &str, VectorOp); 11] = [
+    ("Compose Vector", "3f8df614-1adb-47a7-a297-c5806605a650", VectorOp::Compose),
+    ("Decompose Vector", "0ba74401-f7c7-4329-85e3-1a1a204039ba", VectorOp::Decompose),
+    ("Vector Add", "aa0f1741-2e2f-4478-a425-9ab9d6dea563", VectorOp::Add),
+    ("Vector Subtract", "86a4c52c-c80d-421f-acd6-89e2235bcabd", VectorOp::Subtract),
+    ("Vector Scale", "ea1e5857-79fa-44a6-a131-9b25ffb84e7d", VectorOp::Scale),
+    ("Dot Product", "edb4fe78-73a5-417c-913b-ff0054f9eea5", VectorOp::Dot),
+    ("Cross Product", "7196d74a-22f4-4496-bb68-4461341a0313", VectorOp::Cross),
+    ("Normalize", "7cae6b67-7352-4381-a560-197d65df6766", VectorOp::Normalize),
+    ("Vector Length", "f8f152aa-a6ef-4d04-8932-0b4a0d5ee071", VectorOp::Length),
+    ("Vector Distance", "ffba0485-8235-48e3-800f-5b5383798dc5", VectorOp::Distance),
+    ("Vector Lerp", "d783f475-f803-416b-b3eb-6f892af37d7d", VectorOp::Lerp),
+];
+
+const X: ScalarInput = ("X", "bc14cd51-8c03-4d35-ace7-507e1fc4e507", 0.0);
+const Y: ScalarInput = ("Y", "b4895f83-d630-4682-8c05-a8ce69b4ef60", 0.0);
+const Z: ScalarInput = ("Z", "0a000d41-9f25-4d11-b450-ecb1b9515d66", 0.0);
+const SCALE: ScalarInput = ("Scale", "7ebb72b7-f391-40ec-b5cb-974e8d5e0923", 1.0);
+const T: ScalarInput = ("T", "6b5e1a84-895d-4330-9993-f704940e332f", 0.0);
+const VECTOR: &str = "9120f3d1-c859-4145-9e48-2eaca97d92b4";
+const VECTORS: &str = "739649c4-44ab-4dac-84f0-55297439bbd7";
+const A: &str = "3b34e696-3ff5-41cc-8cf4-c9c04871a75c";
+const B: &str = "04459385-df60-48d3-b0f9-a689d6937173";
+const RESULT_VECTOR: &str = "7fc989e2-ce43-40db-8bff-b4fb16888651";
+const RESULT_VALUE: &str = "6cfb4dba-f3e7-432c-a292-5c56977d38e0";
+const OUTPUT_X: &str = "559ec851-8609-4559-97a8-9aba0a834290";
+const OUTPUT_Y: &str = "70ae6928-fbb3-47ee-b7dc-e0e66c7ece38";
+const OUTPUT_Z: &str = "69e25c41-ed06-4597-9f9b-7ec329fa7553";
+
+///
+/// Works on three dimensional vectors of floating point numbers.
+/// Vectors are sent as nalgebra vectors with the "vec3" datatype.
+///
+pub struct NodeMathVectorV1 {
+    pub op: VectorOp,
+}
+
+impl Nodeable for NodeMathVectorV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            VectorOp::Compose => {
+                for (name, id, _) in [X, Y, Z].iter() {
+                    inputs.push(input_pin(name, id, "f64", false));
+                }
+            }
+            VectorOp::Add => inputs.push(input_pin("Vectors", VECTORS, "vec3", true)),
+            VectorOp::Decompose | VectorOp::Normalize | VectorOp::Length => {
+                inputs.push(input_pin("Vector", VECTOR, "vec3", false))
+            }
+            VectorOp::Scale => {
+                inputs.push(input_pin("Vector", VECTOR, "vec3", false));
+                inputs.push(input_pin(SCALE.0, SCALE.1, "f64", false));
+            }
+            VectorOp::Subtract | VectorOp::Dot | VectorOp::Cross | VectorOp::Distance | VectorOp::Lerp => {
+                inputs.push(input_pin("A", A, "vec3", false));
+                inputs.push(input_pin("B", B, "vec3", false));
+                if self.op == VectorOp::Lerp {
+                    inputs.push(input_pin(T.0, T.1, "f64", false));
+                }
+            }
+        }
+        match self.op {
+            VectorOp::Decompose => {
+                outputs.push(output_pin("X", OUTPUT_X, "f64"));
+                outputs.push(output_pin("Y", OUTPUT_Y, "f64"));
+                outputs.push(output_pin("Z", OUTPUT_Z, "f64"));
+            }
+            VectorOp::Dot | VectorOp::Length | VectorOp::Distance => {
+                outputs.push(output_pin("Value", RESULT_VALUE, "f64"))
+            }
+            _ => outputs.push(output_pin("Result", RESULT_VECTOR, "vec3")),
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let vector = |id: &str| {
+            node.get_input::<Vector3<f64>>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or_else(Vector3::zeros)
+        };
+        let scalar = |input: ScalarInput| scalar_inputs(node, &[input])[0];
+        let message = match self.op {
+            VectorOp::Compose => Message::new(Vector3::new(scalar(X), scalar(Y), scalar(Z))),
+            VectorOp::Decompose => {
+                let value = vector(VECTOR);
+                let component = match output_info.uuid {
+                    id if id == uuid::Uuid::parse_str(OUTPUT_X).unwrap() => value.x,
+                    id if id == uuid::Uuid::parse_str(OUTPUT_Y).unwrap() => value.y,
+                    _ => value.z,
+                };
+                Message::new(component)
+            }
+            VectorOp::Add => Message::new(
+                node.get_inputs::<Vector3<f64>>(&uuid::Uuid::parse_str(VECTORS).unwrap())
+                    .iter()
+                    .fold(Vector3::zeros(), |sum, value| sum + value),
+            ),
+            VectorOp::Subtract => Message::new(vector(A) - vector(B)),
+            VectorOp::Scale => Message::new(vector(VECTOR) * scalar(SCALE)),
+            VectorOp::Dot => Message::new(vector(A).dot(&vector(B))),
+            VectorOp::Cross => Message::new(vector(A).cross(&vector(B))),
+            VectorOp::Normalize => match vector(VECTOR).try_normalize(std::f64::EPSILON) {
+                Some(normal) => Message::new(normal),
+                None => return Err(String::from("cannot normalize a zero length vector")),
+            },
+            VectorOp::Length => Message::new(vector(VECTOR).norm()),
+            VectorOp::Distance => Message::new((vector(A) - vector(B)).norm()),
+            VectorOp::Lerp => Message::new(vector(A).lerp(&vector(B), scalar(T))),
+        };
+        Ok(Some(message))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeMathVectorV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: VectorOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeMathVectorV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -192,7 +192,7 @@ impl NodeMetaCanvasV1 {
                     name: String::from("Canvas"),
                     uuid: uuid::Uuid::parse_str("a795c3e9-0a2f-48bf-a9e2-03378e8e59b8").unwrap(),
                     library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
-                    version: 1,
+                    version: VersionSpec::Exact(1),
                 },
             },
             Box::new(process),
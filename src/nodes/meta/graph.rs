@@ -4,11 +4,33 @@ use axiom::actors::*;
 use crate::catalogue::*;
 use crate::graph::*;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+/// The datum key that holds how many seconds a node within a graph can go unused before it is stopped.
+/// Zero keeps them running for as long as the graph is.
+/// Graphs within a graph take this from it, unless they have their own.
+pub const IDLE_TIMEOUT_DATUM: &str = "idle_timeout";
+
+/// How many seconds a node within a graph can go unused before it is stopped, when the graph does not say.
+pub const DEFAULT_IDLE_TIMEOUT: f64 = 60.0;
+
+///
+/// Commands a graph node sends to itself.
+///
+#[derive(Serialize, Deserialize)]
+pub enum GraphCommand {
+    /// Stops the nodes within the graph that have not been used for longer than the idle timeout.
+    StopIdleNodes,
+}
+
+use serde::{Deserialize, Serialize};
 
 ///
 /// Represents a user created graph that is loaded from a library.
-/// When launched, this will ask the controller to spawn the appropriate nodes for whichever output or recieve that is activated.
+/// When launched, this will spawn the appropriate nodes for whichever output or recieve that is activated.
+/// Only the nodes needed for it are spawned, tracing backwards through the connections, and they are stopped again once they sit idle.
+/// The graph's pins are connected to the nodes within it through boundary pin references, see `GRAPH_BOUNDARY_NODE`.
 ///
 #[derive(Default)]
 pub struct NodeMetaGraphV1 {
@@ -18,6 +40,14 @@ pub struct NodeMetaGraphV1 {
     pub instance: Option<VersionInfo>,
     /// Actively loaded and running nodes that belong to this graph, keyed by their instance UUID within the graph file.
     pub nodes: HashMap<uuid::Uuid, Aid>,
+    /// Outputs of this graph that are waiting on the output of a node within it, keyed by that node's instance UUID and output pin.
+    pub waiting: HashMap<(uuid::Uuid, uuid::Uuid), Vec<uuid::Uuid>>,
+    /// When each running node within the graph was last needed.
+    pub last_used: HashMap<uuid::Uuid, Instant>,
+    /// Whether a check for idle nodes is already scheduled.
+    pub stopping_idle: bool,
+    /// Whether the graph was refreshed while computing, so its nodes are stopped once nothing is waiting on them.
+    pub refresh_pending: bool,
 }
 
 use axiom::prelude::*;
@@ -52,22 +82,326 @@ impl Nodeable for NodeMetaGraphV1 {
     }
     fn compute_output(
         &mut self,
-        _node: &mut Node,
-        _output_info: PinInfo,
-        _context: &Context,
-        _parameter: &Option<Message>,
+        node: &mut Node,
+        output_info: PinInfo,
+        context: &Context,
+        parameter: &Option<Message>,
     ) -> Result<Option<Message>, String> {
-        todo!()
-        // TODO: Load the graph version into memory
+        let instance = match self.instance.clone() {
+            Some(instance) => instance,
+            None => return Err(String::from("graph does not have a loaded version")),
+        };
+        let source = match instance.output_for_input(&PinRef::new_boundary(output_info.uuid)) {
+            Some(source) => source.clone(),
+            None => return Err(format!("graph output {} is not connected to anything", output_info.name)),
+        };
+        let source_pin = match source.pin {
+            Some(source_pin) => source_pin,
+            None => return Err(format!("graph output {} is not connected to a pin", output_info.name)),
+        };
+        // Graph inputs that are connected straight to graph outputs pass through.
+        if source.is_boundary() {
+            return Ok(node.get_input_message(&source_pin));
+        }
+        self.demand(node, context, &instance, source.node, &mut HashSet::new())?;
+        let actor = match self.nodes.get(&source.node) {
+            Some(actor) => actor,
+            None => return Err(format!("graph output {} is connected to node {} which is not in the graph", output_info.name, source.node)),
+        };
+        if let Err(e) = actor.send_new(NodeCommand::ComputeOutput(context.aid.clone(), source_pin, parameter.clone())) {
+            return Err(format!("could not ask node {} for the value of graph output {}: {}", source.node, output_info.name, e.to_string()));
+        }
+        self.waiting.entry((source.node, source_pin)).or_insert_with(Vec::new).push(output_info.uuid);
+        node.defer();
+        Ok(None)
     }
     fn handle_receive(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        message: &Option<axiom::message::Message>,
+    ) {
+        // Nodes within the graph send to the graph's own send pins, and those messages leave the graph.
+        if node.sends.contains_key(receiver) {
+            node.emit(context, receiver, message.clone());
+            return;
+        }
+        let instance = match self.instance.clone() {
+            Some(instance) => instance,
+            None => {
+                error!("graph node actor {:?} does not have a loaded version to receive with", &context.aid);
+                return;
+            }
+        };
+        for target in instance.receives_for_send(&PinRef::new_boundary(*receiver)) {
+            if !target.is_boundary() {
+                if let Err(e) = self.demand(node, context, &instance, target.node, &mut HashSet::new()) {
+                    error!("graph node actor {:?} could not launch its nodes: {}", &context.aid, e);
+                    node.publish(context, EngineEventType::Error(e));
+                    continue;
+                }
+            }
+            match (target.is_boundary(), self.nodes.get(&target.node), target.pin) {
+                (true, _, Some(send)) => node.emit(context, &send, message.clone()),
+                (false, Some(actor), Some(target_pin)) => {
+                    if let Err(e) = actor.send_new(NodeCommand::ReceiverMessage(context.aid.clone(), target_pin, message.clone())) {
+                        error!("graph node actor {:?} could not forward receive pin {} to node {}: {}", &context.aid, receiver, target.node, e.to_string());
+                    }
+                }
+                _ => error!("graph node actor {:?} receive pin {} is connected to a missing pin on node {}", &context.aid, receiver, target.node),
+            }
+        }
+    }
+    fn handle_message(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+        message: &Message,
+    ) {
+        if let Some(response) = message.content_as::<NodeResponse>() {
+            let (responder, pin, value) = match &*response {
+                NodeResponse::OutputPinValue(responder, pin, value) => (responder, pin, Ok(value.clone())),
+                NodeResponse::OutputPinError(responder, pin, reason) => (responder, pin, Err(reason.clone())),
+                _ => return,
+            };
+            let inner = self.nodes.iter().find(|(_, actor)| *actor == responder).map(|(inner, _)| *inner);
+            match inner.and_then(|inner| self.waiting.remove(&(inner, *pin)).map(|outputs| (inner, outputs))) {
+                Some((inner, outputs)) => {
+                    for output in outputs {
+                        let value = value.clone().map_err(|reason| format!("graph output {} failed in node {}: {}", output, inner, reason));
+                        node.resolve(context, &output, value);
+                    }
+                }
+                None => warn!("bad logic: graph node actor {:?} received the result of pin {} from node actor {:?} without asking for it", &context.aid, pin, responder),
+            }
+            if self.refresh_pending && self.waiting.is_empty() {
+                self.refresh_pending = false;
+                self.handle_stop(node, context);
+            }
+        } else if let Some(command) = message.content_as::<GraphCommand>() {
+            match &*command {
+                GraphCommand::StopIdleNodes => {
+                    self.stopping_idle = false;
+                    self.stop_idle(node, context);
+                }
+            }
+        }
+    }
+    fn handle_stop(
         &mut self,
         _node: &mut Node,
-        _context: &Context,
-        _receiver: &uuid::Uuid,
-        _message: &Option<axiom::message::Message>,
+        context: &Context,
     ) {
-        todo!()
+        for (_, actor) in self.nodes.drain() {
+            let _ = actor.send_new(NodeCommand::Stop(context.aid.clone()));
+        }
+        self.last_used.clear();
+    }
+    fn handle_refresh(
+        &mut self,
+        node: &mut Node,
+        context: &Context,
+    ) {
+        let graph = match &self.graph {
+            Some(graph) => graph.clone(),
+            None => return,
+        };
+        let version = node.catalogue.lock().unwrap().get_graph_version(&graph);
+        match version {
+            Some(version) => self.instance = Some(version),
+            None => {
+                error!("graph {} : {} version {} could not be read again", graph.uuid, graph.name, graph.version);
+                return;
+            }
+        }
+        // Nodes within the graph are spawned again from the new version when next needed.
+        // Those still computing are stopped once they are done.
+        if self.waiting.is_empty() {
+            self.handle_stop(node, context);
+        } else {
+            info!("graph {} : {} is still computing, so its nodes are stopped once it is done", graph.uuid, graph.name);
+            self.refresh_pending = true;
+        }
+    }
+}
+
+use crate::engine::ControllerCommand;
+use crate::event::*;
+
+impl NodeMetaGraphV1 {
+    ///
+    /// Makes sure a node within the graph is running, along with every node it needs.
+    /// Those are the nodes its inputs are connected to, traced backwards, and the nodes its sends are connected to.
+    /// Each of them counts as used, and a check for idle nodes is scheduled.
+    ///
+    fn demand(&mut self, node: &Node, context: &Context, instance: &VersionInfo, inner: uuid::Uuid, visited: &mut HashSet<uuid::Uuid>) -> Result<(), String> {
+        if !visited.insert(inner) {
+            return Ok(());
+        }
+        self.last_used.insert(inner, Instant::now());
+        let spawned = match self.nodes.contains_key(&inner) {
+            true => false,
+            false => {
+                self.spawn(node, context, instance, inner)?;
+                true
+            }
+        };
+        let mut needed = Vec::new();
+        for connection in instance.connections.iter() {
+            if let (Some(output), Some(input)) = (&connection.output, &connection.input) {
+                if input.node == inner && !output.is_boundary() {
+                    needed.push(output.node);
+                }
+            }
+            if let (Some(sends), Some(receives)) = (&connection.sends, &connection.receives) {
+                if sends.node == inner && !receives.is_boundary() {
+                    needed.push(receives.node);
+                }
+            }
+        }
+        for needed in needed {
+            self.demand(node, context, instance, needed, visited)?;
+        }
+        // Links go to actors, so they are made once everything this node needs is running.
+        if spawned {
+            for connection in instance.connections.iter() {
+                if let (Some(output), Some(input)) = (&connection.output, &connection.input) {
+                    if input.node == inner {
+                        self.link(context, input, output)?;
+                    }
+                }
+                if let (Some(sends), Some(receives)) = (&connection.sends, &connection.receives) {
+                    if sends.node == inner {
+                        self.link(context, sends, receives)?;
+                    }
+                }
+            }
+        }
+        if let (Some(timeout), false) = (idle_timeout(node), self.stopping_idle) {
+            self.stopping_idle = context.aid.send_new_after(GraphCommand::StopIdleNodes, timeout).is_ok();
+        }
+        Ok(())
+    }
+    ///
+    /// Spawns the actor for a node within the graph.
+    ///
+    fn spawn(&mut self, node: &Node, context: &Context, instance: &VersionInfo, inner: uuid::Uuid) -> Result<(), String> {
+        let info = match instance.nodes.iter().find(|info| info.uuid == inner) {
+            Some(info) => info,
+            None => return Err(format!("node {} is not in the graph", inner)),
+        };
+        let library = self.graph.as_ref().and_then(|graph| graph.library);
+        let mut inner_node = match instantiate(node.controller.clone(), node.catalogue.clone(), &info.graph, library, info.uuid) {
+            Some(inner_node) => inner_node,
+            None => return Err(format!("could not create node {} of graph {}", info.uuid, info.graph.name)),
+        };
+        inner_node.info.data = info.data.clone();
+        let seed = instance.seed.unwrap_or_else(|| node.seed());
+        inner_node
+            .info
+            .data
+            .entry(String::from(crate::seed::SEED_DATUM))
+            .or_insert_with(|| serde_json::json!(crate::seed::node_seed(Some(seed), &info.uuid)));
+        if let Some(timeout) = node.info.data.get(IDLE_TIMEOUT_DATUM) {
+            inner_node.info.data.entry(String::from(IDLE_TIMEOUT_DATUM)).or_insert_with(|| timeout.clone());
+        }
+        if let Some(graph) = &self.graph {
+            inner_node.assets = crate::asset::AssetContext::for_graph(&node.catalogue.lock().unwrap(), graph);
+        }
+        inner_node.parent = Some(node.info.graph.uuid);
+        inner_node.tracer = node.tracer.clone();
+        inner_node.metrics = node.metrics.clone();
+        inner_node.messages = node.messages.clone();
+        inner_node.subscribers = node.subscribers.clone();
+        let inner_graph = inner_node.info.graph.clone();
+        match context.system.spawn().with(inner_node, Node::handle) {
+            Ok(actor) => {
+                info!("graph node actor {:?} spawned node {} : {}", &context.aid, info.uuid, inner_graph.name);
+                let _ = node.controller.send_new(ControllerCommand::PublishEvent(EngineEvent {
+                    graph: Some(inner_graph),
+                    instance: Some(info.uuid),
+                    actor: Some(actor.clone()),
+                    event: EngineEventType::NodeBooted,
+                }));
+                self.nodes.insert(info.uuid, actor);
+                Ok(())
+            }
+            Err(e) => Err(format!("could not spawn node {} of graph {}: {:?}", info.uuid, info.graph.name, e)),
+        }
+    }
+    ///
+    /// Stops the nodes within the graph that have not been used for longer than the idle timeout.
+    /// A node is never used less recently than the nodes that need it, so nothing that is still running links to a stopped node.
+    /// Nothing is stopped while the graph is waiting on one of its outputs.
+    ///
+    fn stop_idle(&mut self, node: &Node, context: &Context) {
+        let timeout = match idle_timeout(node) {
+            Some(timeout) => timeout,
+            None => return,
+        };
+        let now = Instant::now();
+        let idle: Vec<uuid::Uuid> = match self.waiting.is_empty() {
+            true => self
+                .last_used
+                .iter()
+                .filter(|(_, used)| now.duration_since(**used) >= timeout)
+                .map(|(inner, _)| *inner)
+                .collect(),
+            false => Vec::new(),
+        };
+        for inner in idle {
+            self.last_used.remove(&inner);
+            if let Some(actor) = self.nodes.remove(&inner) {
+                info!("graph node actor {:?} stopping idle node {}", &context.aid, inner);
+                let _ = actor.send_new(NodeCommand::Stop(context.aid.clone()));
+            }
+        }
+        // Check again when the next node would become idle, or a whole timeout later while waiting.
+        if let Some(oldest) = self.last_used.values().min() {
+            let remaining = match self.waiting.is_empty() {
+                true => timeout.checked_sub(now.duration_since(*oldest)).unwrap_or_default(),
+                false => timeout,
+            };
+            self.stopping_idle = context.aid.send_new_after(GraphCommand::StopIdleNodes, remaining).is_ok();
+        }
+    }
+    ///
+    /// Links the input or send pin of a node within the graph to its output or receive pin.
+    /// Boundary pins link to this graph node's own pins.
+    ///
+    fn link(&self, context: &Context, pin: &PinRef, target: &PinRef) -> Result<(), String> {
+        let (actor, local_pin) = match (self.nodes.get(&pin.node), pin.pin) {
+            (Some(actor), Some(local_pin)) => (actor, local_pin),
+            _ => return Err(format!("connection from missing pin on node {}", pin.node)),
+        };
+        let target_actor = match target.is_boundary() {
+            true => context.aid.clone(),
+            false => match self.nodes.get(&target.node) {
+                Some(target_actor) => target_actor.clone(),
+                None => return Err(format!("connection to node {} which is not in the graph", target.node)),
+            },
+        };
+        match actor.send_new(NodeCommand::LinkPin(context.aid.clone(), local_pin, target_actor, target.clone())) {
+            Ok(()) => Ok(()),
+            Err(e) => Err(format!("could not link pin {} of node {}: {}", local_pin, pin.node, e.to_string())),
+        }
+    }
+}
+
+///
+/// How long nodes within a graph can go unused before they are stopped, if they ever are.
+///
+fn idle_timeout(node: &Node) -> Option<Duration> {
+    let seconds = node
+        .info
+        .data
+        .get(IDLE_TIMEOUT_DATUM)
+        .and_then(|timeout| timeout.as_f64())
+        .unwrap_or(DEFAULT_IDLE_TIMEOUT);
+    match seconds > 0.0 {
+        true => Some(Duration::from_secs_f64(seconds)),
+        false => None,
     }
 }
 
@@ -75,7 +409,7 @@ use std::sync::{Arc, Mutex};
 
 impl NodeMetaGraphV1 {
     pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
-        let process = Self { graph: None, instance: None, nodes: HashMap::new() };
+        let process = Self::default();
         Node::new(
             NodeInstanceInfo {
                 uuid: instance_id,
@@ -84,7 +418,7 @@ impl NodeMetaGraphV1 {
                     name: String::from("Graph"),
                     uuid: uuid::Uuid::parse_str("25351e69-098b-4330-9317-37436b03d427").unwrap(),
                     library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
-                    version: 1,
+                    version: VersionSpec::Exact(1),
                 },
             },
             Box::new(process),
@@ -92,6 +426,65 @@ impl NodeMetaGraphV1 {
             controller,
         )
     }
+    ///
+    /// Creates a node for an instance of a user graph, from one of its versions.
+    ///
+    pub fn from_version(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        graph: GraphRef,
+        version: VersionInfo,
+        instance_id: uuid::Uuid,
+    ) -> Node {
+        let process = Self {
+            graph: Some(graph.clone()),
+            instance: Some(version),
+            ..Self::default()
+        };
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph,
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+///
+/// Creates a node for an instance of any graph in the catalogue, whether it is an internal node or a user graph.
+/// The library is the one holding the graph that the reference comes from, if any, so that a blank library in the reference means that one.
+///
+pub fn instantiate(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    graph: &GraphRef,
+    library: Option<uuid::Uuid>,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    let internal_lib = uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").unwrap();
+    let (graph_ref, version) = {
+        let cat = catalogue.lock().unwrap();
+        let graph_ref = match cat.resolve_graph_ref_from(graph, library) {
+            Ok(graph_ref) => graph_ref,
+            Err(e) => {
+                error!("could not resolve graph {} : {} version {}: {}", graph.uuid, graph.name, graph.version, e);
+                return None;
+            }
+        };
+        let version = match graph_ref.library == Some(internal_lib) {
+            true => None,
+            false => Some(cat.get_graph_version(&graph_ref)?),
+        };
+        (graph_ref, version)
+    };
+    match version {
+        None => crate::nodes::create(controller, catalogue, graph_ref.uuid, graph_ref.version.exact()?, instance_id),
+        Some(version) => Some(NodeMetaGraphV1::from_version(controller, catalogue, graph_ref, version, instance_id)),
+    }
 }
 
 /// Registers the internal nodes as available graphs to a catalogue.
@@ -1,15 +1,33 @@
-//pub mod math;
-//pub mod string;
+pub mod audio;
+pub mod color;
+pub mod flow;
+pub mod geometry;
+pub mod image;
+pub mod math;
+pub mod string;
 pub mod meta;
+pub mod noise;
+pub mod sdf;
 pub mod util;
+pub mod voxel;
 
 use crate::graph::*;
 
 /// Registers the internal nodes as available graphs to a catalogue.
 pub fn register() -> Vec<(GraphInfo, u64)> {
     let mut nodes = Vec::new();
+    nodes.append(&mut audio::register());
+    nodes.append(&mut color::register());
+    nodes.append(&mut flow::register());
+    nodes.append(&mut geometry::register());
+    nodes.append(&mut image::register());
+    nodes.append(&mut math::register());
     nodes.append(&mut meta::register());
+    nodes.append(&mut noise::register());
+    nodes.append(&mut sdf::register());
+    nodes.append(&mut string::register());
     nodes.append(&mut util::register());
+    nodes.append(&mut voxel::register());
     nodes
 }
 
@@ -27,6 +45,115 @@ pub fn create(
     instance_id: uuid::Uuid,
 ) -> Option<Node> {
     None
+    .or(audio::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(color::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(flow::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(geometry::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(image::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(math::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
     .or(meta::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(noise::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(sdf::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(string::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
     .or(util::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(voxel::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+///
+/// Makes an input pin for an internal node.
+/// Expandable pins take any number of values, for operations like adding many numbers together.
+///
+pub fn input_pin(name: &str, id: &str, datatype: &str, expandable: bool) -> Pin {
+    Pin::new_io_basic(PinInfo::new_extended(
+        String::from(name),
+        uuid::Uuid::parse_str(id).unwrap(),
+        String::from(datatype),
+        Some(true),
+        None,
+        if expandable { Some(true) } else { None },
+    ))
+}
+
+///
+/// Makes an output pin for an internal node.
+///
+pub fn output_pin(name: &str, id: &str, datatype: &str) -> Pin {
+    Pin::new_io_basic(PinInfo::new_basic(
+        String::from(name),
+        uuid::Uuid::parse_str(id).unwrap(),
+        String::from(datatype),
+    ))
+}
+
+///
+/// Makes a receive pin for an internal node, which reacts to messages sent to it.
+///
+pub fn receive_pin(name: &str, id: &str, datatype: &str) -> Pin {
+    Pin::new_rs_basic(PinInfo::new_basic(
+        String::from(name),
+        uuid::Uuid::parse_str(id).unwrap(),
+        String::from(datatype),
+    ))
+}
+
+///
+/// Makes a send pin for an internal node, which passes messages on to the receive pins linked to it.
+///
+pub fn send_pin(name: &str, id: &str, datatype: &str) -> Pin {
+    Pin::new_rs_basic(PinInfo::new_basic(
+        String::from(name),
+        uuid::Uuid::parse_str(id).unwrap(),
+        String::from(datatype),
+    ))
+}
+
+///
+/// Creates the node instance information for an internal node that is one of a table of operations.
+///
+pub fn instance_info(name: &str, graph: &str, instance_id: uuid::Uuid) -> NodeInstanceInfo {
+    NodeInstanceInfo {
+        uuid: instance_id,
+        data: std::collections::HashMap::new(),
+        graph: GraphRef {
+            name: String::from(name),
+            uuid: uuid::Uuid::parse_str(graph).unwrap(),
+            library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+            version: VersionSpec::Exact(1),
+        },
+    }
+}
+
+///
+/// Gives back the registration of every node in a table of operations.
+///
+pub fn register_operations<T>(operations: &[(&str, &str, T)]) -> Vec<(GraphInfo, u64)> {
+    operations
+        .iter()
+        .map(|(name, id, _)| {
+            (
+                GraphInfo {
+                    name: String::from(*name),
+                    uuid: uuid::Uuid::parse_str(id).unwrap(),
+                    format: 1,
+                },
+                1,
+            )
+        })
+        .collect()
+}
+
+///
+/// Finds the operation in a table that a graph UUID and version refer to.
+///
+pub fn find_operation<'a, T>(
+    operations: &'a [(&'a str, &'a str, T)],
+    uuid: uuid::Uuid,
+    version: u64,
+) -> Option<&'a (&'a str, &'a str, T)> {
+    match version {
+        1 => operations
+            .iter()
+            .find(|(_, id, _)| uuid::Uuid::parse_str(id).unwrap() == uuid),
+        _ => None,
+    }
 }
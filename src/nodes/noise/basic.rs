@@ -0,0 +1,92 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use crate::nodes::*;
+use axiom::prelude::*;
+use bracket_noise::prelude::*;
+
+/// The name, graph UUID and noise type of every basic noise node.
+pub const OPERATIONS: [(&str, &str, NoiseType); 3] = [
+    ("Perlin Noise", "c885023c-f6bf-4f2a-a676-6dbffa926c67", NoiseType::Perlin),
+    ("Simplex Noise", "a1afe089-61e9-4869-8d45-a5078a2b64df", NoiseType::Simplex),
+    ("Value Noise", "e535702f-4418-4805-b069-bd81cc36f6dc", NoiseType::Value),
+];
+
+///
+/// Generates gradient Perlin, Simplex or interpolated value noise, which need no settings beyond the shared noise inputs.
+///
+pub struct NodeNoiseBasicV1 {
+    pub noise_type: NoiseType,
+}
+
+impl Nodeable for NodeNoiseBasicV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        super::noise_io()
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        let noise = super::create_noise(node, self.noise_type);
+        super::compute_noise_output(node, &noise, &output_info)
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeNoiseBasicV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        noise_type: NoiseType,
+    ) -> Node {
+        let process = Self { noise_type };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, noise_type)| {
+        NodeNoiseBasicV1::new(controller, catalogue, instance_id, name, graph, *noise_type)
+    })
+}
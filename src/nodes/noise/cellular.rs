@@ -0,0 +1,148 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use axiom::prelude::*;
+use bracket_noise::prelude::*;
+
+///
+/// Generates cellular, also known as Worley, noise.
+/// Distance function is one of euclidean, manhattan or natural.
+/// Return type is one of cell, distance, distance2, distance2add, distance2sub, distance2mul or distance2div.
+///
+#[derive(Default)]
+pub struct NodeNoiseCellularV1 {}
+
+impl Nodeable for NodeNoiseCellularV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let (mut inputs, outputs) = super::noise_io();
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Distance Function"),
+            uuid::Uuid::parse_str("2704ee6d-6c6a-4313-8c1e-a812db74777e").unwrap(),
+            String::from("string"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Return Type"),
+            uuid::Uuid::parse_str("f24c1f3b-e37c-48e6-81bb-3e5310a46690").unwrap(),
+            String::from("string"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Jitter"),
+            uuid::Uuid::parse_str("91b0e02e-2c88-4a88-811e-0e6aee598ecd").unwrap(),
+            String::from("f64"),
+            Some(true),
+            None,
+            None,
+        )));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        let mut noise = super::create_noise(node, NoiseType::Cellular);
+        let distance_function = node
+            .get_input::<String>(&uuid::Uuid::parse_str("2704ee6d-6c6a-4313-8c1e-a812db74777e").unwrap())
+            .unwrap_or_else(|| String::from("euclidean"));
+        noise.set_cellular_distance_function(match distance_function.to_lowercase().as_str() {
+            "euclidean" => CellularDistanceFunction::Euclidean,
+            "manhattan" => CellularDistanceFunction::Manhattan,
+            "natural" => CellularDistanceFunction::Natural,
+            other => return Err(format!("unknown cellular distance function {}", other)),
+        });
+        let return_type = node
+            .get_input::<String>(&uuid::Uuid::parse_str("f24c1f3b-e37c-48e6-81bb-3e5310a46690").unwrap())
+            .unwrap_or_else(|| String::from("distance"));
+        noise.set_cellular_return_type(match return_type.to_lowercase().as_str() {
+            "cell" => CellularReturnType::CellValue,
+            "distance" => CellularReturnType::Distance,
+            "distance2" => CellularReturnType::Distance2,
+            "distance2add" => CellularReturnType::Distance2Add,
+            "distance2sub" => CellularReturnType::Distance2Sub,
+            "distance2mul" => CellularReturnType::Distance2Mul,
+            "distance2div" => CellularReturnType::Distance2Div,
+            other => return Err(format!("unknown cellular return type {}", other)),
+        });
+        if let Some(jitter) = node.get_input::<f64>(&uuid::Uuid::parse_str("91b0e02e-2c88-4a88-811e-0e6aee598ecd").unwrap()) {
+            noise.set_cellular_jitter(jitter as f32);
+        }
+        super::compute_noise_output(node, &noise, &output_info)
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeNoiseCellularV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Cellular Noise"),
+                    uuid: uuid::Uuid::parse_str("01116d3e-cf93-40bc-bb86-1db278647c01").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Cellular Noise"),
+            uuid: uuid::Uuid::parse_str("01116d3e-cf93-40bc-bb86-1db278647c01").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("01116d3e-cf93-40bc-bb86-1db278647c01").unwrap() {
+        return match version {
+            1 => Some(NodeNoiseCellularV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
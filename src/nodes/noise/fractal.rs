@@ -0,0 +1,169 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use axiom::prelude::*;
+use bracket_noise::prelude::*;
+
+///
+/// Generates layered fractal noise out of several octaves of a base noise.
+/// Base is one of perlin, simplex, value or cubic.
+/// Fractal type is one of fbm, billow or ridged.
+///
+#[derive(Default)]
+pub struct NodeNoiseFractalV1 {}
+
+impl Nodeable for NodeNoiseFractalV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let (mut inputs, outputs) = super::noise_io();
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Base"),
+            uuid::Uuid::parse_str("fada2fb9-d8b6-41c7-a605-386f1bf7036b").unwrap(),
+            String::from("string"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Fractal Type"),
+            uuid::Uuid::parse_str("9a6b469d-912c-48d6-a41c-2655362a0cb2").unwrap(),
+            String::from("string"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Octaves"),
+            uuid::Uuid::parse_str("7f66fee9-01ee-49af-b762-dfe92cf80bb9").unwrap(),
+            String::from("u64"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Lacunarity"),
+            uuid::Uuid::parse_str("c352248a-be04-43e1-9e80-97af50bd9e44").unwrap(),
+            String::from("f64"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Gain"),
+            uuid::Uuid::parse_str("0c7bb3c4-e01a-4ef4-a529-e955acf0de60").unwrap(),
+            String::from("f64"),
+            Some(true),
+            None,
+            None,
+        )));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        let base = node
+            .get_input::<String>(&uuid::Uuid::parse_str("fada2fb9-d8b6-41c7-a605-386f1bf7036b").unwrap())
+            .unwrap_or_else(|| String::from("perlin"));
+        let mut noise = super::create_noise(node, match base.to_lowercase().as_str() {
+            "perlin" => NoiseType::PerlinFractal,
+            "simplex" => NoiseType::SimplexFractal,
+            "value" => NoiseType::ValueFractal,
+            "cubic" => NoiseType::CubicFractal,
+            other => return Err(format!("unknown fractal base noise {}", other)),
+        });
+        let fractal_type = node
+            .get_input::<String>(&uuid::Uuid::parse_str("9a6b469d-912c-48d6-a41c-2655362a0cb2").unwrap())
+            .unwrap_or_else(|| String::from("fbm"));
+        noise.set_fractal_type(match fractal_type.to_lowercase().as_str() {
+            "fbm" => FractalType::FBM,
+            "billow" => FractalType::Billow,
+            "ridged" => FractalType::RigidMulti,
+            other => return Err(format!("unknown fractal type {}", other)),
+        });
+        let octaves = node
+            .get_input::<u64>(&uuid::Uuid::parse_str("7f66fee9-01ee-49af-b762-dfe92cf80bb9").unwrap())
+            .unwrap_or(5);
+        noise.set_fractal_octaves(octaves as i32);
+        let lacunarity = node
+            .get_input::<f64>(&uuid::Uuid::parse_str("c352248a-be04-43e1-9e80-97af50bd9e44").unwrap())
+            .unwrap_or(2.0);
+        noise.set_fractal_lacunarity(lacunarity as f32);
+        let gain = node
+            .get_input::<f64>(&uuid::Uuid::parse_str("0c7bb3c4-e01a-4ef4-a529-e955acf0de60").unwrap())
+            .unwrap_or(0.5);
+        noise.set_fractal_gain(gain as f32);
+        super::compute_noise_output(node, &noise, &output_info)
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeNoiseFractalV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Fractal Noise"),
+                    uuid: uuid::Uuid::parse_str("c9693e01-0c9c-4428-97fc-c2cff76ac94c").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Fractal Noise"),
+            uuid: uuid::Uuid::parse_str("c9693e01-0c9c-4428-97fc-c2cff76ac94c").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("c9693e01-0c9c-4428-97fc-c2cff76ac94c").unwrap() {
+        return match version {
+            1 => Some(NodeNoiseFractalV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,214 @@
+pub mod basic;
+pub mod cellular;
+pub mod fractal;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut basic::register());
+    nodes.push(cellular::register());
+    nodes.push(fractal::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(basic::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(cellular::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(fractal::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use bracket_noise::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The most values a noise grid can hold, which is 64 MiB of samples.
+pub const MAX_GRID_VALUES: usize = 1 << 24;
+
+///
+/// Gives the number of values in a grid of the given size, or an error if it would be more than `MAX_GRID_VALUES`.
+///
+pub fn grid_size(width: u64, height: u64, depth: u64) -> Result<usize, String> {
+    match width.checked_mul(height).and_then(|size| size.checked_mul(depth)) {
+        Some(size) if size <= MAX_GRID_VALUES as u64 => Ok(size as usize),
+        _ => Err(format!(
+            "noise grid of {} by {} by {} is larger than the most of {} values",
+            width, height, depth, MAX_GRID_VALUES
+        )),
+    }
+}
+
+///
+/// A block of noise values sampled on a regular grid.
+/// 2D grids have a depth of one.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct NoiseGrid {
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    /// Values ordered by x, then y, then z.
+    pub values: Vec<f32>,
+}
+
+impl NoiseGrid {
+    pub fn index(&self, x: usize, y: usize, z: usize) -> usize {
+        x + y * self.width + z * self.width * self.height
+    }
+    pub fn get(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.values[self.index(x, y, z)]
+    }
+}
+
+///
+/// Gets the input and output pins that every noise node has.
+/// Seed falls back to the node's own seed when it is not set.
+///
+pub fn noise_io() -> (Vec<Pin>, Vec<Pin>) {
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let valuable = |name: &str, id: &str, datatype: &str| {
+        Pin::new_io_basic(PinInfo::new_extended(
+            String::from(name),
+            uuid::Uuid::parse_str(id).unwrap(),
+            String::from(datatype),
+            Some(true),
+            None,
+            None,
+        ))
+    };
+    inputs.push(valuable("Seed", "47168f6c-d51b-4b46-a8b1-9d4437b852d2", "u64"));
+    inputs.push(valuable("Frequency", "728dbd03-9961-464b-aa1d-43b82f388ed8", "f64"));
+    inputs.push(valuable("X", "5d71612c-f6f8-4abf-8802-6137dddf2309", "f64"));
+    inputs.push(valuable("Y", "b657531c-e39f-4543-8500-a88ce700fcb0", "f64"));
+    inputs.push(valuable("Z", "e9aeef1a-d270-4823-937e-11a2a4c943e2", "f64"));
+    inputs.push(valuable("Width", "3d1174f1-cc27-47b6-91c1-e1dcc81d06a0", "u64"));
+    inputs.push(valuable("Height", "126bc9cb-96df-4bfa-b449-549ba9a669a7", "u64"));
+    inputs.push(valuable("Depth", "45323e5e-0158-4034-8a80-e20326b35c7f", "u64"));
+    inputs.push(valuable("Spacing", "0002e28a-f5f7-425e-ad36-55fbf00c600b", "f64"));
+    outputs.push(Pin::new_io_basic(PinInfo::new_basic(
+        String::from("Value"),
+        uuid::Uuid::parse_str("39d13f7b-5d2b-4fdf-93da-19956e9a9411").unwrap(),
+        String::from("f64"),
+    )));
+    outputs.push(Pin::new_io_basic(PinInfo::new_basic(
+        String::from("Grid 2D"),
+        uuid::Uuid::parse_str("9d837344-3bbb-4cdf-b8be-b307fcf428eb").unwrap(),
+        String::from("noise_grid"),
+    )));
+    outputs.push(Pin::new_io_basic(PinInfo::new_basic(
+        String::from("Grid 3D"),
+        uuid::Uuid::parse_str("e3e4eeff-a1c3-4344-a4bf-7ed8c8f0e32b").unwrap(),
+        String::from("noise_grid"),
+    )));
+    (inputs, outputs)
+}
+
+///
+/// Creates a noise generator of the given type, using the seed and frequency inputs of the node.
+///
+pub fn create_noise(node: &Node, noise_type: NoiseType) -> FastNoise {
+    let seed = node
+        .get_input::<u64>(&uuid::Uuid::parse_str("47168f6c-d51b-4b46-a8b1-9d4437b852d2").unwrap())
+        .unwrap_or(node.seed());
+    let frequency = node
+        .get_input::<f64>(&uuid::Uuid::parse_str("728dbd03-9961-464b-aa1d-43b82f388ed8").unwrap())
+        .unwrap_or(0.01);
+    let mut noise = FastNoise::seeded(seed);
+    noise.set_noise_type(noise_type);
+    noise.set_frequency(frequency as f32);
+    noise
+}
+
+///
+/// Computes one of the shared noise outputs: a single value at a coordinate, or a 2D or 3D grid starting at that coordinate.
+///
+pub fn compute_noise_output(
+    node: &Node,
+    noise: &FastNoise,
+    output_info: &PinInfo,
+) -> Result<Option<Message>, String> {
+    let get_f64 = |id: &str, default: f64| {
+        node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+            .unwrap_or(default)
+    };
+    let get_u64 = |id: &str, default: u64| {
+        node.get_input::<u64>(&uuid::Uuid::parse_str(id).unwrap())
+            .unwrap_or(default)
+    };
+    let x = get_f64("5d71612c-f6f8-4abf-8802-6137dddf2309", 0.0);
+    let y = get_f64("b657531c-e39f-4543-8500-a88ce700fcb0", 0.0);
+    let z = get_f64("e9aeef1a-d270-4823-937e-11a2a4c943e2", 0.0);
+    let width = get_u64("3d1174f1-cc27-47b6-91c1-e1dcc81d06a0", 64);
+    let height = get_u64("126bc9cb-96df-4bfa-b449-549ba9a669a7", 64);
+    let spacing = get_f64("0002e28a-f5f7-425e-ad36-55fbf00c600b", 1.0);
+    match output_info.uuid {
+        id_value
+            if id_value
+                == uuid::Uuid::parse_str("39d13f7b-5d2b-4fdf-93da-19956e9a9411").unwrap() =>
+        {
+            let value = noise.get_noise3d(x as f32, y as f32, z as f32);
+            Ok(Some(Message::new(value as f64)))
+        }
+        id_grid_2d
+            if id_grid_2d
+                == uuid::Uuid::parse_str("9d837344-3bbb-4cdf-b8be-b307fcf428eb").unwrap() =>
+        {
+            let mut values = Vec::with_capacity(grid_size(width, height, 1)?);
+            let (width, height) = (width as usize, height as usize);
+            for j in 0..height {
+                for i in 0..width {
+                    values.push(noise.get_noise(
+                        (x + i as f64 * spacing) as f32,
+                        (y + j as f64 * spacing) as f32,
+                    ));
+                }
+            }
+            Ok(Some(Message::new(NoiseGrid {
+                width,
+                height,
+                depth: 1,
+                values,
+            })))
+        }
+        id_grid_3d
+            if id_grid_3d
+                == uuid::Uuid::parse_str("e3e4eeff-a1c3-4344-a4bf-7ed8c8f0e32b").unwrap() =>
+        {
+            let depth = get_u64("45323e5e-0158-4034-8a80-e20326b35c7f", 64);
+            let mut values = Vec::with_capacity(grid_size(width, height, depth)?);
+            let (width, height, depth) = (width as usize, height as usize, depth as usize);
+            for k in 0..depth {
+                for j in 0..height {
+                    for i in 0..width {
+                        values.push(noise.get_noise3d(
+                            (x + i as f64 * spacing) as f32,
+                            (y + j as f64 * spacing) as f32,
+                            (z + k as f64 * spacing) as f32,
+                        ));
+                    }
+                }
+            }
+            Ok(Some(Message::new(NoiseGrid {
+                width,
+                height,
+                depth,
+                values,
+            })))
+        }
+        _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+    }
+}
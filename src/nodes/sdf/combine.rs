@@ -0,0 +1,165 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The ways two or more fields can be joined into one.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CombineOp {
+    Union,
+    Subtract,
+    Intersect,
+    SmoothUnion,
+    SmoothSubtract,
+    SmoothIntersect,
+}
+
+/// The name, graph UUID and operation of every field combining node.
+pub const OPERATIONS: [(&str, &str, CombineOp); 6] = [
+    ("SDF Union", "ca641b65-aa6a-4fa5-8caa-a12328c56a06", CombineOp::Union),
+    ("SDF Subtract", "f5a0b329-2b0a-4847-b6ea-9e52542fcbba", CombineOp::Subtract),
+    ("SDF Intersect", "9b12019c-31c5-4834-b1f8-ac510b908017", CombineOp::Intersect),
+    ("SDF Smooth Union", "28108ea4-9fed-4658-abd4-54f38a2a76a1", CombineOp::SmoothUnion),
+    ("SDF Smooth Subtract", "7197afc2-d50e-4198-9012-c65720ed323b", CombineOp::SmoothSubtract),
+    ("SDF Smooth Intersect", "bad07851-3a7f-4f9c-b835-ea0b3effdda6", CombineOp::SmoothIntersect),
+];
+
+const SHAPES: &str = "63ce80c3-2b5d-4bda-b6f4-dbf0fbcf8e99";
+const SHAPE: &str = "602f6652-c3c4-4576-9dbe-d21b51c8a0c0";
+const CUTTER: &str = "25073c42-1e1e-426a-b5a4-273034a69bf2";
+const SMOOTHNESS: &str = "60957a46-014a-439a-b669-c7994527edf1";
+const OUTPUT_SDF: &str = "0a06cfbd-db93-4e56-8504-ce0597fad21d";
+
+///
+/// Joins fields together, cuts one field out of another, or keeps only where fields overlap.
+/// The smooth versions blend the surfaces together over the smoothness distance.
+///
+pub struct NodeSdfCombineV1 {
+    pub op: CombineOp,
+}
+
+impl Nodeable for NodeSdfCombineV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            CombineOp::Subtract | CombineOp::SmoothSubtract => {
+                inputs.push(input_pin("Shape", SHAPE, "sdf", false));
+                inputs.push(input_pin("Cutter", CUTTER, "sdf", false));
+            }
+            _ => inputs.push(input_pin("Shapes", SHAPES, "sdf", true)),
+        }
+        match self.op {
+            CombineOp::SmoothUnion | CombineOp::SmoothSubtract | CombineOp::SmoothIntersect => {
+                inputs.push(input_pin("Smoothness", SMOOTHNESS, "f64", false))
+            }
+            _ => {}
+        }
+        outputs.push(output_pin("Shape", OUTPUT_SDF, "sdf"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_SDF).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let k = node
+            .get_input::<f64>(&uuid::Uuid::parse_str(SMOOTHNESS).unwrap())
+            .unwrap_or(0.1) as f32;
+        let sdf = match self.op {
+            CombineOp::Subtract => Sdf::Subtract(
+                Box::new(sdf_input(node, SHAPE)?),
+                Box::new(sdf_input(node, CUTTER)?),
+            ),
+            CombineOp::SmoothSubtract => Sdf::SmoothSubtract(
+                Box::new(sdf_input(node, SHAPE)?),
+                Box::new(sdf_input(node, CUTTER)?),
+                k,
+            ),
+            op => {
+                let mut shapes = node
+                    .get_inputs::<Sdf>(&uuid::Uuid::parse_str(SHAPES).unwrap())
+                    .into_iter();
+                let first = match shapes.next() {
+                    Some(first) => first,
+                    None => return Err(String::from("no shapes to combine")),
+                };
+                shapes.fold(first, |combined, shape| {
+                    let (a, b) = (Box::new(combined), Box::new(shape));
+                    match op {
+                        CombineOp::Union => Sdf::Union(a, b),
+                        CombineOp::Intersect => Sdf::Intersect(a, b),
+                        CombineOp::SmoothUnion => Sdf::SmoothUnion(a, b, k),
+                        _ => Sdf::SmoothIntersect(a, b, k),
+                    }
+                })
+            }
+        };
+        Ok(Some(Message::new(sdf)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeSdfCombineV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: CombineOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeSdfCombineV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,147 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use nalgebra::{Matrix4, Vector3};
+
+///
+/// The operations that change the space a field is evaluated in.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DomainOp {
+    Repeat,
+    Twist,
+    Bend,
+    Transform,
+}
+
+/// The name, graph UUID and operation of every field domain node.
+pub const OPERATIONS: [(&str, &str, DomainOp); 4] = [
+    ("SDF Repeat", "107f048b-0a34-4bf6-b33e-1290828fc38b", DomainOp::Repeat),
+    ("SDF Twist", "2934efc6-39e2-4d32-af07-5f6281b0ed81", DomainOp::Twist),
+    ("SDF Bend", "096aa250-075f-4334-8165-8b5412e3c0e2", DomainOp::Bend),
+    ("SDF Transform", "c1c3d68e-0f46-4a7a-b4fa-0967e232bdce", DomainOp::Transform),
+];
+
+const SHAPE: &str = "ff4d31d3-d8b5-4d99-a96e-bcf4287076b3";
+const PERIOD: &str = "a9b4fc6d-1f86-40d6-90e7-5d150bfb889f";
+const RATE: &str = "1bffa39d-21a0-4c03-b473-016b4209ba0e";
+const MATRIX: &str = "35bb6d18-8202-4f3c-a499-81f4f6bbefba";
+const OUTPUT_SDF: &str = "c9c71235-fcda-40d9-be7c-0c214c07699a";
+
+///
+/// Repeats a field along a grid, twists it around the Y axis, bends it around the Z axis, or moves it by a transform.
+/// Repeating is only exact when the shape fits inside one period.
+///
+pub struct NodeSdfDomainV1 {
+    pub op: DomainOp,
+}
+
+impl Nodeable for NodeSdfDomainV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Shape", SHAPE, "sdf", false));
+        match self.op {
+            DomainOp::Repeat => inputs.push(input_pin("Period", PERIOD, "vec3", false)),
+            DomainOp::Twist | DomainOp::Bend => inputs.push(input_pin("Rate", RATE, "f64", false)),
+            DomainOp::Transform => inputs.push(input_pin("Matrix", MATRIX, "mat4", false)),
+        }
+        outputs.push(output_pin("Shape", OUTPUT_SDF, "sdf"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_SDF).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let sdf = Box::new(sdf_input(node, SHAPE)?);
+        let rate = node
+            .get_input::<f64>(&uuid::Uuid::parse_str(RATE).unwrap())
+            .unwrap_or(1.0) as f32;
+        let shape = match self.op {
+            DomainOp::Repeat => {
+                let period = node
+                    .get_input::<Vector3<f64>>(&uuid::Uuid::parse_str(PERIOD).unwrap())
+                    .unwrap_or_else(|| Vector3::new(2.0, 2.0, 2.0));
+                Sdf::Repeat {
+                    sdf,
+                    period: [period.x as f32, period.y as f32, period.z as f32],
+                }
+            }
+            DomainOp::Twist => Sdf::Twist { sdf, rate },
+            DomainOp::Bend => Sdf::Bend { sdf, rate },
+            DomainOp::Transform => {
+                let matrix = node
+                    .get_input::<Matrix4<f64>>(&uuid::Uuid::parse_str(MATRIX).unwrap())
+                    .unwrap_or_else(Matrix4::identity);
+                sdf.transformed(&matrix)?
+            }
+        };
+        Ok(Some(Message::new(shape)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeSdfDomainV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: DomainOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeSdfDomainV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
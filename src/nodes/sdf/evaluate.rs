@@ -0,0 +1,149 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use crate::nodes::voxel::{surface::surface_nets, volume_input};
+use nalgebra::Vector3;
+
+///
+/// The ways a field can be turned into concrete values.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EvaluateOp {
+    Distance,
+    Sample,
+    Polygonize,
+}
+
+/// The name, graph UUID and operation of every field evaluating node.
+pub const OPERATIONS: [(&str, &str, EvaluateOp); 3] = [
+    ("SDF Distance", "b4a735ba-f65c-41e2-ae0c-db6a92835517", EvaluateOp::Distance),
+    ("Sample SDF", "04a90259-dd3a-478a-85ca-2c90d204beef", EvaluateOp::Sample),
+    ("SDF To Mesh", "11b1a638-22e3-4066-9094-727b9b971b28", EvaluateOp::Polygonize),
+];
+
+const SHAPE: &str = "7c7fe74c-5ca1-47c3-a2ee-7e7540aff3d4";
+const POINT: &str = "d7d677da-706f-4739-a0aa-8b9d73decb46";
+const VOLUME: &str = "1f97bc46-0827-4ec3-8ff5-efab0f5238a5";
+const OUTPUT_DISTANCE: &str = "2efc7b04-f01c-40a3-9dd0-b85f12628327";
+const OUTPUT_VOLUME: &str = "38fe1f0f-46ea-4e02-bc10-00c6c1def5b6";
+const OUTPUT_MESH: &str = "9471d210-1ca1-4281-9d5a-0250d28cc9b1";
+
+///
+/// Measures the distance from a point to a field, samples a field onto the grid of a volume, or turns it into a mesh.
+/// The volume given for sampling and meshing only sets the size, spacing and storage, and its contents are replaced.
+/// Meshes are built with surface nets, the same as the volume to mesh node.
+///
+pub struct NodeSdfEvaluateV1 {
+    pub op: EvaluateOp,
+}
+
+impl Nodeable for NodeSdfEvaluateV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Shape", SHAPE, "sdf", false));
+        match self.op {
+            EvaluateOp::Distance => {
+                inputs.push(input_pin("Point", POINT, "vec3", false));
+                outputs.push(output_pin("Distance", OUTPUT_DISTANCE, "f64"));
+            }
+            EvaluateOp::Sample => {
+                inputs.push(input_pin("Volume", VOLUME, "volume", false));
+                outputs.push(output_pin("Volume", OUTPUT_VOLUME, "volume"));
+            }
+            EvaluateOp::Polygonize => {
+                inputs.push(input_pin("Volume", VOLUME, "volume", false));
+                outputs.push(output_pin("Mesh", OUTPUT_MESH, "mesh"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let sdf = sdf_input(node, SHAPE)?;
+        match self.op {
+            EvaluateOp::Distance => {
+                let point = node
+                    .get_input::<Vector3<f64>>(&uuid::Uuid::parse_str(POINT).unwrap())
+                    .unwrap_or_else(Vector3::zeros);
+                Ok(Some(Message::new(sdf.distance(point.map(|value| value as f32)) as f64)))
+            }
+            EvaluateOp::Sample => {
+                let volume = volume_input(node, VOLUME)?.fill(|point| sdf.distance(Vector3::from(point)));
+                Ok(Some(Message::new(volume)))
+            }
+            EvaluateOp::Polygonize => {
+                let volume = volume_input(node, VOLUME)?.fill(|point| sdf.distance(Vector3::from(point)));
+                Ok(Some(Message::new(surface_nets(&volume))))
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeSdfEvaluateV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: EvaluateOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeSdfEvaluateV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,183 @@
+pub mod combine;
+pub mod domain;
+pub mod evaluate;
+pub mod primitive;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut primitive::register());
+    nodes.append(&mut combine::register());
+    nodes.append(&mut domain::register());
+    nodes.append(&mut evaluate::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(primitive::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(combine::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(domain::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(evaluate::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use nalgebra::{Matrix4, Point3, Vector2, Vector3};
+use serde::{Deserialize, Serialize};
+
+///
+/// A signed distance field, carried on pins with the "sdf" datatype.
+/// Each node wraps the fields it is given, so the value is a tree that describes the whole shape and is evaluated on demand.
+/// Distances are negative inside. Smooth combinations and the twist and bend operations only estimate the distance.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Sdf {
+    Sphere {
+        radius: f32,
+    },
+    Box {
+        half_size: [f32; 3],
+    },
+    Capsule {
+        start: [f32; 3],
+        end: [f32; 3],
+        radius: f32,
+    },
+    /// A ring lying on the XZ plane.
+    Torus {
+        major_radius: f32,
+        minor_radius: f32,
+    },
+    Union(Box<Sdf>, Box<Sdf>),
+    /// The first field with the second cut out of it.
+    Subtract(Box<Sdf>, Box<Sdf>),
+    Intersect(Box<Sdf>, Box<Sdf>),
+    SmoothUnion(Box<Sdf>, Box<Sdf>, f32),
+    SmoothSubtract(Box<Sdf>, Box<Sdf>, f32),
+    SmoothIntersect(Box<Sdf>, Box<Sdf>, f32),
+    /// Repeats the field forever along every axis with a period above zero.
+    Repeat {
+        sdf: Box<Sdf>,
+        period: [f32; 3],
+    },
+    /// Twists the field around the Y axis by the rate in radians per unit of height.
+    Twist {
+        sdf: Box<Sdf>,
+        rate: f32,
+    },
+    /// Bends the field around the Z axis by the rate in radians per unit along X.
+    Bend {
+        sdf: Box<Sdf>,
+        rate: f32,
+    },
+    /// Moves the field by a transform, keeping its inverse to bring points back into the field's own space.
+    Transform {
+        sdf: Box<Sdf>,
+        inverse: Matrix4<f64>,
+        /// The smallest scale of the transform, which keeps distances from being overestimated.
+        scale: f32,
+    },
+}
+
+impl Sdf {
+    ///
+    /// Wraps a field in a transform.
+    ///
+    pub fn transformed(self, transform: &Matrix4<f64>) -> Result<Self, String> {
+        let inverse = match transform.try_inverse() {
+            Some(inverse) => inverse,
+            None => return Err(String::from("cannot transform a field by a matrix with no inverse")),
+        };
+        let scale = (0..3)
+            .map(|column| transform.fixed_slice::<nalgebra::U3, nalgebra::U1>(0, column).norm())
+            .fold(std::f64::MAX, f64::min);
+        Ok(Sdf::Transform {
+            sdf: Box::new(self),
+            inverse,
+            scale: scale as f32,
+        })
+    }
+    ///
+    /// Gets the signed distance from a point to the surface of the field.
+    ///
+    pub fn distance(&self, point: Vector3<f32>) -> f32 {
+        match self {
+            Sdf::Sphere { radius } => point.norm() - radius,
+            Sdf::Box { half_size } => {
+                crate::nodes::voxel::fill::box_distance(point, Vector3::zeros(), Vector3::from(*half_size))
+            }
+            Sdf::Capsule { start, end, radius } => {
+                let (start, end) = (Vector3::from(*start), Vector3::from(*end));
+                let (along, axis) = (point - start, end - start);
+                let length = axis.norm_squared();
+                let t = if length == 0.0 { 0.0 } else { (along.dot(&axis) / length).max(0.0).min(1.0) };
+                (along - axis * t).norm() - radius
+            }
+            Sdf::Torus { major_radius, minor_radius } => {
+                Vector2::new(Vector2::new(point.x, point.z).norm() - major_radius, point.y).norm() - minor_radius
+            }
+            Sdf::Union(a, b) => a.distance(point).min(b.distance(point)),
+            Sdf::Subtract(a, b) => a.distance(point).max(-b.distance(point)),
+            Sdf::Intersect(a, b) => a.distance(point).max(b.distance(point)),
+            Sdf::SmoothUnion(a, b, k) => smooth_min(a.distance(point), b.distance(point), *k),
+            Sdf::SmoothSubtract(a, b, k) => -smooth_min(-a.distance(point), b.distance(point), *k),
+            Sdf::SmoothIntersect(a, b, k) => -smooth_min(-a.distance(point), -b.distance(point), *k),
+            Sdf::Repeat { sdf, period } => {
+                let mut local = point;
+                for axis in 0..3 {
+                    if period[axis] > 0.0 {
+                        local[axis] -= period[axis] * (point[axis] / period[axis]).round();
+                    }
+                }
+                sdf.distance(local)
+            }
+            Sdf::Twist { sdf, rate } => {
+                let (sin, cos) = (rate * point.y).sin_cos();
+                sdf.distance(Vector3::new(cos * point.x - sin * point.z, point.y, sin * point.x + cos * point.z))
+            }
+            Sdf::Bend { sdf, rate } => {
+                let (sin, cos) = (rate * point.x).sin_cos();
+                sdf.distance(Vector3::new(cos * point.x - sin * point.y, sin * point.x + cos * point.y, point.z))
+            }
+            Sdf::Transform { sdf, inverse, scale } => {
+                let local = inverse.transform_point(&Point3::new(point.x as f64, point.y as f64, point.z as f64));
+                sdf.distance(Vector3::new(local.x as f32, local.y as f32, local.z as f32)) * scale
+            }
+        }
+    }
+}
+
+///
+/// Gives back the smaller of two distances, blended over a distance of k so the shapes melt together.
+/// A k of zero or less is the plain minimum.
+///
+pub fn smooth_min(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+    let h = (0.5 + 0.5 * (b - a) / k).max(0.0).min(1.0);
+    b + (a - b) * h - k * h * (1.0 - h)
+}
+
+///
+/// Gets the field on an input pin, which is an error if there is none.
+///
+pub fn sdf_input(node: &Node, id: &str) -> Result<Sdf, String> {
+    match node.get_input::<Sdf>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(sdf) => Ok(sdf),
+        None => Err(format!("no signed distance field on input pin {}", id)),
+    }
+}
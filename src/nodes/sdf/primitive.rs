@@ -0,0 +1,163 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use nalgebra::Vector3;
+
+///
+/// The shapes that signed distance fields start from.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PrimitiveOp {
+    Sphere,
+    Box,
+    Capsule,
+    Torus,
+}
+
+/// The name, graph UUID and operation of every field primitive node.
+pub const OPERATIONS: [(&str, &str, PrimitiveOp); 4] = [
+    ("SDF Sphere", "434838cb-1738-4b46-804b-b04d76c92172", PrimitiveOp::Sphere),
+    ("SDF Box", "3a88ff27-b7c1-4c19-8bcf-5103a35c8125", PrimitiveOp::Box),
+    ("SDF Capsule", "1829238d-5238-428a-bbc2-89306fa5019d", PrimitiveOp::Capsule),
+    ("SDF Torus", "7dee113a-08b3-441c-baa2-213b0356871d", PrimitiveOp::Torus),
+];
+
+const RADIUS: &str = "05528a33-e05b-4249-a2b9-e82226a8e99b";
+const SIZE: &str = "42fd66f6-6df3-4126-9552-c8745f03eb6b";
+const START: &str = "37c03dae-b4d7-49ff-af52-b1c9058a6781";
+const END: &str = "6a2ca10f-d6a3-4565-b15b-8cfeb17b1f30";
+const MAJOR_RADIUS: &str = "e99e0404-66a9-4776-9a84-c5948f5271a4";
+const MINOR_RADIUS: &str = "838e2469-3223-4351-80f4-034b02b99e50";
+const OUTPUT_SDF: &str = "8b77cd5f-7754-4b2f-b88d-abc80236d026";
+
+///
+/// Creates a signed distance field of a primitive shape centered on the origin.
+/// Move shapes into place with the transform node.
+///
+pub struct NodeSdfPrimitiveV1 {
+    pub op: PrimitiveOp,
+}
+
+impl Nodeable for NodeSdfPrimitiveV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            PrimitiveOp::Sphere => inputs.push(input_pin("Radius", RADIUS, "f64", false)),
+            PrimitiveOp::Box => inputs.push(input_pin("Size", SIZE, "vec3", false)),
+            PrimitiveOp::Capsule => {
+                inputs.push(input_pin("Start", START, "vec3", false));
+                inputs.push(input_pin("End", END, "vec3", false));
+                inputs.push(input_pin("Radius", RADIUS, "f64", false));
+            }
+            PrimitiveOp::Torus => {
+                inputs.push(input_pin("Major Radius", MAJOR_RADIUS, "f64", false));
+                inputs.push(input_pin("Minor Radius", MINOR_RADIUS, "f64", false));
+            }
+        }
+        outputs.push(output_pin("Shape", OUTPUT_SDF, "sdf"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_SDF).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default) as f32
+        };
+        let get_vec3 = |id: &str, default: Vector3<f64>| {
+            let value = node
+                .get_input::<Vector3<f64>>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default);
+            [value.x as f32, value.y as f32, value.z as f32]
+        };
+        let sdf = match self.op {
+            PrimitiveOp::Sphere => Sdf::Sphere {
+                radius: get_f64(RADIUS, 0.5),
+            },
+            PrimitiveOp::Box => {
+                let size = get_vec3(SIZE, Vector3::new(1.0, 1.0, 1.0));
+                Sdf::Box {
+                    half_size: [size[0] / 2.0, size[1] / 2.0, size[2] / 2.0],
+                }
+            }
+            PrimitiveOp::Capsule => Sdf::Capsule {
+                start: get_vec3(START, Vector3::new(0.0, -0.5, 0.0)),
+                end: get_vec3(END, Vector3::new(0.0, 0.5, 0.0)),
+                radius: get_f64(RADIUS, 0.25),
+            },
+            PrimitiveOp::Torus => Sdf::Torus {
+                major_radius: get_f64(MAJOR_RADIUS, 0.5),
+                minor_radius: get_f64(MINOR_RADIUS, 0.125),
+            },
+        };
+        Ok(Some(Message::new(sdf)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeSdfPrimitiveV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: PrimitiveOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeSdfPrimitiveV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,108 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Joins any number of strings together, with an optional separator between each of them.
+///
+#[derive(Default)]
+pub struct NodeStringConcatV1 {}
+
+impl Nodeable for NodeStringConcatV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Strings", "70fac27a-5700-41d7-8249-6cb4d7f8c56b", "string", true));
+        inputs.push(input_pin("Separator", "bac4fb74-ebb6-4021-85a8-7a125bb14320", "string", false));
+        outputs.push(output_pin("Text", "51d5f7bf-e75c-4516-812f-c4ed4e4966e2", "string"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_text if id_text == uuid::Uuid::parse_str("51d5f7bf-e75c-4516-812f-c4ed4e4966e2").unwrap() => {
+                let strings = node.get_inputs::<String>(&uuid::Uuid::parse_str("70fac27a-5700-41d7-8249-6cb4d7f8c56b").unwrap());
+                let separator = string_input(node, "bac4fb74-ebb6-4021-85a8-7a125bb14320");
+                Ok(Some(Message::new(strings.join(&separator))))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeStringConcatV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Concatenate"),
+                    uuid: uuid::Uuid::parse_str("d5c58e38-f8a1-449c-9a5a-9b93a2bc800d").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Concatenate"),
+            uuid: uuid::Uuid::parse_str("d5c58e38-f8a1-449c-9a5a-9b93a2bc800d").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("d5c58e38-f8a1-449c-9a5a-9b93a2bc800d").unwrap() {
+        return match version {
+            1 => Some(NodeStringConcatV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
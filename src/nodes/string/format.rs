@@ -0,0 +1,162 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Fills in the named placeholders of a template, such as "{name}_{index}.png".
+/// Values come from the values map first, then from any string datums on the node.
+/// Doubled braces give back a literal brace.
+///
+#[derive(Default)]
+pub struct NodeStringFormatV1 {}
+
+impl Nodeable for NodeStringFormatV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Template", "9822a136-b466-4339-80ba-9c3ec9beb3d5", "string", false));
+        inputs.push(input_pin("Values", "e142b037-539e-4040-898f-b9fc8bd98eb7", "string_map", false));
+        outputs.push(output_pin("Text", "f3fb5878-1b83-46f3-bf2f-f768d3866897", "string"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_text if id_text == uuid::Uuid::parse_str("f3fb5878-1b83-46f3-bf2f-f768d3866897").unwrap() => {
+                let template = string_input(node, "9822a136-b466-4339-80ba-9c3ec9beb3d5");
+                let values = node
+                    .get_input::<HashMap<String, String>>(&uuid::Uuid::parse_str("e142b037-539e-4040-898f-b9fc8bd98eb7").unwrap())
+                    .unwrap_or_default();
+                let text = format_template(&template, |name| match values.get(name) {
+                    Some(value) => Some(value.clone()),
+                    None => match node.info.data.get(name) {
+                        Some(serde_json::Value::String(value)) => Some(value.clone()),
+                        Some(value) => Some(value.to_string()),
+                        None => None,
+                    },
+                })?;
+                Ok(Some(Message::new(text)))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+///
+/// Replaces each "{name}" in a template with the value the lookup gives for that name.
+/// "{{" and "}}" give back literal braces.
+///
+pub fn format_template<F>(template: &str, lookup: F) -> Result<String, String>
+where
+    F: Fn(&str) -> Option<String>,
+{
+    let mut text = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                text.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                text.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(format!("unclosed placeholder {{{} in template", name)),
+                    }
+                }
+                match lookup(name.trim()) {
+                    Some(value) => text.push_str(&value),
+                    None => return Err(format!("no value for placeholder {} in template", name.trim())),
+                }
+            }
+            '}' => return Err(String::from("unmatched closing brace in template")),
+            c => text.push(c),
+        }
+    }
+    Ok(text)
+}
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+impl NodeStringFormatV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Format"),
+                    uuid: uuid::Uuid::parse_str("fa93acee-efc6-4eb3-a34b-ee1fe5c9051c").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Format"),
+            uuid: uuid::Uuid::parse_str("fa93acee-efc6-4eb3-a34b-ee1fe5c9051c").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("fa93acee-efc6-4eb3-a34b-ee1fe5c9051c").unwrap() {
+        return match version {
+            1 => Some(NodeStringFormatV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,64 @@
+pub mod concat;
+pub mod format;
+pub mod parse;
+pub mod pattern;
+pub mod split;
+pub mod transform;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.push(concat::register());
+    nodes.push(format::register());
+    nodes.push(parse::register());
+    nodes.append(&mut pattern::register());
+    nodes.append(&mut split::register());
+    nodes.append(&mut transform::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(concat::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(format::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(parse::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(pattern::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(split::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(transform::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+///
+/// Makes a pin that carries a list of strings, which is sent as a vector of strings.
+///
+pub fn string_list_pin(name: &str, id: &str) -> Pin {
+    Pin::new_io_basic(PinInfo::new_extended(
+        String::from(name),
+        uuid::Uuid::parse_str(id).unwrap(),
+        String::from("string"),
+        Some(true),
+        Some(1),
+        None,
+    ))
+}
+
+///
+/// Gets the value of a string input, or an empty string if it is not set.
+///
+pub fn string_input(node: &Node, id: &str) -> String {
+    node.get_input::<String>(&uuid::Uuid::parse_str(id).unwrap())
+        .unwrap_or_default()
+}
@@ -0,0 +1,113 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Reads a number out of a string, ignoring surrounding whitespace.
+///
+#[derive(Default)]
+pub struct NodeStringParseV1 {}
+
+impl Nodeable for NodeStringParseV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Text", "a50c9f5c-a54f-4b6d-84b6-edfffa2b0a5e", "string", false));
+        outputs.push(output_pin("Value", "967ddffd-f9ca-4aa1-9554-44431e790401", "f64"));
+        outputs.push(output_pin("Integer", "29887913-7acf-4da9-a6e5-e01ae60880cf", "i64"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        let text = string_input(node, "a50c9f5c-a54f-4b6d-84b6-edfffa2b0a5e");
+        let text = text.trim();
+        match output_info.uuid {
+            id_value if id_value == uuid::Uuid::parse_str("967ddffd-f9ca-4aa1-9554-44431e790401").unwrap() => match text.parse::<f64>() {
+                Ok(value) => Ok(Some(Message::new(value))),
+                Err(e) => Err(format!("could not parse {:?} as a number: {}", text, e)),
+            },
+            id_integer if id_integer == uuid::Uuid::parse_str("29887913-7acf-4da9-a6e5-e01ae60880cf").unwrap() => match text.parse::<i64>() {
+                Ok(value) => Ok(Some(Message::new(value))),
+                Err(e) => Err(format!("could not parse {:?} as an integer: {}", text, e)),
+            },
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeStringParseV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Parse Number"),
+                    uuid: uuid::Uuid::parse_str("36646738-b7c9-4dab-93a2-fea7abd03238").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Parse Number"),
+            uuid: uuid::Uuid::parse_str("36646738-b7c9-4dab-93a2-fea7abd03238").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("36646738-b7c9-4dab-93a2-fea7abd03238").unwrap() {
+        return match version {
+            1 => Some(NodeStringParseV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
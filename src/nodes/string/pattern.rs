@@ -0,0 +1,161 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+use regex::Regex;
+
+///
+/// The things the pattern nodes do with a regular expression.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PatternOp {
+    Match,
+    Capture,
+    Replace,
+}
+
+/// The name, graph UUID and operation of every pattern node.
+pub const OPERATIONS: [(&str, &str, PatternOp); 3] = [
+    ("Regex Match", "d9565e17-6b4a-4bb6-b055-a9938c630429", PatternOp::Match),
+    ("Regex Capture", "50949dde-1d64-413b-a5be-1d6bbc4f01c4", PatternOp::Capture),
+    ("Regex Replace", "ed767fd6-8940-420c-a965-236e77d7623b", PatternOp::Replace),
+];
+
+const TEXT: &str = "b27c3fef-1a41-4c8b-950d-8a958d2b5f46";
+const PATTERN: &str = "4a4f02e5-b7e7-4419-8114-00bdbf22f106";
+const REPLACEMENT: &str = "a3754195-5c5f-4451-b4e2-6b6f737b6c84";
+const OUTPUT_MATCHED: &str = "bca4db0e-908d-43ab-8306-4d07eb9771e1";
+const OUTPUT_GROUPS: &str = "3d8a171e-1541-47a2-a8ab-0296ea27f8ff";
+const OUTPUT_TEXT: &str = "f529f42e-cab7-4cfb-9836-021f38847df6";
+
+///
+/// Matches text against a regular expression, using the syntax of the regex crate.
+/// Capturing gives back every group of the first match, with the whole match first and unmatched groups left empty.
+/// Replacing replaces every match, and the replacement can refer to groups such as "$1" or "$name".
+///
+pub struct NodeStringPatternV1 {
+    pub op: PatternOp,
+    /// The last pattern that was compiled, so it is not compiled again for every compute.
+    pub compiled: Option<Regex>,
+}
+
+impl NodeStringPatternV1 {
+    fn regex(&mut self, pattern: &str) -> Result<&Regex, String> {
+        let stale = match &self.compiled {
+            Some(regex) => regex.as_str() != pattern,
+            None => true,
+        };
+        if stale {
+            self.compiled = Some(
+                Regex::new(pattern).map_err(|e| format!("invalid pattern {:?}: {}", pattern, e))?,
+            );
+        }
+        Ok(self.compiled.as_ref().unwrap())
+    }
+}
+
+impl Nodeable for NodeStringPatternV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Text", TEXT, "string", false));
+        inputs.push(input_pin("Pattern", PATTERN, "string", false));
+        match self.op {
+            PatternOp::Match => outputs.push(output_pin("Matched", OUTPUT_MATCHED, "bool")),
+            PatternOp::Capture => outputs.push(string_list_pin("Groups", OUTPUT_GROUPS)),
+            PatternOp::Replace => {
+                inputs.push(input_pin("Replacement", REPLACEMENT, "string", false));
+                outputs.push(output_pin("Text", OUTPUT_TEXT, "string"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let text = string_input(node, TEXT);
+        let replacement = string_input(node, REPLACEMENT);
+        let op = self.op;
+        let regex = self.regex(&string_input(node, PATTERN))?;
+        let message = match op {
+            PatternOp::Match => Message::new(regex.is_match(&text)),
+            PatternOp::Capture => {
+                let groups: Vec<String> = match regex.captures(&text) {
+                    Some(captures) => captures
+                        .iter()
+                        .map(|group| group.map_or(String::new(), |group| String::from(group.as_str())))
+                        .collect(),
+                    None => Vec::new(),
+                };
+                Message::new(groups)
+            }
+            PatternOp::Replace => Message::new(regex.replace_all(&text, replacement.as_str()).into_owned()),
+        };
+        Ok(Some(message))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeStringPatternV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: PatternOp,
+    ) -> Node {
+        let process = Self { op, compiled: None };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeStringPatternV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
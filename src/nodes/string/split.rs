@@ -0,0 +1,140 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// Whether a string is being broken into parts or parts are being put back together.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SplitOp {
+    Split,
+    Join,
+}
+
+/// The name, graph UUID and operation of every split node.
+pub const OPERATIONS: [(&str, &str, SplitOp); 2] = [
+    ("Split", "5751e69b-a402-417c-aded-9049b5c485d4", SplitOp::Split),
+    ("Join", "cf0d7adf-2c27-4120-b3da-8d217a54df1c", SplitOp::Join),
+];
+
+const TEXT: &str = "9963fd8a-ddda-462d-8c2f-1590bbc7c74e";
+const PARTS: &str = "99d762a9-219e-482a-8d6a-7b2a7de00886";
+const SEPARATOR: &str = "3a1dd26b-d276-4cfc-a8f9-3ba7c80760c6";
+const OUTPUT_TEXT: &str = "cd448943-d5c4-45b7-9f84-2e730c7830d4";
+const OUTPUT_PARTS: &str = "02c1a68e-d789-4d92-865b-afb67889942c";
+
+///
+/// Splits a string into a list of strings at each separator, or joins a list of strings with a separator.
+/// Splitting with an empty separator splits on whitespace.
+///
+pub struct NodeStringSplitV1 {
+    pub op: SplitOp,
+}
+
+impl Nodeable for NodeStringSplitV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            SplitOp::Split => {
+                inputs.push(input_pin("Text", TEXT, "string", false));
+                inputs.push(input_pin("Separator", SEPARATOR, "string", false));
+                outputs.push(string_list_pin("Parts", OUTPUT_PARTS));
+            }
+            SplitOp::Join => {
+                inputs.push(string_list_pin("Parts", PARTS));
+                inputs.push(input_pin("Separator", SEPARATOR, "string", false));
+                outputs.push(output_pin("Text", OUTPUT_TEXT, "string"));
+            }
+        }
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if !node.outputs.contains_key(&output_info.uuid) {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let separator = string_input(node, SEPARATOR);
+        match self.op {
+            SplitOp::Split => {
+                let text = string_input(node, TEXT);
+                let parts: Vec<String> = if separator.is_empty() {
+                    text.split_whitespace().map(String::from).collect()
+                } else {
+                    text.split(separator.as_str()).map(String::from).collect()
+                };
+                Ok(Some(Message::new(parts)))
+            }
+            SplitOp::Join => {
+                let parts = node
+                    .get_input::<Vec<String>>(&uuid::Uuid::parse_str(PARTS).unwrap())
+                    .unwrap_or_default();
+                Ok(Some(Message::new(parts.join(&separator))))
+            }
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeStringSplitV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: SplitOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeStringSplitV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
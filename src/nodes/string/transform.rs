@@ -0,0 +1,198 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The ways the transform nodes change a single string.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TransformOp {
+    Uppercase,
+    Lowercase,
+    TitleCase,
+    SnakeCase,
+    KebabCase,
+    CamelCase,
+    PascalCase,
+    Trim,
+    TrimStart,
+    TrimEnd,
+}
+
+/// The name, graph UUID and operation of every transform node.
+pub const OPERATIONS: [(&str, &str, TransformOp); 10] = [
+    ("Uppercase", "374459c7-51c6-4abf-8cb5-077b2dfa1ab4", TransformOp::Uppercase),
+    ("Lowercase", "2cef9a75-417e-46c4-a735-6b07dea641de", TransformOp::Lowercase),
+    ("Title Case", "21316675-562c-4def-82ea-02a09d08eb14", TransformOp::TitleCase),
+    ("Snake Case", "b709e515-31b7-4288-9a8a-dd8d991e2707", TransformOp::SnakeCase),
+    ("Kebab Case", "afc1ba80-aa1c-4847-bf1f-86e122f1fc86", TransformOp::KebabCase),
+    ("Camel Case", "f862b233-2f41-4737-a7e7-1daebb82573d", TransformOp::CamelCase),
+    ("Pascal Case", "eecc225e-31d0-473d-b38b-8af637c0c407", TransformOp::PascalCase),
+    ("Trim", "f74738ea-4cf6-498f-8432-24021401fe6a", TransformOp::Trim),
+    ("Trim Start", "8d834d1c-fcb0-4e7d-9dee-d59e31899ea5", TransformOp::TrimStart),
+    ("Trim End", "cecb1e06-f6f1-4d54-9581-fd9be6f2faf5", TransformOp::TrimEnd),
+];
+
+const TEXT: &str = "de5cac9e-337c-4078-81df-e7e67ea83a6c";
+const OUTPUT_TEXT: &str = "f052ffbf-d947-4253-ae47-dd325cf9abb3";
+
+impl TransformOp {
+    /// Applies the transform to some text.
+    pub fn apply(&self, text: &str) -> String {
+        match self {
+            TransformOp::Uppercase => text.to_uppercase(),
+            TransformOp::Lowercase => text.to_lowercase(),
+            TransformOp::TitleCase => text
+                .split(' ')
+                .map(capitalize)
+                .collect::<Vec<String>>()
+                .join(" "),
+            TransformOp::SnakeCase => words(text)
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<String>>()
+                .join("_"),
+            TransformOp::KebabCase => words(text)
+                .iter()
+                .map(|word| word.to_lowercase())
+                .collect::<Vec<String>>()
+                .join("-"),
+            TransformOp::CamelCase => words(text)
+                .iter()
+                .enumerate()
+                .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+                .collect(),
+            TransformOp::PascalCase => words(text).iter().map(|word| capitalize(word)).collect(),
+            TransformOp::Trim => String::from(text.trim()),
+            TransformOp::TrimStart => String::from(text.trim_start()),
+            TransformOp::TrimEnd => String::from(text.trim_end()),
+        }
+    }
+}
+
+/// Uppercases the first letter of a word and lowercases the rest.
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
+
+///
+/// Breaks text into words for case conversion.
+/// Words end at anything that is not a letter or digit, and at changes of case, so "loadHTTPServer2" is "load", "HTTP" and "Server2".
+///
+fn words(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::replace(&mut word, String::new()));
+            }
+            continue;
+        }
+        if c.is_uppercase() && !word.is_empty() {
+            let previous = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_numeric() || (previous.is_uppercase() && next_is_lowercase) {
+                words.push(std::mem::replace(&mut word, String::new()));
+            }
+        }
+        word.push(*c);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+///
+/// Changes the case of a string, or trims whitespace from it.
+///
+pub struct NodeStringTransformV1 {
+    pub op: TransformOp,
+}
+
+impl Nodeable for NodeStringTransformV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Text", TEXT, "string", false));
+        outputs.push(output_pin("Text", OUTPUT_TEXT, "string"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_TEXT).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        Ok(Some(Message::new(self.op.apply(&string_input(node, TEXT)))))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeStringTransformV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: TransformOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeStringTransformV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -85,6 +85,11 @@ impl Nodeable for NodeUtilLogV1 {
             uuid::Uuid::parse_str("ab04b49d-ff65-44c6-a70b-8546ecdbc5ba").unwrap(),
             String::from("string"),
         )));
+        sends.push(Pin::new_rs_basic(PinInfo::new_basic(
+            String::from("Pass Through"),
+            uuid::Uuid::parse_str("76e93dc1-50d7-4686-8b5e-07019f4597df").unwrap(),
+            String::from("string"),
+        )));
         (recvs, sends)
     }
 
@@ -158,12 +163,43 @@ impl Nodeable for NodeUtilLogV1 {
     }
     fn handle_receive(
         &mut self,
-        _node: &mut Node,
-        _context: &Context,
-        _receiver: &uuid::Uuid,
-        _message: &Option<Message>,
+        node: &mut Node,
+        context: &Context,
+        receiver: &uuid::Uuid,
+        message: &Option<Message>,
     ) {
-        todo!()
+        let text = match message {
+            Some(message) => match message.content_as::<String>() {
+                Some(text) => (*text).clone(),
+                None => String::new(),
+            },
+            None => String::new(),
+        };
+        let send_info = uuid::Uuid::parse_str("dfc26f11-fa2b-4667-aad3-456edbdd9c84").unwrap();
+        let send_warn = uuid::Uuid::parse_str("3982006c-9e32-4e59-a544-58bc9a367daf").unwrap();
+        let send_error = uuid::Uuid::parse_str("ab04b49d-ff65-44c6-a70b-8546ecdbc5ba").unwrap();
+        let send_pass = uuid::Uuid::parse_str("76e93dc1-50d7-4686-8b5e-07019f4597df").unwrap();
+        match receiver {
+            // Each level logs and then signals its matching send pin with the same message.
+            id_info if id_info == &uuid::Uuid::parse_str("6b9c6c69-13e8-473a-ac47-818fcdf6d7bd").unwrap() => {
+                info!("{}", text);
+                node.emit(context, &send_info, message.clone());
+            }
+            id_warn if id_warn == &uuid::Uuid::parse_str("4eb1bc59-ca1b-4754-be49-0ad13f86421a").unwrap() => {
+                warn!("{}", text);
+                node.emit(context, &send_warn, message.clone());
+            }
+            id_error if id_error == &uuid::Uuid::parse_str("3f66f874-b785-4444-b7c6-5007052b531c").unwrap() => {
+                error!("{}", text);
+                node.emit(context, &send_error, message.clone());
+            }
+            // Logs as info and hands the message on unchanged to its own send pin, so it can be dropped inline anywhere.
+            id_pass if id_pass == &uuid::Uuid::parse_str("bccf1a26-793d-4c80-ad25-be110c4dc1d7").unwrap() => {
+                info!("{}", text);
+                node.emit(context, &send_pass, message.clone());
+            }
+            _ => error!("log node does not have receive pin with uuid of {}", receiver),
+        }
     }
 }
 
@@ -180,7 +216,7 @@ impl NodeUtilLogV1 {
                     name: String::from("Log"),
                     uuid: uuid::Uuid::parse_str("fd41d8ef-d10f-4499-8a90-35b73d8ff246").unwrap(),
                     library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
-                    version: 1,
+                    version: VersionSpec::Exact(1),
                 },
             },
             Box::new(process),
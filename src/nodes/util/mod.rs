@@ -1,4 +1,5 @@
 pub mod log;
+pub mod random;
 
 use crate::graph::*;
 
@@ -6,6 +7,7 @@ use crate::graph::*;
 pub fn register() -> Vec<(GraphInfo, u64)> {
     let mut nodes = Vec::new();
     nodes.push(log::register());
+    nodes.push(random::register());
     nodes
 }
 
@@ -22,5 +24,7 @@ pub fn create(
     version: u64,
     instance_id: uuid::Uuid,
 ) -> Option<Node> {
-    log::create(controller, catalogue, uuid, version, instance_id)
+    None
+    .or(log::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(random::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
 }
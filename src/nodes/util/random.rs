@@ -0,0 +1,166 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use axiom::prelude::*;
+use rand::distributions::Uniform;
+use rand::Rng;
+
+///
+/// Gives out deterministic random numbers, seeded from the node's seed.
+/// The same seed always gives the same numbers, no matter where or when the graph runs.
+///
+#[derive(Default)]
+pub struct NodeUtilRandomV1 {}
+
+impl Nodeable for NodeUtilRandomV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Min"),
+            uuid::Uuid::parse_str("741e8159-2f51-472d-9700-e6e0a2496766").unwrap(),
+            String::from("f64"),
+            Some(true),
+            None,
+            None,
+        )));
+        inputs.push(Pin::new_io_basic(PinInfo::new_extended(
+            String::from("Max"),
+            uuid::Uuid::parse_str("0a17d763-1476-45ac-9165-cafce0fd79e9").unwrap(),
+            String::from("f64"),
+            Some(true),
+            None,
+            None,
+        )));
+        outputs.push(Pin::new_io_basic(PinInfo::new_basic(
+            String::from("Value"),
+            uuid::Uuid::parse_str("3c83e8cd-3296-44e8-a799-ce90f3eeaf2a").unwrap(),
+            String::from("f64"),
+        )));
+        outputs.push(Pin::new_io_basic(PinInfo::new_basic(
+            String::from("Integer"),
+            uuid::Uuid::parse_str("cfba7e05-0fa9-429b-a64e-c1b707fd16ef").unwrap(),
+            String::from("i64"),
+        )));
+        outputs.push(Pin::new_io_basic(PinInfo::new_basic(
+            String::from("Seed"),
+            uuid::Uuid::parse_str("94d660f1-a131-495e-af8d-ab39b46b5521").unwrap(),
+            String::from("u64"),
+        )));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        let min = node
+            .get_input::<f64>(&uuid::Uuid::parse_str("741e8159-2f51-472d-9700-e6e0a2496766").unwrap())
+            .unwrap_or(0.0);
+        let max = node
+            .get_input::<f64>(&uuid::Uuid::parse_str("0a17d763-1476-45ac-9165-cafce0fd79e9").unwrap())
+            .unwrap_or(1.0);
+        let mut rng = node.rng(&output_info.uuid);
+        match output_info.uuid {
+            id_value
+                if id_value
+                    == uuid::Uuid::parse_str("3c83e8cd-3296-44e8-a799-ce90f3eeaf2a").unwrap() =>
+            {
+                if min < max {
+                    Ok(Some(Message::new(rng.gen_range(min, max))))
+                } else {
+                    Ok(Some(Message::new(min)))
+                }
+            }
+            id_integer
+                if id_integer
+                    == uuid::Uuid::parse_str("cfba7e05-0fa9-429b-a64e-c1b707fd16ef").unwrap() =>
+            {
+                let (min, max) = (min.ceil() as i64, max.floor() as i64);
+                if min < max {
+                    Ok(Some(Message::new(rng.sample(Uniform::new_inclusive(min, max)))))
+                } else {
+                    Ok(Some(Message::new(min)))
+                }
+            }
+            id_seed
+                if id_seed
+                    == uuid::Uuid::parse_str("94d660f1-a131-495e-af8d-ab39b46b5521").unwrap() =>
+            {
+                Ok(Some(Message::new(node.seed())))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeUtilRandomV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Random"),
+                    uuid: uuid::Uuid::parse_str("7ea23d03-9c75-4c7d-8d5f-f6b114bf776e").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Random"),
+            uuid: uuid::Uuid::parse_str("7ea23d03-9c75-4c7d-8d5f-f6b114bf776e").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("7ea23d03-9c75-4c7d-8d5f-f6b114bf776e").unwrap() {
+        return match version {
+            1 => Some(NodeUtilRandomV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
@@ -0,0 +1,138 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+///
+/// The boolean operations between volumes.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CsgOp {
+    Union,
+    Subtract,
+    Intersect,
+}
+
+/// The name, graph UUID and operation of every volume boolean node.
+pub const OPERATIONS: [(&str, &str, CsgOp); 3] = [
+    ("Volume Union", "68a8e7a9-2499-4d20-8396-0bc7f88b7977", CsgOp::Union),
+    ("Volume Subtract", "220a84dd-e895-407f-9d95-679533fe1f8c", CsgOp::Subtract),
+    ("Volume Intersect", "037d486c-8154-47e8-b0de-04df47899e09", CsgOp::Intersect),
+];
+
+const VOLUMES: &str = "234e3496-3bc9-4287-9c93-179d725bb69e";
+const VOLUME: &str = "071216c4-7b8f-4d8f-a5bc-7eb6849b21e5";
+const CUTTER: &str = "b1c53bf9-f667-4195-87b6-5c44a6d7c541";
+const OUTPUT_VOLUME: &str = "94c681ce-e6b9-4930-9ab2-13f5c0bf7dab";
+
+///
+/// Joins volumes together, cuts one volume out of another, or keeps only where volumes overlap.
+/// The result has the size and spacing of the first volume, and the others are sampled onto it.
+///
+pub struct NodeVoxelCsgV1 {
+    pub op: CsgOp,
+}
+
+impl Nodeable for NodeVoxelCsgV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            CsgOp::Union | CsgOp::Intersect => inputs.push(input_pin("Volumes", VOLUMES, "volume", true)),
+            CsgOp::Subtract => {
+                inputs.push(input_pin("Volume", VOLUME, "volume", false));
+                inputs.push(input_pin("Cutter", CUTTER, "volume", false));
+            }
+        }
+        outputs.push(output_pin("Volume", OUTPUT_VOLUME, "volume"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_VOLUME).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let volume = match self.op {
+            CsgOp::Union | CsgOp::Intersect => {
+                let volumes = node.get_inputs::<Volume>(&uuid::Uuid::parse_str(VOLUMES).unwrap());
+                let (first, rest) = match volumes.split_first() {
+                    Some(split) => split,
+                    None => return Err(String::from("no volumes to combine")),
+                };
+                rest.iter().fold(first.clone(), |combined, volume| {
+                    if self.op == CsgOp::Union {
+                        combined.combine(volume, f32::min)
+                    } else {
+                        combined.combine(volume, f32::max)
+                    }
+                })
+            }
+            CsgOp::Subtract => volume_input(node, VOLUME)?
+                .combine(&volume_input(node, CUTTER)?, |volume, cutter| volume.max(-cutter)),
+        };
+        Ok(Some(Message::new(volume)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeVoxelCsgV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: CsgOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeVoxelCsgV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
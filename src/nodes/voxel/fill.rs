@@ -0,0 +1,234 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+use bracket_noise::prelude::*;
+use nalgebra::Vector3;
+
+///
+/// The ways a volume can be created or filled.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FillOp {
+    Empty,
+    Sphere,
+    Box,
+    Noise,
+}
+
+/// The name, graph UUID and operation of every volume filling node.
+pub const OPERATIONS: [(&str, &str, FillOp); 4] = [
+    ("Empty Volume", "994c3bfe-1890-4466-9fdf-62c2b14abc9a", FillOp::Empty),
+    ("Fill Volume Sphere", "2de0d203-e40b-4a37-b536-a9ac2e4257f0", FillOp::Sphere),
+    ("Fill Volume Box", "16aa3c73-f77f-4bff-acaf-2382b7de9b81", FillOp::Box),
+    ("Fill Volume Noise", "b0ca0bf6-3ad7-4a72-996f-0c45e2845e21", FillOp::Noise),
+];
+
+const WIDTH: &str = "f3cb233c-8e7c-41c0-9007-978bec690114";
+const HEIGHT: &str = "d5fb3084-cc00-4112-a7ae-f4667f415d22";
+const DEPTH: &str = "5099b59d-2b32-4a2e-889d-96c57cf2d11e";
+const VOXEL_SIZE: &str = "babc5f1e-8d8f-4bc7-ab73-9c836365d7a0";
+const ORIGIN: &str = "063148c0-bc67-41a2-840b-5baf21a965b6";
+const SPARSE: &str = "38220564-1640-4240-a3f3-f8d3f1738e02";
+const VOLUME: &str = "4f37d045-f956-468e-8122-1ae592706276";
+const CENTER: &str = "13a0b9e1-6d2a-4784-bad9-f99aba5b531b";
+const RADIUS: &str = "09295cd7-d37c-4ebe-bad3-5dabf38b8c1a";
+const SIZE: &str = "f2131bb7-7109-4d21-bdba-4a73afb0336f";
+const NOISE_TYPE: &str = "809d5dd2-3f99-49a1-98cc-466bf09d9443";
+const SEED: &str = "bf8a4e6b-d547-4429-94ea-d4abf2def4ce";
+const FREQUENCY: &str = "6cda8141-bd5f-456b-871f-c65174c4dc36";
+const THRESHOLD: &str = "f1d1c366-4269-499a-9a72-ede959356b87";
+const OUTPUT_VOLUME: &str = "e592dae7-9228-4ef5-9a1c-2d856e6d9947";
+
+///
+/// Gives back the signed distance from a point to an axis aligned box with the given center and half size.
+///
+pub fn box_distance(point: Vector3<f32>, center: Vector3<f32>, half_size: Vector3<f32>) -> f32 {
+    let q = (point - center).abs() - half_size;
+    let outside = Vector3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).norm();
+    outside + q.x.max(q.y).max(q.z).min(0.0)
+}
+
+///
+/// Gets the bracket-noise type from its name, which is one of perlin, simplex, value or cellular.
+///
+pub fn parse_noise_type(name: &str) -> Option<NoiseType> {
+    match name.to_lowercase().as_str() {
+        "perlin" => Some(NoiseType::Perlin),
+        "simplex" => Some(NoiseType::Simplex),
+        "value" => Some(NoiseType::Value),
+        "cellular" => Some(NoiseType::Cellular),
+        _ => None,
+    }
+}
+
+///
+/// Creates an empty volume, or replaces the contents of a volume with a sphere, a box, or 3D noise.
+/// Noise is solid where it is above the threshold, and its distance is only an estimate made by dividing by the frequency.
+///
+pub struct NodeVoxelFillV1 {
+    pub op: FillOp,
+}
+
+impl Nodeable for NodeVoxelFillV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        match self.op {
+            FillOp::Empty => {
+                inputs.push(input_pin("Width", WIDTH, "u64", false));
+                inputs.push(input_pin("Height", HEIGHT, "u64", false));
+                inputs.push(input_pin("Depth", DEPTH, "u64", false));
+                inputs.push(input_pin("Voxel Size", VOXEL_SIZE, "f64", false));
+                inputs.push(input_pin("Origin", ORIGIN, "vec3", false));
+                inputs.push(input_pin("Sparse", SPARSE, "bool", false));
+            }
+            FillOp::Sphere => {
+                inputs.push(input_pin("Volume", VOLUME, "volume", false));
+                inputs.push(input_pin("Center", CENTER, "vec3", false));
+                inputs.push(input_pin("Radius", RADIUS, "f64", false));
+            }
+            FillOp::Box => {
+                inputs.push(input_pin("Volume", VOLUME, "volume", false));
+                inputs.push(input_pin("Center", CENTER, "vec3", false));
+                inputs.push(input_pin("Size", SIZE, "vec3", false));
+            }
+            FillOp::Noise => {
+                inputs.push(input_pin("Volume", VOLUME, "volume", false));
+                inputs.push(input_pin("Noise Type", NOISE_TYPE, "string", false));
+                inputs.push(input_pin("Seed", SEED, "u64", false));
+                inputs.push(input_pin("Frequency", FREQUENCY, "f64", false));
+                inputs.push(input_pin("Threshold", THRESHOLD, "f64", false));
+            }
+        }
+        outputs.push(output_pin("Volume", OUTPUT_VOLUME, "volume"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        if output_info.uuid != uuid::Uuid::parse_str(OUTPUT_VOLUME).unwrap() {
+            return Err(format!("output pin with uuid {}", output_info.uuid));
+        }
+        let get_f64 = |id: &str, default: f64| {
+            node.get_input::<f64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+        };
+        let get_u64 = |id: &str, default: u64| {
+            node.get_input::<u64>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+        };
+        let get_vec3 = |id: &str, default: Vector3<f64>| {
+            node.get_input::<Vector3<f64>>(&uuid::Uuid::parse_str(id).unwrap())
+                .unwrap_or(default)
+                .map(|value| value as f32)
+        };
+        let volume = match self.op {
+            FillOp::Empty => {
+                let size = [get_u64(WIDTH, 32), get_u64(HEIGHT, 32), get_u64(DEPTH, 32)];
+                // Checked before narrowing, so that sizes past usize are rejected rather than cut short.
+                voxel_count(size)?;
+                let size = [size[0] as usize, size[1] as usize, size[2] as usize];
+                let voxel_size = get_f64(VOXEL_SIZE, 1.0) as f32;
+                if voxel_size <= 0.0 {
+                    return Err(format!("voxel size must be above zero, got {}", voxel_size));
+                }
+                let origin = get_vec3(ORIGIN, Vector3::zeros());
+                let sparse = node
+                    .get_input::<bool>(&uuid::Uuid::parse_str(SPARSE).unwrap())
+                    .unwrap_or(false);
+                Volume::new(size, voxel_size, [origin.x, origin.y, origin.z], sparse)?
+            }
+            FillOp::Sphere => {
+                let center = get_vec3(CENTER, Vector3::zeros());
+                let radius = get_f64(RADIUS, 1.0) as f32;
+                volume_input(node, VOLUME)?.fill(|point| (Vector3::from(point) - center).norm() - radius)
+            }
+            FillOp::Box => {
+                let center = get_vec3(CENTER, Vector3::zeros());
+                let half_size = get_vec3(SIZE, Vector3::new(1.0, 1.0, 1.0)) / 2.0;
+                volume_input(node, VOLUME)?.fill(|point| box_distance(Vector3::from(point), center, half_size))
+            }
+            FillOp::Noise => {
+                let name = node
+                    .get_input::<String>(&uuid::Uuid::parse_str(NOISE_TYPE).unwrap())
+                    .unwrap_or_else(|| String::from("simplex"));
+                let noise_type = match parse_noise_type(&name) {
+                    Some(noise_type) => noise_type,
+                    None => return Err(format!("unknown noise type {}", name)),
+                };
+                let frequency = get_f64(FREQUENCY, 0.05) as f32;
+                let threshold = get_f64(THRESHOLD, 0.0) as f32;
+                let mut noise = FastNoise::seeded(get_u64(SEED, node.seed()));
+                noise.set_noise_type(noise_type);
+                noise.set_frequency(frequency);
+                let scale = if frequency == 0.0 { 1.0 } else { 1.0 / frequency };
+                volume_input(node, VOLUME)?
+                    .fill(|point| (threshold - noise.get_noise3d(point[0], point[1], point[2])) * scale)
+            }
+        };
+        Ok(Some(Message::new(volume)))
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeVoxelFillV1 {
+    pub fn new(
+        controller: Aid,
+        catalogue: Arc<Mutex<Catalogue>>,
+        instance_id: uuid::Uuid,
+        name: &str,
+        graph: &str,
+        op: FillOp,
+    ) -> Node {
+        let process = Self { op };
+        Node::new(
+            instance_info(name, graph, instance_id),
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    register_operations(&OPERATIONS)
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    find_operation(&OPERATIONS, uuid, version).map(|(name, graph, op)| {
+        NodeVoxelFillV1::new(controller, catalogue, instance_id, name, graph, *op)
+    })
+}
@@ -0,0 +1,330 @@
+pub mod csg;
+pub mod fill;
+pub mod surface;
+
+use crate::graph::*;
+
+/// Registers the internal nodes as available graphs to a catalogue.
+pub fn register() -> Vec<(GraphInfo, u64)> {
+    let mut nodes = Vec::new();
+    nodes.append(&mut fill::register());
+    nodes.append(&mut csg::register());
+    nodes.push(surface::register());
+    nodes
+}
+
+use crate::catalogue::*;
+use crate::node::*;
+use axiom::prelude::*;
+use std::sync::{Arc, Mutex};
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    None
+    .or(fill::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(csg::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+    .or(surface::create(controller.clone(), catalogue.clone(), uuid, version, instance_id))
+}
+
+use serde::{Deserialize, Serialize};
+
+/// The number of voxels along each side of a chunk in a chunked volume.
+pub const CHUNK_SIZE: usize = 16;
+/// How many voxels away from the surface distances are kept before being clamped.
+pub const NARROW_BAND: f32 = 3.0;
+/// The most voxels a volume can hold, which is 64 MiB of distances when dense.
+pub const MAX_VOXELS: usize = 1 << 24;
+
+///
+/// Gives the number of voxels in a volume of the given size, or an error if it would be more than `MAX_VOXELS`.
+///
+pub fn voxel_count(size: [u64; 3]) -> Result<usize, String> {
+    let count = size.iter().try_fold(1u64, |count, axis| count.checked_mul(*axis));
+    match count {
+        Some(count) if count <= MAX_VOXELS as u64 && size.iter().all(|axis| *axis <= MAX_VOXELS as u64) => Ok(count as usize),
+        _ => Err(format!(
+            "volume of {} by {} by {} is larger than the most of {} voxels",
+            size[0], size[1], size[2], MAX_VOXELS
+        )),
+    }
+}
+
+///
+/// A block of voxels in a chunked volume.
+/// Chunks that are entirely inside or outside hold a single value instead of one per voxel.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum Chunk {
+    Uniform(f32),
+    Values(Vec<f32>),
+}
+
+///
+/// How the voxels of a volume are stored.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum VolumeStorage {
+    /// One value per voxel, ordered by x, then y, then z.
+    Dense(Vec<f32>),
+    /// Chunks of CHUNK_SIZE voxels per side, ordered by x, then y, then z.
+    Chunked(Vec<Chunk>),
+}
+
+///
+/// A voxel volume, carried on pins with the "volume" datatype.
+/// Each voxel holds a signed distance to a surface, which is negative inside and positive outside.
+/// Distances are clamped to a narrow band around the surface, which is all surface extraction needs,
+/// and lets chunked volumes skip storing the chunks that are entirely inside or outside.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct Volume {
+    /// The number of voxels along x, y and z.
+    pub size: [usize; 3],
+    /// The distance between neighbouring voxels.
+    pub voxel_size: f32,
+    /// The position of the first voxel.
+    pub origin: [f32; 3],
+    pub storage: VolumeStorage,
+}
+
+impl Volume {
+    ///
+    /// Creates a volume where every voxel is outside.
+    /// Sparse volumes are chunked, while the rest are dense.
+    /// Gives an error if the volume would hold more than `MAX_VOXELS`.
+    ///
+    pub fn new(size: [usize; 3], voxel_size: f32, origin: [f32; 3], sparse: bool) -> Result<Self, String> {
+        let voxels = voxel_count([size[0] as u64, size[1] as u64, size[2] as u64])?;
+        let mut volume = Self {
+            size,
+            voxel_size,
+            origin,
+            storage: VolumeStorage::Dense(Vec::new()),
+        };
+        let outside = volume.band();
+        volume.storage = if sparse {
+            let chunks = volume
+                .chunks()
+                .iter()
+                .try_fold(1usize, |count, axis| count.checked_mul(*axis))
+                .ok_or_else(|| format!("volume of {:?} voxels has too many chunks", size))?;
+            VolumeStorage::Chunked(vec![Chunk::Uniform(outside); chunks])
+        } else {
+            VolumeStorage::Dense(vec![outside; voxels])
+        };
+        Ok(volume)
+    }
+    pub fn is_sparse(&self) -> bool {
+        match self.storage {
+            VolumeStorage::Dense(_) => false,
+            VolumeStorage::Chunked(_) => true,
+        }
+    }
+    /// The furthest distance from the surface that voxels keep.
+    pub fn band(&self) -> f32 {
+        self.voxel_size * NARROW_BAND
+    }
+    /// The number of chunks along x, y and z.
+    pub fn chunks(&self) -> [usize; 3] {
+        [
+            (self.size[0] + CHUNK_SIZE - 1) / CHUNK_SIZE,
+            (self.size[1] + CHUNK_SIZE - 1) / CHUNK_SIZE,
+            (self.size[2] + CHUNK_SIZE - 1) / CHUNK_SIZE,
+        ]
+    }
+    fn chunk_count(&self) -> usize {
+        let chunks = self.chunks();
+        chunks[0] * chunks[1] * chunks[2]
+    }
+    /// Gives back the chunk index and the index inside the chunk of a voxel.
+    fn chunk_index(&self, x: usize, y: usize, z: usize) -> (usize, usize) {
+        let chunks = self.chunks();
+        let chunk = x / CHUNK_SIZE + (y / CHUNK_SIZE + z / CHUNK_SIZE * chunks[1]) * chunks[0];
+        let local = x % CHUNK_SIZE + (y % CHUNK_SIZE + z % CHUNK_SIZE * CHUNK_SIZE) * CHUNK_SIZE;
+        (chunk, local)
+    }
+    /// The number of voxel values actually held in memory.
+    pub fn stored_voxels(&self) -> usize {
+        match &self.storage {
+            VolumeStorage::Dense(values) => values.len(),
+            VolumeStorage::Chunked(chunks) => chunks
+                .iter()
+                .map(|chunk| match chunk {
+                    Chunk::Uniform(_) => 1,
+                    Chunk::Values(values) => values.len(),
+                })
+                .sum(),
+        }
+    }
+    /// Gets a voxel, where voxels past the edges are outside.
+    pub fn get(&self, x: usize, y: usize, z: usize) -> f32 {
+        if x >= self.size[0] || y >= self.size[1] || z >= self.size[2] {
+            return self.band();
+        }
+        match &self.storage {
+            VolumeStorage::Dense(values) => values[x + (y + z * self.size[1]) * self.size[0]],
+            VolumeStorage::Chunked(chunks) => {
+                let (chunk, local) = self.chunk_index(x, y, z);
+                match &chunks[chunk] {
+                    Chunk::Uniform(value) => *value,
+                    Chunk::Values(values) => values[local],
+                }
+            }
+        }
+    }
+    /// Sets a voxel, clamping the distance to the narrow band. Voxels past the edges are ignored.
+    pub fn set(&mut self, x: usize, y: usize, z: usize, value: f32) {
+        if x >= self.size[0] || y >= self.size[1] || z >= self.size[2] {
+            return;
+        }
+        let band = self.band();
+        let value = value.max(-band).min(band);
+        let (chunk, local) = self.chunk_index(x, y, z);
+        let index = x + (y + z * self.size[1]) * self.size[0];
+        match &mut self.storage {
+            VolumeStorage::Dense(values) => values[index] = value,
+            VolumeStorage::Chunked(chunks) => match &mut chunks[chunk] {
+                Chunk::Uniform(uniform) if *uniform == value => {}
+                Chunk::Uniform(uniform) => {
+                    let mut values = vec![*uniform; CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE];
+                    values[local] = value;
+                    chunks[chunk] = Chunk::Values(values);
+                }
+                Chunk::Values(values) => values[local] = value,
+            },
+        }
+    }
+    /// Gets the position of a voxel.
+    pub fn position(&self, x: usize, y: usize, z: usize) -> [f32; 3] {
+        [
+            self.origin[0] + x as f32 * self.voxel_size,
+            self.origin[1] + y as f32 * self.voxel_size,
+            self.origin[2] + z as f32 * self.voxel_size,
+        ]
+    }
+    ///
+    /// Gets the distance at any position by blending the eight voxels around it.
+    /// Positions outside of the volume are outside.
+    ///
+    pub fn sample(&self, position: [f32; 3]) -> f32 {
+        let mut base = [0usize; 3];
+        let mut fraction = [0.0f32; 3];
+        for axis in 0..3 {
+            let local = (position[axis] - self.origin[axis]) / self.voxel_size;
+            if local < 0.0 || local > (self.size[axis] as f32 - 1.0) || self.size[axis] == 0 {
+                return self.band();
+            }
+            let floor = local.floor().min(self.size[axis].saturating_sub(2) as f32);
+            base[axis] = floor as usize;
+            fraction[axis] = local - floor;
+        }
+        let mut value = 0.0;
+        for corner in 0..8 {
+            let offset = [corner & 1, (corner >> 1) & 1, (corner >> 2) & 1];
+            let mut weight = 1.0;
+            for axis in 0..3 {
+                weight *= if offset[axis] == 1 { fraction[axis] } else { 1.0 - fraction[axis] };
+            }
+            if weight > 0.0 {
+                value += weight * self.get(base[0] + offset[0], base[1] + offset[1], base[2] + offset[2]);
+            }
+        }
+        value
+    }
+    ///
+    /// Creates a volume with the same size, spacing and storage, where every voxel is computed from its coordinates.
+    /// Chunks that come out entirely inside or outside are stored as a single value.
+    ///
+    pub fn generate<F>(&self, f: F) -> Self
+    where
+        F: Fn(usize, usize, usize) -> f32,
+    {
+        let band = self.band();
+        let clamp = |value: f32| value.max(-band).min(band);
+        let storage = match &self.storage {
+            VolumeStorage::Dense(_) => {
+                let mut values = Vec::with_capacity(self.size[0] * self.size[1] * self.size[2]);
+                for z in 0..self.size[2] {
+                    for y in 0..self.size[1] {
+                        for x in 0..self.size[0] {
+                            values.push(clamp(f(x, y, z)));
+                        }
+                    }
+                }
+                VolumeStorage::Dense(values)
+            }
+            VolumeStorage::Chunked(_) => {
+                let chunks = self.chunks();
+                let mut generated = Vec::with_capacity(self.chunk_count());
+                for cz in 0..chunks[2] {
+                    for cy in 0..chunks[1] {
+                        for cx in 0..chunks[0] {
+                            let mut values = Vec::with_capacity(CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE);
+                            for lz in 0..CHUNK_SIZE {
+                                for ly in 0..CHUNK_SIZE {
+                                    for lx in 0..CHUNK_SIZE {
+                                        let (x, y, z) = (cx * CHUNK_SIZE + lx, cy * CHUNK_SIZE + ly, cz * CHUNK_SIZE + lz);
+                                        // Voxels past the edges only pad out the chunk, so they copy the first voxel to keep it uniform.
+                                        let value = if x < self.size[0] && y < self.size[1] && z < self.size[2] {
+                                            clamp(f(x, y, z))
+                                        } else {
+                                            values.first().copied().unwrap_or(band)
+                                        };
+                                        values.push(value);
+                                    }
+                                }
+                            }
+                            if values.iter().all(|value| *value == values[0]) {
+                                generated.push(Chunk::Uniform(values[0]));
+                            } else {
+                                generated.push(Chunk::Values(values));
+                            }
+                        }
+                    }
+                }
+                VolumeStorage::Chunked(generated)
+            }
+        };
+        Self {
+            size: self.size,
+            voxel_size: self.voxel_size,
+            origin: self.origin,
+            storage,
+        }
+    }
+    ///
+    /// Creates a volume with the same size, spacing and storage, filled from a signed distance function of position.
+    ///
+    pub fn fill<F>(&self, distance: F) -> Self
+    where
+        F: Fn([f32; 3]) -> f32,
+    {
+        self.generate(|x, y, z| distance(self.position(x, y, z)))
+    }
+    ///
+    /// Combines this volume with another, voxel by voxel, keeping the size and spacing of this one.
+    /// The other volume is sampled at the positions of these voxels, so the two do not need to line up.
+    ///
+    pub fn combine<F>(&self, other: &Volume, f: F) -> Self
+    where
+        F: Fn(f32, f32) -> f32,
+    {
+        self.generate(|x, y, z| f(self.get(x, y, z), other.sample(self.position(x, y, z))))
+    }
+}
+
+///
+/// Gets the volume on an input pin, which is an error if there is none.
+///
+pub fn volume_input(node: &Node, id: &str) -> Result<Volume, String> {
+    match node.get_input::<Volume>(&uuid::Uuid::parse_str(id).unwrap()) {
+        Some(volume) => Ok(volume),
+        None => Err(format!("no volume on input pin {}", id)),
+    }
+}
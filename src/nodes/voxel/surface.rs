@@ -0,0 +1,221 @@
+use crate::node::*;
+
+use crate::catalogue::*;
+use crate::graph::*;
+
+use super::*;
+use crate::nodes::*;
+use axiom::prelude::*;
+
+use crate::nodes::geometry::Mesh;
+use nalgebra::Vector3;
+
+///
+/// Extracts the surface of a volume as a triangle mesh.
+///
+#[derive(Default)]
+pub struct NodeVoxelSurfaceV1 {}
+
+///
+/// Builds a mesh where distances in a volume cross zero, with surface nets.
+/// Like dual contouring, each cell the surface passes through gets one vertex, placed at the average of where its edges cross,
+/// and every voxel edge that crosses the surface joins the four cells around it with a quad.
+/// Normals come from the gradient of the distances, and UVs are the X and Z positions across the volume.
+///
+pub fn surface_nets(volume: &Volume) -> Mesh {
+    let mut mesh = Mesh::new();
+    let size = volume.size;
+    if size.iter().any(|side| *side < 2) {
+        return mesh;
+    }
+    let cells = [size[0] - 1, size[1] - 1, size[2] - 1];
+    let cell_index = |x: usize, y: usize, z: usize| x + (y + z * cells[1]) * cells[0];
+    let mut cell_vertices = vec![std::u32::MAX; cells[0] * cells[1] * cells[2]];
+    let extent = [
+        (cells[0] as f32 * volume.voxel_size).max(std::f32::EPSILON),
+        (cells[2] as f32 * volume.voxel_size).max(std::f32::EPSILON),
+    ];
+    let step = volume.voxel_size * 0.5;
+
+    for z in 0..cells[2] {
+        for y in 0..cells[1] {
+            for x in 0..cells[0] {
+                let mut values = [0.0f32; 8];
+                for (corner, value) in values.iter_mut().enumerate() {
+                    *value = volume.get(x + (corner & 1), y + ((corner >> 1) & 1), z + ((corner >> 2) & 1));
+                }
+                let inside = values.iter().filter(|value| **value < 0.0).count();
+                if inside == 0 || inside == 8 {
+                    continue;
+                }
+                let mut sum = Vector3::<f32>::zeros();
+                let mut crossings = 0.0;
+                for start in 0..8 {
+                    for bit in [1, 2, 4].iter() {
+                        let end = start | bit;
+                        if start & bit != 0 || (values[start] < 0.0) == (values[end] < 0.0) {
+                            continue;
+                        }
+                        let t = values[start] / (values[start] - values[end]);
+                        let corner = |c: usize| Vector3::new((c & 1) as f32, ((c >> 1) & 1) as f32, ((c >> 2) & 1) as f32);
+                        sum += corner(start) + (corner(end) - corner(start)) * t;
+                        crossings += 1.0;
+                    }
+                }
+                let local = sum / crossings;
+                let origin = volume.position(x, y, z);
+                let position = [
+                    origin[0] + local.x * volume.voxel_size,
+                    origin[1] + local.y * volume.voxel_size,
+                    origin[2] + local.z * volume.voxel_size,
+                ];
+                let along = |axis: usize| {
+                    let (mut forward, mut backward) = (position, position);
+                    forward[axis] += step;
+                    backward[axis] -= step;
+                    volume.sample(forward) - volume.sample(backward)
+                };
+                let gradient = Vector3::new(along(0), along(1), along(2));
+                let normal = gradient.try_normalize(std::f32::EPSILON).unwrap_or_else(Vector3::y);
+                let uv = [
+                    (position[0] - volume.origin[0]) / extent[0],
+                    (position[2] - volume.origin[2]) / extent[1],
+                ];
+                cell_vertices[cell_index(x, y, z)] = mesh.push_vertex(position, [normal.x, normal.y, normal.z], uv);
+            }
+        }
+    }
+
+    for z in 0..size[2] {
+        for y in 0..size[1] {
+            for x in 0..size[0] {
+                let point = [x, y, z];
+                let start = volume.get(x, y, z) < 0.0;
+                for axis in 0..3 {
+                    // The other two axes, in the order that makes their cross product point along this one.
+                    let (b, c) = ((axis + 1) % 3, (axis + 2) % 3);
+                    if point[axis] + 1 >= size[axis]
+                        || point[b] == 0
+                        || point[c] == 0
+                        || point[b] >= cells[b]
+                        || point[c] >= cells[c]
+                    {
+                        continue;
+                    }
+                    let mut next = point;
+                    next[axis] += 1;
+                    if start == (volume.get(next[0], next[1], next[2]) < 0.0) {
+                        continue;
+                    }
+                    let vertex = |db: usize, dc: usize| {
+                        let mut cell = point;
+                        cell[b] -= db;
+                        cell[c] -= dc;
+                        cell_vertices[cell_index(cell[0], cell[1], cell[2])]
+                    };
+                    let quad = [vertex(1, 1), vertex(0, 1), vertex(0, 0), vertex(1, 0)];
+                    if start {
+                        mesh.push_triangle(quad[0], quad[1], quad[2]);
+                        mesh.push_triangle(quad[0], quad[2], quad[3]);
+                    } else {
+                        mesh.push_triangle(quad[0], quad[2], quad[1]);
+                        mesh.push_triangle(quad[0], quad[3], quad[2]);
+                    }
+                }
+            }
+        }
+    }
+    mesh
+}
+
+impl Nodeable for NodeVoxelSurfaceV1 {
+    fn get_io(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        let mut inputs = Vec::new();
+        let mut outputs = Vec::new();
+        inputs.push(input_pin("Volume", "00183220-185a-4bb3-86f2-d4d88990211a", "volume", false));
+        outputs.push(output_pin("Mesh", "b167941c-ecac-4a0d-bf63-8922d68988d3", "mesh"));
+        (inputs, outputs)
+    }
+
+    fn get_rs(&self, _catalogue: &Catalogue) -> (std::vec::Vec<Pin>, std::vec::Vec<Pin>) {
+        (Vec::new(), Vec::new())
+    }
+
+    fn compute_output(
+        &mut self,
+        node: &mut Node,
+        output_info: PinInfo,
+        _context: &Context,
+        _parameter: &Option<Message>,
+    ) -> Result<Option<Message>, String> {
+        match output_info.uuid {
+            id_mesh if id_mesh == uuid::Uuid::parse_str("b167941c-ecac-4a0d-bf63-8922d68988d3").unwrap() => {
+                let volume = volume_input(node, "00183220-185a-4bb3-86f2-d4d88990211a")?;
+                Ok(Some(Message::new(surface_nets(&volume))))
+            }
+            _ => Err(format!("output pin with uuid {}", output_info.uuid)),
+        }
+    }
+
+    fn handle_receive(
+        &mut self,
+        _node: &mut Node,
+        _context: &Context,
+        _receiver: &uuid::Uuid,
+        _message: &Option<Message>,
+    ) {
+    }
+}
+
+use std::sync::{Arc, Mutex};
+
+impl NodeVoxelSurfaceV1 {
+    pub fn new(controller: Aid, catalogue: Arc<Mutex<Catalogue>>, instance_id: uuid::Uuid) -> Node {
+        let process = Self {};
+        Node::new(
+            NodeInstanceInfo {
+                uuid: instance_id,
+                data: std::collections::HashMap::new(),
+                graph: GraphRef {
+                    name: String::from("Volume To Mesh"),
+                    uuid: uuid::Uuid::parse_str("619c1e0f-e669-48c3-93e3-7bf763a732c8").unwrap(),
+                    library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+                    version: VersionSpec::Exact(1),
+                },
+            },
+            Box::new(process),
+            catalogue.clone(),
+            controller,
+        )
+    }
+}
+
+/// Registers the internal nodes as available graphs to a catalogue.
+/// Returns the graphs basic information and the number of versions it has.
+pub fn register() -> (GraphInfo, u64) {
+    (
+        GraphInfo {
+            name: String::from("Volume To Mesh"),
+            uuid: uuid::Uuid::parse_str("619c1e0f-e669-48c3-93e3-7bf763a732c8").unwrap(),
+            format: 1,
+        },
+        1,
+    )
+}
+
+/// Gives back a new internal node object from a given UUID, if it exists.
+pub fn create(
+    controller: Aid,
+    catalogue: Arc<Mutex<Catalogue>>,
+    uuid: uuid::Uuid,
+    version: u64,
+    instance_id: uuid::Uuid,
+) -> Option<Node> {
+    if uuid == uuid::Uuid::parse_str("619c1e0f-e669-48c3-93e3-7bf763a732c8").unwrap() {
+        return match version {
+            1 => Some(NodeVoxelSurfaceV1::new(controller, catalogue, instance_id)),
+            _ => None,
+        };
+    }
+    None
+}
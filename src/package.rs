@@ -0,0 +1,314 @@
+use serde::{Deserialize, Serialize};
+
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use log::*;
+
+use super::graph::*;
+use super::library::*;
+
+/// The extension of library package files.
+pub const PACKAGE_EXTENSION: &str = "pflib";
+
+/// The name of the manifest within a package.
+pub const MANIFEST_NAME: &str = "manifest.json";
+
+/// The format of package manifests written by this version.
+pub const MANIFEST_FORMAT: u32 = 1;
+
+///
+/// Describes what a library package holds, so that it can be checked before it is used.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PackageManifest {
+    pub format: u32,
+    /// The library in the package, as its lib.json says.
+    pub library: LibraryInfo,
+    /// Every file in the package other than the manifest.
+    pub files: Vec<PackageFile>,
+}
+
+///
+/// A file within a library package.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PackageFile {
+    /// The path of the file within the package, separated by forward slashes.
+    pub path: String,
+    pub size: u64,
+    /// The SHA-256 of the file contents, in lowercase hex.
+    pub sha256: String,
+}
+
+fn checksum(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+///
+/// Whether a path within a package stays within it, being made only of plain names separated by forward slashes.
+///
+pub fn stays_within(path: &str) -> bool {
+    !path.is_empty()
+        && !path.contains('\\')
+        && Path::new(path).components().all(|component| matches!(component, std::path::Component::Normal(_)))
+}
+
+///
+/// Whether a path is a library package file.
+///
+pub fn is_package(path: &Path) -> bool {
+    path.extension().map_or(false, |extension| extension == PACKAGE_EXTENSION) && path.is_file()
+}
+
+///
+/// Splits a path within a mounted package into the package file and the path inside it.
+/// Paths of files on disk, or of folders that are not in a package, give none.
+///
+pub fn split_package_path(path: &Path) -> Option<(PathBuf, String)> {
+    let package = path.ancestors().find(|ancestor| is_package(ancestor))?;
+    let inner = path.strip_prefix(package).ok()?;
+    let inner: Vec<String> = inner.components().map(|component| component.as_os_str().to_string_lossy().into_owned()).collect();
+    Some((package.to_path_buf(), inner.join("/")))
+}
+
+fn open(package: &Path) -> Result<zip::ZipArchive<std::fs::File>, String> {
+    let file = std::fs::File::open(package).map_err(|e| format!("could not open package {}: {}", package.display(), e))?;
+    zip::ZipArchive::new(file).map_err(|e| format!("could not read package {}: {}", package.display(), e))
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<std::fs::File>, package: &Path, name: &str) -> Result<Vec<u8>, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("could not find {} in package {}: {}", name, package.display(), e))?;
+    let mut bytes = Vec::new();
+    entry
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("could not read {} in package {}: {}", name, package.display(), e))?;
+    Ok(bytes)
+}
+
+///
+/// A mounted package kept open, so that its zip directory is only read again when the file changes.
+///
+struct OpenPackage {
+    /// The modification time and size of the package file when it was opened.
+    stamp: Option<(SystemTime, u64)>,
+    archive: Mutex<zip::ZipArchive<std::fs::File>>,
+    /// The names of every entry in the package.
+    names: HashSet<String>,
+}
+
+/// The packages kept open, keyed by their path.
+static OPEN_PACKAGES: Mutex<Option<HashMap<PathBuf, Arc<OpenPackage>>>> = Mutex::new(None);
+
+///
+/// Gets a package that is kept open, opening it again if the file changed since it was last opened.
+///
+fn open_package(package: &Path) -> Result<Arc<OpenPackage>, String> {
+    let current = stamp(package);
+    let mut packages = OPEN_PACKAGES.lock().unwrap();
+    let packages = packages.get_or_insert_with(HashMap::new);
+    if let Some(open_package) = packages.get(package).filter(|open_package| open_package.stamp == current) {
+        return Ok(open_package.clone());
+    }
+    let archive = open(package)?;
+    let names = archive.file_names().map(String::from).collect();
+    let open_package = Arc::new(OpenPackage { stamp: current, archive: Mutex::new(archive), names });
+    packages.insert(package.to_path_buf(), open_package.clone());
+    Ok(open_package)
+}
+
+///
+/// Reads a library file, whether it is on disk or within a mounted package.
+///
+pub fn read_library_file(path: &Path) -> Result<Vec<u8>, String> {
+    if path.is_file() {
+        return std::fs::read(path).map_err(|e| format!("could not read {}: {}", path.display(), e));
+    }
+    match split_package_path(path) {
+        Some((package, inner)) => read_entry(&mut open_package(&package)?.archive.lock().unwrap(), &package, &inner),
+        None => Err(format!("{} does not exist", path.display())),
+    }
+}
+
+///
+/// Whether a library file exists, whether it is on disk or within a mounted package.
+///
+pub fn library_file_exists(path: &Path) -> bool {
+    if path.is_file() {
+        return true;
+    }
+    match split_package_path(path) {
+        Some((package, inner)) => open_package(&package).map_or(false, |open_package| open_package.names.contains(&inner)),
+        None => false,
+    }
+}
+
+///
+/// The names of the entries directly within a folder of a package.
+///
+pub fn list_package_dir(path: &Path) -> Result<Vec<String>, String> {
+    let (package, inner) = match split_package_path(path) {
+        Some(split) => split,
+        None => return Err(format!("{} is not within a package", path.display())),
+    };
+    let prefix = match inner.is_empty() {
+        true => String::new(),
+        false => format!("{}/", inner),
+    };
+    let mut names: Vec<String> = open_package(&package)?
+        .names
+        .iter()
+        .filter_map(|name| name.strip_prefix(&prefix))
+        .filter_map(|rest| rest.split('/').next())
+        .filter(|name| !name.is_empty())
+        .map(String::from)
+        .collect();
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+///
+/// Packs a library folder into a single package file, with a manifest of every file in it.
+///
+pub fn pack(library_path: &Path, package: &Path) -> Result<PackageManifest, String> {
+    let libjson = library_path.join("lib.json");
+    let library: LibraryInfo = serde_json::from_slice(&std::fs::read(&libjson).map_err(|e| format!("could not read {}: {}", libjson.display(), e))?)
+        .map_err(|e| format!("could not parse {}: {}", libjson.display(), e))?;
+    let file = std::fs::File::create(package).map_err(|e| format!("could not create package {}: {}", package.display(), e))?;
+    let mut writer = zip::ZipWriter::new(file);
+    let options = zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    let mut files = Vec::new();
+    let walker = walkdir::WalkDir::new(library_path).sort_by(|a, b| a.file_name().cmp(b.file_name()));
+    for entry in walker {
+        let entry = entry.map_err(|e| format!("could not walk {}: {}", library_path.display(), e))?;
+        if !entry.file_type().is_file() || entry.path() == package {
+            continue;
+        }
+        let relative = entry.path().strip_prefix(library_path).map_err(|e| e.to_string())?;
+        let name: Vec<String> = relative.components().map(|component| component.as_os_str().to_string_lossy().into_owned()).collect();
+        let name = name.join("/");
+        if name == MANIFEST_NAME {
+            continue;
+        }
+        let bytes = std::fs::read(entry.path()).map_err(|e| format!("could not read {}: {}", entry.path().display(), e))?;
+        writer.start_file(name.clone(), options).map_err(|e| format!("could not add {} to package: {}", name, e))?;
+        writer.write_all(&bytes).map_err(|e| format!("could not write {} to package: {}", name, e))?;
+        files.push(PackageFile {
+            path: name,
+            size: bytes.len() as u64,
+            sha256: checksum(&bytes),
+        });
+    }
+    let manifest = PackageManifest {
+        format: MANIFEST_FORMAT,
+        library,
+        files,
+    };
+    let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| e.to_string())?;
+    writer.start_file(MANIFEST_NAME, options).map_err(|e| format!("could not add manifest to package: {}", e))?;
+    writer.write_all(manifest_json.as_bytes()).map_err(|e| format!("could not write manifest to package: {}", e))?;
+    writer.finish().map_err(|e| format!("could not finish package {}: {}", package.display(), e))?;
+    info!("packed library {} into {} with {} files", library_path.display(), package.display(), manifest.files.len());
+    Ok(manifest)
+}
+
+///
+/// Checks that a package holds exactly the files its manifest lists, with the right checksums, and that none of them are outside the library.
+///
+pub fn verify(package: &Path) -> Result<PackageManifest, String> {
+    let mut archive = open(package)?;
+    let manifest: PackageManifest = serde_json::from_slice(&read_entry(&mut archive, package, MANIFEST_NAME)?)
+        .map_err(|e| format!("could not parse manifest of package {}: {}", package.display(), e))?;
+    if manifest.format > MANIFEST_FORMAT {
+        return Err(format!("package {} has manifest format {}, newer than {}", package.display(), manifest.format, MANIFEST_FORMAT));
+    }
+    if let Some(file) = manifest.files.iter().find(|file| !stays_within(&file.path)) {
+        return Err(format!("package {} holds {} which is outside the library", package.display(), file.path));
+    }
+    let listed: HashMap<&str, &PackageFile> = manifest.files.iter().map(|file| (file.path.as_str(), file)).collect();
+    let names: Vec<String> = archive.file_names().map(String::from).collect();
+    for name in names.iter().filter(|name| *name != MANIFEST_NAME && !name.ends_with('/')) {
+        if !listed.contains_key(name.as_str()) {
+            return Err(format!("package {} holds {} which is not in its manifest", package.display(), name));
+        }
+    }
+    for file in manifest.files.iter() {
+        let bytes = read_entry(&mut archive, package, &file.path)?;
+        if bytes.len() as u64 != file.size || checksum(&bytes) != file.sha256 {
+            return Err(format!("{} in package {} does not match its manifest", file.path, package.display()));
+        }
+    }
+    Ok(manifest)
+}
+
+///
+/// Unpacks a package into a library folder, after checking it.
+///
+pub fn unpack(package: &Path, library_path: &Path) -> Result<PackageManifest, String> {
+    let manifest = verify(package)?;
+    let mut archive = open(package)?;
+    for file in manifest.files.iter() {
+        let target = library_path.join(Path::new(&file.path));
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("could not create {}: {}", parent.display(), e))?;
+        }
+        let bytes = read_entry(&mut archive, package, &file.path)?;
+        std::fs::write(&target, bytes).map_err(|e| format!("could not write {}: {}", target.display(), e))?;
+    }
+    info!("unpacked package {} into {}", package.display(), library_path.display());
+    Ok(manifest)
+}
+
+///
+/// Reads a package as a read-only library, after checking it and that its lib.json says what its manifest does.
+/// Paths of the library and its graphs point within the package file, and are read with `read_library_file`.
+///
+pub fn mount(package: &Path) -> Result<Library, String> {
+    let manifest = verify(package)?;
+    let mut archive = open(package)?;
+    let library: LibraryInfo = serde_json::from_slice(&read_entry(&mut archive, package, "lib.json")?)
+        .map_err(|e| format!("could not parse lib.json in package {}: {}", package.display(), e))?;
+    let same = serde_json::to_value(&library).map_err(|e| e.to_string())? == serde_json::to_value(&manifest.library).map_err(|e| e.to_string())?;
+    if !same {
+        return Err(format!("lib.json in package {} does not match its manifest", package.display()));
+    }
+    let mut graphs = HashMap::new();
+    for file in manifest.files.iter() {
+        let graph_dir = match file.path.strip_suffix("/graph.json") {
+            Some(graph_dir) => graph_dir,
+            None => continue,
+        };
+        let info: GraphInfo = match serde_json::from_slice(&read_entry(&mut archive, package, &file.path)?) {
+            Ok(info) => info,
+            Err(e) => {
+                error!("could not parse {} in package {}: {}", file.path, package.display(), e);
+                continue;
+            }
+        };
+        let prefix = format!("{}/", graph_dir);
+        let versions = manifest
+            .files
+            .iter()
+            .filter_map(|other| other.path.strip_prefix(&prefix))
+            .filter_map(|rest| rest.strip_suffix("/version.json").or_else(|| rest.strip_suffix("/version.bin")))
+            .filter_map(|version| version.parse::<u64>().ok())
+            .collect::<std::collections::HashSet<u64>>()
+            .len() as u64;
+        let path = graph_dir.split('/').fold(package.to_path_buf(), |path, part| path.join(part));
+        info!("added graph info {}: {} with {} versions", path.display(), info.name, versions);
+        graphs.insert(info.uuid, LibraryGraphInfo { info, versions, path });
+    }
+    Ok(Library {
+        info: manifest.library,
+        path: package.to_path_buf(),
+        graphs,
+        loaded_versions: Default::default(),
+    })
+}
@@ -0,0 +1,45 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+/// The datum key that holds a node's seed.
+pub const SEED_DATUM: &str = "seed";
+
+/// Mixes bits with the SplitMix64 finalizer, so that nearby inputs give unrelated outputs.
+fn mix(mut value: u64) -> u64 {
+    value = value.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    value ^ (value >> 31)
+}
+
+///
+/// Derives a seed from a parent seed and a UUID, such as a graph seed and a node instance.
+/// This only depends on its inputs, so it is the same on every machine and in every scheduling order.
+///
+pub fn derive_seed(seed: u64, id: &uuid::Uuid) -> u64 {
+    let bytes = id.as_bytes();
+    let mut high = [0; 8];
+    let mut low = [0; 8];
+    high.copy_from_slice(&bytes[0..8]);
+    low.copy_from_slice(&bytes[8..16]);
+    mix(mix(seed ^ u64::from_le_bytes(high)) ^ u64::from_le_bytes(low))
+}
+
+///
+/// Gives back the seed of a node instance within a graph.
+/// Graphs without a seed act as though their seed is zero.
+///
+pub fn node_seed(graph_seed: Option<u64>, instance: &uuid::Uuid) -> u64 {
+    derive_seed(graph_seed.unwrap_or(0), instance)
+}
+
+/// The random number generator that nodes use.
+/// This is a fixed algorithm, unlike `StdRng`, so that its numbers do not change between platforms or rand versions.
+pub type SeededRng = ChaCha8Rng;
+
+///
+/// Creates a random number generator that always gives the same numbers for the same seed.
+///
+pub fn seeded_rng(seed: u64) -> SeededRng {
+    SeededRng::seed_from_u64(seed)
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Instant;
+
+///
+/// A single piece of work done by a node or the controller.
+/// Node computes are timed, while the controller only hands work on to nodes, so its spans mark the moment a command was handled.
+///
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct TraceSpan {
+    /// What the span is called in the trace viewer.
+    pub name: String,
+    /// What sort of work this was, such as a compute or a controller command.
+    pub category: String,
+    /// The name of the graph that the working node represents, if any.
+    pub graph: Option<String>,
+    /// The node instance that did the work, if any.
+    pub instance: Option<uuid::Uuid>,
+    /// The pin the work was done for, if any.
+    pub pin: Option<uuid::Uuid>,
+    /// Whether or not the value came out of a pin's cache, if that applies.
+    pub cache_hit: Option<bool>,
+    /// The actor that did the work.
+    pub actor: String,
+    /// Microseconds between the start of tracing and the start of this span.
+    pub start: u64,
+    /// How many microseconds the work took, or none for a span that only marks when something happened.
+    pub duration: Option<u64>,
+}
+
+///
+/// Collects trace spans while tracing is running.
+///
+pub struct Tracer {
+    /// Whether or not spans are currently being recorded.
+    pub enabled: bool,
+    /// When tracing was started.
+    pub epoch: Instant,
+    /// Every span recorded since tracing was started.
+    pub spans: Vec<TraceSpan>,
+}
+
+impl Tracer {
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            epoch: Instant::now(),
+            spans: Vec::new(),
+        }
+    }
+    /// Throws away any old spans and begins recording.
+    pub fn start(&mut self) {
+        self.spans.clear();
+        self.epoch = Instant::now();
+        self.enabled = true;
+    }
+    /// Stops recording and hands back everything that was recorded.
+    pub fn stop(&mut self) -> Vec<TraceSpan> {
+        self.enabled = false;
+        std::mem::replace(&mut self.spans, Vec::new())
+    }
+    /// Microseconds between the start of tracing and the given instant.
+    pub fn since_start(&self, instant: Instant) -> u64 {
+        match instant.checked_duration_since(self.epoch) {
+            Some(duration) => duration.as_micros() as u64,
+            None => 0,
+        }
+    }
+    pub fn record(&mut self, span: TraceSpan) {
+        if self.enabled {
+            self.spans.push(span);
+        }
+    }
+}
+
+///
+/// Converts spans into the Chrome trace event format, which can be opened in chrome://tracing or Perfetto.
+/// Every actor gets its own track, and spans without a duration are instant events on it.
+///
+pub fn to_chrome_trace(spans: &[TraceSpan]) -> serde_json::Value {
+    let mut tracks: HashMap<String, usize> = HashMap::new();
+    let mut events = Vec::new();
+    for span in spans {
+        let next_track = tracks.len() + 1;
+        let track = *tracks.entry(span.actor.clone()).or_insert_with(|| {
+            events.push(serde_json::json!({
+                "name": "thread_name",
+                "ph": "M",
+                "pid": 1,
+                "tid": next_track,
+                "args": { "name": span.actor },
+            }));
+            next_track
+        });
+        let mut event = serde_json::json!({
+            "name": span.name,
+            "cat": span.category,
+            "ph": "X",
+            "ts": span.start,
+            "pid": 1,
+            "tid": track,
+            "args": {
+                "graph": span.graph,
+                "instance": span.instance,
+                "pin": span.pin,
+                "cache_hit": span.cache_hit,
+            },
+        });
+        match span.duration {
+            Some(duration) => event["dur"] = serde_json::json!(duration),
+            None => {
+                event["ph"] = serde_json::json!("i");
+                event["s"] = serde_json::json!("t");
+            }
+        }
+        events.push(event);
+    }
+    serde_json::json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+///
+/// Writes spans to a Chrome trace event JSON file.
+///
+pub fn write_chrome_trace(spans: &[TraceSpan], path: &Path) -> std::io::Result<()> {
+    let json = serde_json::to_string(&to_chrome_trace(spans))?;
+    std::fs::write(path, json)
+}
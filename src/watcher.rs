@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use log::*;
+
+///
+/// The files that describe libraries, graphs and versions, which are the ones watched for changes along with library packages.
+///
+const WATCHED_FILES: [&str; 4] = ["lib.json", "graph.json", "version.json", "version.bin"];
+
+///
+/// Notices when library files in the catalogue search paths change, by comparing their modification times and sizes between polls.
+///
+#[derive(Default)]
+pub struct LibraryWatcher {
+    /// The last seen modification time and size of each watched file.
+    pub files: HashMap<PathBuf, (SystemTime, u64)>,
+}
+
+impl LibraryWatcher {
+    ///
+    /// Creates a watcher that counts the files as they are now as unchanged.
+    ///
+    pub fn new(paths: &[PathBuf]) -> Self {
+        Self { files: snapshot(paths) }
+    }
+
+    ///
+    /// Gives every watched file that was added, changed or removed since the last poll.
+    ///
+    pub fn poll(&mut self, paths: &[PathBuf]) -> Vec<PathBuf> {
+        let files = snapshot(paths);
+        let mut changed: Vec<PathBuf> = files
+            .iter()
+            .filter(|(path, stamp)| self.files.get(*path) != Some(*stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(self.files.keys().filter(|path| !files.contains_key(*path)).cloned());
+        changed.sort();
+        if !changed.is_empty() {
+            info!("{} library files changed", changed.len());
+        }
+        self.files = files;
+        changed
+    }
+}
+
+///
+/// Reads the modification time and size of every watched file under the given folders.
+///
+fn snapshot(paths: &[PathBuf]) -> HashMap<PathBuf, (SystemTime, u64)> {
+    let mut files = HashMap::new();
+    for path in paths.iter().filter(|path| path.is_dir()) {
+        for entry in walkdir::WalkDir::new(path).into_iter().filter_map(|entry| entry.ok()) {
+            let watched = entry
+                .file_name()
+                .to_str()
+                .map_or(false, |name| WATCHED_FILES.contains(&name))
+                || crate::package::is_package(entry.path());
+            if !watched {
+                continue;
+            }
+            match entry.metadata() {
+                Ok(metadata) => {
+                    let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                    files.insert(entry.path().to_path_buf(), (modified, metadata.len()));
+                }
+                Err(e) => warn!("could not read metadata of {}: {}", entry.path().display(), e),
+            }
+        }
+    }
+    files
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use proc_flow_lib as pf;
+use pf::asset::*;
+use pf::catalogue::*;
+use pf::graph::*;
+
+fn write_library(library_path: &std::path::Path, library_id: uuid::Uuid, graph_id: uuid::Uuid, node_id: uuid::Uuid) {
+    let graph_path = library_path.join("textured");
+    common::write_library(library_path, "Assets", library_id);
+    common::write_graph(&graph_path, "Textured", graph_id);
+    std::fs::create_dir_all(graph_path.join("1").join("textures")).unwrap();
+    std::fs::create_dir_all(library_path.join("shared")).unwrap();
+    std::fs::write(graph_path.join("1").join("textures").join("bricks.txt"), "bricks").unwrap();
+    std::fs::write(library_path.join("shared").join("palette.txt"), "palette").unwrap();
+    let mut data = std::collections::HashMap::new();
+    data.insert(String::from("path"), serde_json::json!("textures/bricks.txt"));
+    data.insert(String::from(ASSETS_DATUM), serde_json::json!(["shared/palette.txt", "missing.txt"]));
+    data.insert(String::from("label"), serde_json::json!("not an asset"));
+    let mut version = common::version(Vec::new(), Vec::new());
+    version.nodes.push(NodeInfo {
+        uuid: node_id,
+        x: 0.0,
+        y: 0.0,
+        data,
+        graph: GraphRef {
+            name: String::from("Log"),
+            uuid: uuid::Uuid::new_v4(),
+            library: None,
+            version: VersionSpec::Exact(1),
+        },
+    });
+    common::write_version(&graph_path, 1, &version);
+}
+
+#[test]
+fn resolves_assets_against_library_folders_and_packages() {
+    let root = common::temp_root("proc_flow_assets");
+    let (library_id, graph_id, node_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let library_path = root.join("loose").join("assets");
+    write_library(&library_path, library_id, graph_id, node_id);
+
+    let mut catalogue = Catalogue::new();
+    catalogue.load_libraries(CatalogueConfig::new().without_default_paths().with_path(root.join("loose")));
+    let graph_ref = catalogue.get_graph_ref(graph_id, 1).unwrap();
+    let context = AssetContext::for_graph(&catalogue, &graph_ref);
+    assert_eq!(Some(library_id), context.library);
+    assert_eq!(library_path.join("textured").join("1").join("textures").join("bricks.txt"), context.resolve("textures/bricks.txt").unwrap());
+    assert_eq!(library_path.join("shared").join("palette.txt"), context.resolve("shared/palette.txt").unwrap());
+    assert!(context.resolve("missing.txt").is_err());
+    assert!(context.resolve("../assets/shared/palette.txt").is_err());
+    assert!(context.resolve("textures/../../../shared/palette.txt").is_err());
+    assert!(std::path::Path::new("Cargo.toml").is_file());
+    assert!(context.resolve("Cargo.toml").is_err(), "assets were looked for in the working directory");
+
+    let assets = catalogue.graph_assets(&graph_ref).unwrap();
+    let paths: Vec<&str> = assets.iter().map(|asset| asset.path.as_str()).collect();
+    assert_eq!(vec!["shared/palette.txt", "missing.txt", "textures/bricks.txt"], paths);
+    assert!(assets.iter().all(|asset| asset.node == node_id));
+    let problems = catalogue.check_libraries();
+    assert!(problems.iter().any(|problem| match problem {
+        CatalogueProblem::MissingAsset { graph, path, .. } => *graph == graph_id && path == "missing.txt",
+        _ => false,
+    }));
+    assert_eq!(1, problems.iter().filter(|problem| matches!(problem, CatalogueProblem::MissingAsset { .. })).count());
+
+    let packages = root.join("packages");
+    std::fs::create_dir_all(&packages).unwrap();
+    let package = packages.join("assets.pflib");
+    pf::package::pack(&library_path, &package).unwrap();
+    catalogue.load_libraries(CatalogueConfig::new().without_default_paths().with_path(packages));
+    let context = AssetContext::for_graph(&catalogue, &graph_ref);
+    let packaged = context.resolve("textures/bricks.txt").unwrap();
+    assert!(packaged.starts_with(&package));
+    let extracted = asset_file(&packaged).unwrap();
+    assert_eq!("bricks", std::fs::read_to_string(&extracted).unwrap());
+    assert!(asset_file(&package.join("..").join("escaped.txt")).is_err());
+
+    // Extracted assets go in a folder of their own for the package, under the shared one in the temporary folder.
+    let extracted_root = std::env::temp_dir().join("proc_flow_assets");
+    let package_dir = extracted.ancestors().find(|dir| dir.parent() == Some(extracted_root.as_path())).unwrap().to_path_buf();
+    let again = asset_file(&packaged).unwrap();
+    assert_eq!(extracted, again);
+    let left: Vec<_> = std::fs::read_dir(extracted.parent().unwrap()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(vec![std::ffi::OsString::from("bricks.txt")], left);
+
+    let _ = std::fs::remove_dir_all(&root);
+    let _ = std::fs::remove_dir_all(&package_dir);
+}
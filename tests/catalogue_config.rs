@@ -0,0 +1,34 @@
+mod common;
+
+use proc_flow_lib as pf;
+use pf::catalogue::*;
+
+#[test]
+fn loads_libraries_from_configured_search_paths() {
+    let root = common::temp_root("proc_flow_config");
+    let (first, second, from_env) = (root.join("first"), root.join("second"), root.join("env"));
+    let shared_id = uuid::Uuid::new_v4();
+    let env_id = uuid::Uuid::new_v4();
+    common::write_library(&first, "First", shared_id);
+    common::write_library(&second, "Second", shared_id);
+    common::write_library(&from_env, "Env", env_id);
+
+    let config_file = root.join("catalogue.json");
+    std::fs::write(&config_file, format!("{{\"search_paths\": [{:?}], \"use_default_paths\": false}}", second.to_str().unwrap())).unwrap();
+    std::env::set_var(LIBRARY_PATH_VAR, std::env::join_paths(vec![from_env.clone()]).unwrap());
+    let config = CatalogueConfig::from_file(&config_file).unwrap().with_env().with_first_path(first.clone());
+    std::env::remove_var(LIBRARY_PATH_VAR);
+    assert_eq!(vec![first.clone(), second.clone(), from_env.clone()], config.paths());
+
+    let mut catalogue = Catalogue::new();
+    catalogue.load_libraries(config);
+    assert_eq!(3, catalogue.libraries.len());
+    assert_eq!("First", catalogue.libraries[&shared_id].info.name);
+    assert!(catalogue.libraries.contains_key(&env_id));
+
+    assert!(CatalogueConfig::new().paths().len() >= CatalogueConfig::default_paths().len());
+    assert!(CatalogueConfig::new().without_default_paths().paths().is_empty());
+    assert!(CatalogueConfig::from_file(&root.join("missing.json")).is_err());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use proc_flow_lib as pf;
+use pf::catalogue::CatalogueProblem;
+use pf::graph::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+fn write_library(path: &Path, name: &str, id: uuid::Uuid, version: u64, dependencies: serde_json::Value) {
+    let mut info = common::library_info(name, id);
+    info["version"] = serde_json::json!(version);
+    info["dependencies"] = dependencies;
+    common::write_library_info(path, &info);
+}
+
+fn write_graph(library: &Path, name: &str, id: uuid::Uuid, nodes: Vec<NodeInfo>) {
+    let graph_path = library.join(name);
+    common::write_graph(&graph_path, name, id);
+    let mut version = common::version(Vec::new(), Vec::new());
+    version.nodes = nodes;
+    common::write_version(&graph_path, 1, &version);
+}
+
+fn node(name: &str, id: uuid::Uuid, library: Option<uuid::Uuid>) -> NodeInfo {
+    NodeInfo {
+        uuid: uuid::Uuid::new_v4(),
+        x: 0.0,
+        y: 0.0,
+        data: HashMap::new(),
+        graph: GraphRef { name: String::from(name), uuid: id, library, version: VersionSpec::Exact(1) },
+    }
+}
+
+#[test]
+fn resolves_graphs_across_libraries_and_reports_problems() {
+    let root = common::temp_root("proc_flow_libraries");
+    let (a_id, b_id, c_id, d_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let (g_id, h_id, m_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+
+    write_library(
+        &root.join("a"),
+        "A",
+        a_id,
+        1,
+        serde_json::json!([{ "uuid": b_id, "name": "B", "min_version": 2 }, { "uuid": c_id, "name": "C" }]),
+    );
+    write_graph(
+        &root.join("a"),
+        "H",
+        h_id,
+        vec![node("G", g_id, None), node("G", g_id, Some(d_id)), node("M", m_id, None)],
+    );
+    write_library(&root.join("b"), "B", b_id, 1, serde_json::json!([]));
+    write_graph(&root.join("b"), "G", g_id, Vec::new());
+    write_library(&root.join("d"), "D", d_id, 1, serde_json::json!([]));
+    write_graph(&root.join("d"), "Other G", g_id, Vec::new());
+
+    let mut catalogue = pf::catalogue::Catalogue::new();
+    for lib in ["a", "b", "d"].iter().flat_map(|name| pf::library::get_libraries(root.join(name))) {
+        catalogue.libraries.insert(lib.info.uuid, lib);
+    }
+
+    let blank = GraphRef { name: String::from("G"), uuid: g_id, library: None, version: VersionSpec::Exact(1) };
+    assert_eq!(Some(b_id), catalogue.resolve_graph_ref_from(&blank, Some(a_id)).unwrap().library);
+    let explicit = GraphRef { library: Some(d_id), ..blank.clone() };
+    let resolved = catalogue.resolve_graph_ref(&explicit).unwrap();
+    assert_eq!(Some(d_id), resolved.library);
+    assert_eq!("Other G", resolved.name);
+    assert!(catalogue.resolve_graph_ref(&GraphRef { library: Some(c_id), ..blank.clone() }).is_err());
+
+    let problems = catalogue.check_libraries();
+    assert!(problems.iter().any(|problem| match problem {
+        CatalogueProblem::IncompatibleDependency { library, dependency, found } => *library == a_id && dependency.uuid == b_id && *found == 1,
+        _ => false,
+    }));
+    assert!(problems.iter().any(|problem| match problem {
+        CatalogueProblem::MissingDependency { library, dependency } => *library == a_id && dependency.uuid == c_id,
+        _ => false,
+    }));
+    assert!(problems.iter().any(|problem| match problem {
+        CatalogueProblem::ConflictingGraph { graph, libraries } => *graph == g_id && libraries.contains(&b_id) && libraries.contains(&d_id),
+        _ => false,
+    }));
+    assert!(problems.iter().any(|problem| match problem {
+        CatalogueProblem::MissingGraph { library, graph, missing, .. } => *library == a_id && *graph == h_id && missing.uuid == m_id,
+        _ => false,
+    }));
+    assert!(problems.iter().any(|problem| match problem {
+        CatalogueProblem::UndeclaredDependency { library, used, .. } => *library == a_id && *used == d_id,
+        _ => false,
+    }));
+    assert!(!problems.iter().any(|problem| match problem {
+        CatalogueProblem::UndeclaredDependency { used, .. } => *used == b_id,
+        _ => false,
+    }));
+
+    let _ = std::fs::remove_dir_all(&root);
+}
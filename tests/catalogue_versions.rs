@@ -0,0 +1,54 @@
+mod common;
+
+use proc_flow_lib as pf;
+use common::{pin, version};
+use pf::graph::*;
+
+#[test]
+fn resolves_version_specifiers_and_reports_breaking_changes() {
+    let library_path = common::temp_root("proc_flow_versions");
+    let graph_path = library_path.join("scale");
+    let graph_id = uuid::Uuid::new_v4();
+    let value_pin_id = uuid::Uuid::new_v4();
+    let offset_pin_id = uuid::Uuid::new_v4();
+    let result_pin_id = uuid::Uuid::new_v4();
+    let versions = vec![
+        version(vec![pin("Value", value_pin_id, "f64")], vec![pin("Result", result_pin_id, "f64")]),
+        version(vec![pin("Value", value_pin_id, "f64"), pin("Offset", offset_pin_id, "f64")], vec![pin("Scaled", result_pin_id, "f64")]),
+        version(vec![pin("Value", value_pin_id, "vec3"), pin("Offset", offset_pin_id, "f64")], vec![pin("Scaled", result_pin_id, "f64")]),
+    ];
+    for (number, info) in versions.iter().enumerate() {
+        common::write_version(&graph_path, number as u64 + 1, info);
+    }
+    common::write_graph(&graph_path, "Scale", graph_id);
+    common::write_library(&library_path, "Versions", uuid::Uuid::new_v4());
+
+    let mut catalogue = pf::catalogue::Catalogue::new();
+    for lib in pf::library::get_libraries(library_path.clone()) {
+        catalogue.libraries.insert(lib.info.uuid, lib);
+    }
+    assert_eq!(Ok(3), catalogue.resolve_version(graph_id, &VersionSpec::Latest));
+    assert_eq!(Ok(2), catalogue.resolve_version(graph_id, &VersionSpec::LatestCompatible(1)));
+    assert_eq!(Ok(3), catalogue.resolve_version(graph_id, &VersionSpec::LatestCompatible(3)));
+    assert!(catalogue.resolve_version(graph_id, &VersionSpec::Exact(4)).is_err());
+    assert!(catalogue.get_graph_ref(graph_id, 4).is_none());
+
+    let changes = catalogue.compare_versions(graph_id, 1, 2).unwrap();
+    assert_eq!(2, changes.len());
+    assert!(changes.iter().all(|change| !change.is_breaking()));
+    let breaking: Vec<PinChange> = catalogue.compare_versions(graph_id, 2, 3).unwrap().into_iter().filter(|change| change.is_breaking()).collect();
+    assert_eq!(1, breaking.len());
+    match &breaking[0] {
+        PinChange::Retyped(PinKind::Input, old, new) => {
+            assert_eq!(value_pin_id, old.uuid);
+            assert_eq!("vec3", new.datatype);
+        }
+        change => panic!("unexpected pin change {}", change),
+    }
+
+    let specs: Vec<VersionSpec> = serde_json::from_str("[2, \"latest\", \"^1\"]").unwrap();
+    assert_eq!(vec![VersionSpec::Exact(2), VersionSpec::Latest, VersionSpec::LatestCompatible(1)], specs);
+    assert_eq!("[2,\"latest\",\"^1\"]", serde_json::to_string(&specs).unwrap());
+
+    let _ = std::fs::remove_dir_all(&library_path);
+}
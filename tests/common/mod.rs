@@ -0,0 +1,73 @@
+// Helpers for the tests that write libraries of their own to the temporary folder.
+// Not every test uses every helper.
+#![allow(dead_code)]
+
+use proc_flow_lib as pf;
+use pf::graph::*;
+use std::path::{Path, PathBuf};
+
+///
+/// A folder within the temporary folder that no other test run uses.
+///
+pub fn temp_root(prefix: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}_{}", prefix, uuid::Uuid::new_v4()))
+}
+
+///
+/// What a lib.json holds for a library, which tests can add to before writing it.
+///
+pub fn library_info(name: &str, id: uuid::Uuid) -> serde_json::Value {
+    serde_json::json!({ "name": name, "uuid": id, "author": "test", "format": 1 })
+}
+
+///
+/// Writes the lib.json of a library, creating its folder.
+///
+pub fn write_library_info(library_path: &Path, info: &serde_json::Value) {
+    std::fs::create_dir_all(library_path).unwrap();
+    std::fs::write(library_path.join("lib.json"), info.to_string()).unwrap();
+}
+
+///
+/// Writes the lib.json of a library with nothing but a name and UUID, creating its folder.
+///
+pub fn write_library(library_path: &Path, name: &str, id: uuid::Uuid) {
+    write_library_info(library_path, &library_info(name, id));
+}
+
+///
+/// Writes the graph.json of a graph, creating its folder.
+///
+pub fn write_graph(graph_path: &Path, name: &str, id: uuid::Uuid) {
+    std::fs::create_dir_all(graph_path).unwrap();
+    std::fs::write(graph_path.join("graph.json"), serde_json::to_string(&GraphInfo { name: String::from(name), uuid: id, format: 1 }).unwrap()).unwrap();
+}
+
+///
+/// Writes a version of a graph as json, creating its folder.
+///
+pub fn write_version(graph_path: &Path, number: u64, version: &VersionInfo) {
+    let version_path = graph_path.join(format!("{}", number));
+    std::fs::create_dir_all(&version_path).unwrap();
+    std::fs::write(version_path.join("version.json"), serde_json::to_string(version).unwrap()).unwrap();
+}
+
+pub fn pin(name: &str, id: uuid::Uuid, datatype: &str) -> PinInfo {
+    PinInfo::new_basic(String::from(name), id, String::from(datatype))
+}
+
+///
+/// A version with the given pins, and no nodes or connections.
+///
+pub fn version(inputs: Vec<PinInfo>, outputs: Vec<PinInfo>) -> VersionInfo {
+    VersionInfo {
+        format: 1,
+        seed: None,
+        receives: Vec::new(),
+        sends: Vec::new(),
+        inputs,
+        outputs,
+        nodes: Vec::new(),
+        connections: Vec::new(),
+    }
+}
@@ -0,0 +1,49 @@
+use proc_flow_lib as pf;
+use pf::event::*;
+
+#[test]
+fn publishes_node_events() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let log_id = uuid::Uuid::parse_str("fd41d8ef-d10f-4499-8a90-35b73d8ff246").unwrap();
+    let log_info_input_pin_id = uuid::Uuid::parse_str("5e6ab872-5cca-4e01-8dbb-2df843102dc0").unwrap();
+    let log_info_output_pin_id = uuid::Uuid::parse_str("44a986b1-dc09-45d9-ab65-e2c0c7b6f5ce").unwrap();
+    let instance_id = uuid::Uuid::new_v4();
+    let booted = engine.subscribe(EngineEventFilter::all().with_graph(log_id).with_kind(EngineEventKind::NodeBooted));
+    let computed = engine.subscribe(EngineEventFilter::all().with_instance(instance_id).with_kind(EngineEventKind::OutputComputed));
+    let log_actor = engine.boot_graph(log_id, 1, instance_id).expect("did not get aid back from engine boot of log node");
+    engine.set_input_pin_value(log_actor.clone(), log_info_input_pin_id, Some(axiom::prelude::Message::new("testing events".to_owned())), "string".to_owned());
+    engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None).unwrap();
+
+    let event = booted.recv_timeout(std::time::Duration::from_secs(5)).expect("no boot event published");
+    assert_eq!(Some(instance_id), event.instance);
+    assert_eq!(EngineEventKind::NodeBooted, event.kind());
+
+    let event = computed.recv_timeout(std::time::Duration::from_secs(5)).expect("no compute event published");
+    match event.event {
+        EngineEventType::OutputComputed(pin, Some(value)) => {
+            assert_eq!(log_info_output_pin_id, pin);
+            assert_eq!("testing events", *value.content_as::<String>().unwrap());
+        }
+        _ => panic!("unexpected event for computed output"),
+    }
+    assert!(computed.try_recv().is_err());
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn counts_subscribers_until_they_hang_up() {
+    let mut bus = EventBus::new();
+    let subscribers = bus.subscribers();
+    assert_eq!(0, subscribers.load(std::sync::atomic::Ordering::Relaxed));
+
+    let kept = bus.subscribe(EngineEventFilter::all().with_kind(EngineEventKind::Error));
+    let dropped = bus.subscribe(EngineEventFilter::all());
+    assert_eq!(2, subscribers.load(std::sync::atomic::Ordering::Relaxed));
+
+    drop(dropped);
+    bus.publish(EngineEvent { graph: None, instance: None, actor: None, event: EngineEventType::Error(String::from("failed")) });
+    assert_eq!(1, subscribers.load(std::sync::atomic::Ordering::Relaxed));
+    assert!(kept.try_recv().is_ok());
+}
@@ -0,0 +1,105 @@
+use proc_flow_lib as pf;
+
+#[test]
+fn counts_computes() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let log_id = uuid::Uuid::parse_str("fd41d8ef-d10f-4499-8a90-35b73d8ff246").unwrap();
+    let log_info_input_pin_id = uuid::Uuid::parse_str("5e6ab872-5cca-4e01-8dbb-2df843102dc0").unwrap();
+    let log_info_output_pin_id = uuid::Uuid::parse_str("44a986b1-dc09-45d9-ab65-e2c0c7b6f5ce").unwrap();
+    let instance_id = uuid::Uuid::new_v4();
+    let log_actor = engine.boot_graph(log_id, 1, instance_id).expect("did not get aid back from engine boot of log node");
+    engine.set_input_pin_value(log_actor.clone(), log_info_input_pin_id, Some(axiom::prelude::Message::new("testing metrics".to_owned())), "string".to_owned());
+    engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None).unwrap();
+    engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None).unwrap();
+
+    let metrics = engine.metrics();
+    let log_metrics = metrics.node_types.get(&log_id).expect("no metrics for log nodes");
+    assert_eq!(2, log_metrics.computes);
+    assert_eq!(1, log_metrics.cache_hits);
+    assert_eq!(0, log_metrics.errors);
+    assert_eq!(2, log_metrics.latency.count);
+    assert_eq!(0.5, metrics.nodes.get(&instance_id).expect("no metrics for log node").cache_hit_rate());
+    assert_eq!(Some(&2), metrics.node_messages.get("ComputeOutput"));
+    assert_eq!(Some(&1), metrics.controller_messages.get("BootGraph"));
+
+    let text = metrics.to_prometheus();
+    assert!(text.contains(&format!("proc_flow_node_computes_total{{graph=\"{}\",name=\"Log\",parent_graph=\"\"}} 2", log_id)));
+    assert!(text.contains(&format!("proc_flow_node_pending_requests{{graph=\"{}\",name=\"Log\",parent_graph=\"\"}} 0", log_id)));
+    assert_eq!(0, metrics.queue_depths.get(&instance_id).expect("no queue depth for log node").depth);
+    assert!(text.contains("proc_flow_node_messages_total{command=\"InputValue\"} 1"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn exports_queue_depths_per_containing_graph() {
+    let mut metrics = pf::metrics::Metrics::new();
+    let graph = pf::graph::GraphRef {
+        name: String::from("Add"),
+        uuid: uuid::Uuid::new_v4(),
+        library: None,
+        version: pf::graph::VersionSpec::Exact(1),
+    };
+    let parent = uuid::Uuid::new_v4();
+    let (first, second) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    metrics.record_compute(&graph, Some(parent), &first, 20, false, false);
+    metrics.record_compute(&graph, None, &second, 20, true, false);
+    metrics.record_queue_depth(&graph, Some(parent), &first, 3);
+    metrics.record_queue_depth(&graph, Some(parent), &second, 2);
+    metrics.record_queue_depth(&graph, Some(parent), &first, 1);
+    assert_eq!(3, metrics.queue_depths[&first].peak);
+    assert_eq!(2, metrics.node_types[&graph.uuid].computes);
+
+    let text = metrics.to_prometheus();
+    assert!(text.contains(&format!("proc_flow_node_computes_total{{graph=\"{}\",name=\"Add\",parent_graph=\"{}\"}} 1", graph.uuid, parent)));
+    assert!(text.contains(&format!("proc_flow_node_cache_hits_total{{graph=\"{}\",name=\"Add\",parent_graph=\"\"}} 1", graph.uuid)));
+    assert!(text.contains(&format!("proc_flow_node_pending_requests{{graph=\"{}\",name=\"Add\",parent_graph=\"{}\"}} 3", graph.uuid, parent)));
+
+    metrics.remove_queue_depth(&first);
+    assert!(metrics.to_prometheus().contains(&format!("proc_flow_node_pending_requests{{graph=\"{}\",name=\"Add\",parent_graph=\"{}\"}} 2", graph.uuid, parent)));
+}
+
+#[test]
+fn counts_messages_from_many_threads() {
+    let messages = std::sync::Arc::new(pf::metrics::MessageCounters::new());
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let messages = messages.clone();
+            std::thread::spawn(move || {
+                for _ in 0..1000 {
+                    messages.node.count("ComputeOutput");
+                }
+                messages.controller.count("BootGraph");
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    let mut metrics = pf::metrics::Metrics::new();
+    messages.fold_into(&mut metrics);
+    assert_eq!(Some(&4000), metrics.node_messages.get("ComputeOutput"));
+    assert_eq!(Some(&4), metrics.controller_messages.get("BootGraph"));
+    assert!(metrics.to_prometheus().contains("proc_flow_node_messages_total{command=\"ComputeOutput\"} 4000"));
+}
+
+#[test]
+fn stops_serving_metrics_and_frees_the_port() {
+    use std::io::{Read, Write};
+    let metrics = std::sync::Arc::new(std::sync::Mutex::new(pf::metrics::Metrics::new()));
+    let messages = std::sync::Arc::new(pf::metrics::MessageCounters::new());
+    messages.controller.count("BootGraph");
+    let server = pf::metrics::serve_prometheus(metrics, messages, "127.0.0.1:0".parse().unwrap()).expect("could not serve metrics");
+    let address = server.address;
+
+    let mut stream = std::net::TcpStream::connect(address).expect("could not connect to metrics server");
+    stream.write_all(b"GET /metrics HTTP/1.1\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.contains("proc_flow_controller_messages_total{command=\"BootGraph\"} 1"));
+
+    server.stop();
+    assert!(std::net::TcpListener::bind(address).is_ok(), "metrics server still holds its port after stopping");
+}
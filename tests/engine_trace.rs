@@ -0,0 +1,58 @@
+use proc_flow_lib as pf;
+
+#[test]
+fn traces_computes() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let log_id = uuid::Uuid::parse_str("fd41d8ef-d10f-4499-8a90-35b73d8ff246").unwrap();
+    let log_info_input_pin_id = uuid::Uuid::parse_str("5e6ab872-5cca-4e01-8dbb-2df843102dc0").unwrap();
+    let log_info_output_pin_id = uuid::Uuid::parse_str("44a986b1-dc09-45d9-ab65-e2c0c7b6f5ce").unwrap();
+    engine.start_tracing();
+    let log_actor = engine.boot_graph(log_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of log node");
+    engine.set_input_pin_value(log_actor.clone(), log_info_input_pin_id, Some(axiom::prelude::Message::new("testing trace".to_owned())), "string".to_owned());
+    engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None).unwrap();
+    engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None).unwrap();
+    let spans = engine.stop_tracing();
+
+    let computes: Vec<&pf::trace::TraceSpan> = spans.iter().filter(|span| span.category == "compute").collect();
+    assert_eq!(2, computes.len());
+    assert_eq!(Some(false), computes[0].cache_hit);
+    assert_eq!(Some(true), computes[1].cache_hit);
+    assert_eq!(Some(log_info_output_pin_id), computes[0].pin);
+    assert!(computes.iter().all(|span| span.duration.is_some()));
+    assert!(spans.iter().any(|span| span.category == "controller" && span.name == "BootGraph"));
+    assert!(spans.iter().filter(|span| span.category == "controller").all(|span| span.duration.is_none()));
+
+    let path = std::env::temp_dir().join(format!("proc_flow_trace_{}.json", uuid::Uuid::new_v4()));
+    pf::trace::write_chrome_trace(&spans, &path).expect("could not write chrome trace");
+    let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let _ = std::fs::remove_file(&path);
+    let events = json["traceEvents"].as_array().expect("trace has no events");
+    assert_eq!(computes.len(), events.iter().filter(|event| event["ph"] == "X").count());
+    assert_eq!(spans.len() - computes.len(), events.iter().filter(|event| event["ph"] == "i").count());
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn writes_spans_without_a_duration_as_instant_events() {
+    let span = |category: &str, duration: Option<u64>| pf::trace::TraceSpan {
+        name: String::from(category),
+        category: String::from(category),
+        graph: None,
+        instance: None,
+        pin: None,
+        cache_hit: None,
+        actor: String::from("actor"),
+        start: 10,
+        duration,
+    };
+    let trace = pf::trace::to_chrome_trace(&[span("compute", Some(5)), span("controller", None)]);
+    let events = trace["traceEvents"].as_array().expect("trace has no events");
+    let compute = events.iter().find(|event| event["name"] == "compute").unwrap();
+    assert_eq!("X", compute["ph"]);
+    assert_eq!(5, compute["dur"]);
+    let controller = events.iter().find(|event| event["name"] == "controller").unwrap();
+    assert_eq!("i", controller["ph"]);
+    assert!(controller.get("dur").is_none());
+}
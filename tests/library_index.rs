@@ -0,0 +1,95 @@
+mod common;
+
+use proc_flow_lib as pf;
+use pf::index::*;
+
+#[test]
+fn reuses_indexed_libraries_until_their_files_change() {
+    let root = common::temp_root("proc_flow_index");
+    let search_path = root.join("libraries");
+    let library_path = search_path.join("indexed");
+    let index_path = root.join("cache").join("catalogue.index");
+    let (library_id, graph_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    common::write_library(&library_path, "Indexed", library_id);
+    common::write_graph(&library_path.join("graph"), "Before", graph_id);
+    std::fs::create_dir_all(library_path.join("graph").join("1")).unwrap();
+
+    let config = pf::catalogue::CatalogueConfig::new()
+        .without_default_paths()
+        .with_path(search_path.clone())
+        .with_index(Some(index_path.clone()));
+    let mut catalogue = pf::catalogue::Catalogue::new();
+    catalogue.load_libraries(config.clone());
+    assert_eq!("Before", catalogue.libraries[&library_id].graphs[&graph_id].info.name);
+    // The version folder has no version file in it yet, so it is not counted.
+    assert_eq!(0, catalogue.libraries[&library_id].graphs[&graph_id].versions);
+
+    // An unchanged library comes from the index, which is shown here by editing the index by hand.
+    let mut index = LibraryIndex::load(&index_path);
+    assert_eq!(vec![library_path.clone()], index.search_paths[&search_path].libraries);
+    index.libraries.get_mut(&library_path).unwrap().library.info.name = String::from("From Index");
+    index.save(&index_path).unwrap();
+    catalogue.load_libraries(config.clone());
+    assert_eq!("From Index", catalogue.libraries[&library_id].info.name);
+
+    // A changed graph.json makes the library be scanned again.
+    common::write_graph(&library_path.join("graph"), "After a longer name", graph_id);
+    catalogue.load_libraries(config.clone());
+    assert_eq!("Indexed", catalogue.libraries[&library_id].info.name);
+    assert_eq!("After a longer name", catalogue.libraries[&library_id].graphs[&graph_id].info.name);
+
+    // A broken index is rebuilt.
+    std::fs::write(&index_path, b"not an index").unwrap();
+    assert!(LibraryIndex::load(&index_path).libraries.is_empty());
+    catalogue.load_libraries(config);
+    assert_eq!(1, LibraryIndex::load(&index_path).libraries.len());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn saves_at_the_same_time_without_clashing() {
+    let root = common::temp_root("proc_flow_index");
+    let index_path = root.join("catalogue.index");
+    let saves: Vec<_> = (0..8)
+        .map(|_| {
+            let index_path = index_path.clone();
+            std::thread::spawn(move || (0..16).map(|_| LibraryIndex::new().save(&index_path)).collect::<Result<Vec<()>, String>>())
+        })
+        .collect();
+    for save in saves {
+        save.join().unwrap().expect("concurrent index save failed");
+    }
+    let left: Vec<_> = std::fs::read_dir(&root).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(vec![std::ffi::OsString::from("catalogue.index")], left);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn rescans_libraries_whose_files_change_size_within_the_same_modification_time() {
+    let root = common::temp_root("proc_flow_index");
+    let search_path = root.join("libraries");
+    let library_path = search_path.join("indexed");
+    let index_path = root.join("cache").join("catalogue.index");
+    let (library_id, graph_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    common::write_library(&library_path, "Indexed", library_id);
+    common::write_graph(&library_path.join("graph"), "Before", graph_id);
+    let graphjson = library_path.join("graph").join("graph.json");
+    let modified = std::fs::metadata(&graphjson).unwrap().modified().unwrap();
+
+    let config = pf::catalogue::CatalogueConfig::new()
+        .without_default_paths()
+        .with_path(search_path.clone())
+        .with_index(Some(index_path.clone()));
+    let mut catalogue = pf::catalogue::Catalogue::new();
+    catalogue.load_libraries(config.clone());
+    assert_eq!("Before", catalogue.libraries[&library_id].graphs[&graph_id].info.name);
+
+    common::write_graph(&library_path.join("graph"), "After a longer name", graph_id);
+    std::fs::File::options().write(true).open(&graphjson).unwrap().set_modified(modified).unwrap();
+    catalogue.load_libraries(config);
+    assert_eq!("After a longer name", catalogue.libraries[&library_id].graphs[&graph_id].info.name);
+
+    let _ = std::fs::remove_dir_all(&root);
+}
@@ -0,0 +1,112 @@
+mod common;
+
+use proc_flow_lib as pf;
+use common::version;
+use pf::graph::*;
+use sha2::Digest;
+use std::io::Write;
+
+#[test]
+fn packs_mounts_and_unpacks_libraries() {
+    let root = common::temp_root("proc_flow_package");
+    let library_path = root.join("loose");
+    let graph_path = library_path.join("graphs").join("shape");
+    let (library_id, graph_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let mut library_info = common::library_info("Packaged", library_id);
+    library_info["version"] = serde_json::json!(3);
+    common::write_library_info(&library_path, &library_info);
+    common::write_graph(&graph_path, "Shape", graph_id);
+    common::write_version(&graph_path, 1, &version(Vec::new(), Vec::new()));
+    common::write_version(&graph_path, 2, &version(vec![common::pin("Size", uuid::Uuid::new_v4(), "f64")], Vec::new()));
+    std::fs::create_dir_all(graph_path.join("2").join("assets")).unwrap();
+    std::fs::write(graph_path.join("2").join("assets").join("outline.txt"), "square").unwrap();
+
+    let search_path = root.join("packages");
+    std::fs::create_dir_all(&search_path).unwrap();
+    let package = search_path.join("packaged.pflib");
+    let manifest = pf::package::pack(&library_path, &package).unwrap();
+    assert_eq!(5, manifest.files.len());
+    assert_eq!(3, manifest.library.version);
+    assert!(pf::package::verify(&package).is_ok());
+
+    let mut catalogue = pf::catalogue::Catalogue::new();
+    catalogue.load_libraries(pf::catalogue::CatalogueConfig::new().without_default_paths().with_path(search_path.clone()));
+    let library = &catalogue.libraries[&library_id];
+    assert_eq!(package, library.path);
+    assert_eq!(vec![1, 2], pf::library::get_graph_versions(&library.graphs[&graph_id]));
+    let latest = catalogue.get_graph_version(&GraphRef { name: String::new(), uuid: graph_id, library: None, version: VersionSpec::Latest }).unwrap();
+    assert_eq!("Size", latest.inputs[0].name);
+    let asset = library.graphs[&graph_id].path.join("2").join("assets").join("outline.txt");
+    assert_eq!(b"square".to_vec(), pf::package::read_library_file(&asset).unwrap());
+
+    // The package is kept open between reads, but opened again once it is packed anew.
+    std::fs::write(graph_path.join("2").join("assets").join("outline.txt"), "rounded square").unwrap();
+    std::fs::write(graph_path.join("2").join("assets").join("fill.txt"), "solid").unwrap();
+    pf::package::pack(&library_path, &package).unwrap();
+    assert_eq!(b"rounded square".to_vec(), pf::package::read_library_file(&asset).unwrap());
+    assert!(pf::package::library_file_exists(&asset.with_file_name("fill.txt")));
+    std::fs::remove_file(graph_path.join("2").join("assets").join("fill.txt")).unwrap();
+    std::fs::write(graph_path.join("2").join("assets").join("outline.txt"), "square").unwrap();
+    pf::package::pack(&library_path, &package).unwrap();
+    assert!(!pf::package::library_file_exists(&asset.with_file_name("fill.txt")));
+
+    let unpacked = root.join("unpacked");
+    pf::package::unpack(&package, &unpacked).unwrap();
+    assert_eq!("square", std::fs::read_to_string(unpacked.join("graphs").join("shape").join("2").join("assets").join("outline.txt")).unwrap());
+
+    // A package holding a file that its manifest does not list is rejected.
+    let tampered = search_path.join("tampered.pflib");
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&tampered).unwrap());
+    for file in manifest.files.iter() {
+        writer.start_file(file.path.clone(), zip::write::FileOptions::default()).unwrap();
+        writer.write_all(&pf::package::read_library_file(&package.join(&file.path)).unwrap()).unwrap();
+    }
+    writer.start_file("extra.json", zip::write::FileOptions::default()).unwrap();
+    writer.write_all(b"{}").unwrap();
+    writer.start_file(pf::package::MANIFEST_NAME, zip::write::FileOptions::default()).unwrap();
+    writer.write_all(serde_json::to_string(&manifest).unwrap().as_bytes()).unwrap();
+    writer.finish().unwrap();
+    assert!(pf::package::verify(&tampered).is_err());
+
+    // A package listing a file outside the library is rejected, even with the right checksum.
+    let escaping = search_path.join("escaping.pflib");
+    let mut escaping_manifest = manifest.clone();
+    escaping_manifest.files.push(pf::package::PackageFile { path: String::from("../escaped.txt"), size: 2, sha256: format!("{:x}", sha2::Sha256::digest(b"{}")) });
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&escaping).unwrap());
+    for file in manifest.files.iter() {
+        writer.start_file(file.path.clone(), zip::write::FileOptions::default()).unwrap();
+        writer.write_all(&pf::package::read_library_file(&package.join(&file.path)).unwrap()).unwrap();
+    }
+    writer.start_file("../escaped.txt", zip::write::FileOptions::default()).unwrap();
+    writer.write_all(b"{}").unwrap();
+    writer.start_file(pf::package::MANIFEST_NAME, zip::write::FileOptions::default()).unwrap();
+    writer.write_all(serde_json::to_string(&escaping_manifest).unwrap().as_bytes()).unwrap();
+    writer.finish().unwrap();
+    assert!(pf::package::verify(&escaping).is_err());
+    assert!(pf::package::unpack(&escaping, &root.join("escaping").join("library")).is_err());
+    assert!(!root.join("escaping").join("escaped.txt").exists());
+
+    // A package whose lib.json says something other than its manifest is not mounted.
+    let mismatched = search_path.join("mismatched.pflib");
+    let mut renamed = library_info.clone();
+    renamed["name"] = serde_json::json!("Renamed");
+    let mut mismatched_manifest = manifest.clone();
+    let mut writer = zip::ZipWriter::new(std::fs::File::create(&mismatched).unwrap());
+    for file in mismatched_manifest.files.iter_mut() {
+        let bytes = match file.path.as_str() {
+            "lib.json" => renamed.to_string().into_bytes(),
+            _ => pf::package::read_library_file(&package.join(&file.path)).unwrap(),
+        };
+        file.size = bytes.len() as u64;
+        file.sha256 = format!("{:x}", sha2::Sha256::digest(&bytes));
+        writer.start_file(file.path.clone(), zip::write::FileOptions::default()).unwrap();
+        writer.write_all(&bytes).unwrap();
+    }
+    writer.start_file(pf::package::MANIFEST_NAME, zip::write::FileOptions::default()).unwrap();
+    writer.write_all(serde_json::to_string(&mismatched_manifest).unwrap().as_bytes()).unwrap();
+    writer.finish().unwrap();
+    assert!(pf::package::verify(&mismatched).is_ok());
+    assert!(pf::package::mount(&mismatched).is_err());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
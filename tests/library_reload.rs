@@ -0,0 +1,175 @@
+mod common;
+
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::catalogue::*;
+use pf::event::*;
+use pf::graph::*;
+
+/// The library written by these tests, which keeps its UUID when it is written again.
+const LIBRARY_ID: &str = "6f4a2c8e-3b1d-4e5f-9a7c-2d8b0e1f3a5c";
+
+fn pin(name: &str, id: uuid::Uuid) -> PinInfo {
+    common::pin(name, id, "f64")
+}
+
+///
+/// Writes a library with a single graph that passes its input straight to its output.
+///
+fn write_pass_library(root: &std::path::Path, graph_id: uuid::Uuid, inputs: Vec<PinInfo>, output: PinInfo) {
+    let graph_path = root.join("pass");
+    common::write_library(root, "Reload", uuid::Uuid::parse_str(LIBRARY_ID).unwrap());
+    common::write_graph(&graph_path, "Pass", graph_id);
+    let connection = ConnectionInfo {
+        receives: None,
+        sends: None,
+        output: Some(PinRef::new_boundary(inputs[0].uuid)),
+        input: Some(PinRef::new_boundary(output.uuid)),
+    };
+    let mut version = common::version(inputs, vec![output]);
+    version.connections.push(connection);
+    common::write_version(&graph_path, 1, &version);
+}
+
+///
+/// Writes a library with a single graph that makes a grid of fractal noise, which takes a while at large sizes.
+/// The width, height and octaves of the noise are the graph's inputs, and an extra input can be added to change the file.
+///
+fn write_slow_library(root: &std::path::Path, graph_id: uuid::Uuid, fractal_id: uuid::Uuid, extra: bool) {
+    let graph_path = root.join("slow");
+    common::write_library(root, "Reload", uuid::Uuid::parse_str(LIBRARY_ID).unwrap());
+    common::write_graph(&graph_path, "Slow", graph_id);
+    let node_id = uuid::Uuid::parse_str("0d3c1f5e-7a2b-4c9d-8e6f-1b2a3c4d5e6f").unwrap();
+    let boundary = [
+        ("Width", "3d1174f1-cc27-47b6-91c1-e1dcc81d06a0"),
+        ("Height", "126bc9cb-96df-4bfa-b449-549ba9a669a7"),
+        ("Octaves", "7f66fee9-01ee-49af-b762-dfe92cf80bb9"),
+    ];
+    let mut inputs: Vec<PinInfo> = boundary.iter().map(|(name, id)| common::pin(name, uuid::Uuid::parse_str(id).unwrap(), "u64")).collect();
+    let mut connections: Vec<ConnectionInfo> = inputs
+        .iter()
+        .map(|input| ConnectionInfo {
+            receives: None,
+            sends: None,
+            output: Some(PinRef::new_boundary(input.uuid)),
+            input: Some(PinRef::new_pin(node_id, input.uuid)),
+        })
+        .collect();
+    if extra {
+        inputs.push(pin("Extra", uuid::Uuid::new_v4()));
+    }
+    let grid = common::pin("Grid", uuid::Uuid::parse_str("9d837344-3bbb-4cdf-b8be-b307fcf428eb").unwrap(), "noise_grid");
+    connections.push(ConnectionInfo {
+        receives: None,
+        sends: None,
+        output: Some(PinRef::new_pin(node_id, grid.uuid)),
+        input: Some(PinRef::new_boundary(grid.uuid)),
+    });
+    let mut version = common::version(inputs, vec![grid]);
+    version.nodes.push(NodeInfo {
+        uuid: node_id,
+        x: 0.0,
+        y: 0.0,
+        data: std::collections::HashMap::new(),
+        graph: GraphRef {
+            name: String::from("Fractal Noise"),
+            uuid: fractal_id,
+            library: uuid::Uuid::parse_str("b0fa443c-20d0-4c2a-acf9-76c63af3cbed").ok(),
+            version: VersionSpec::Exact(1),
+        },
+    });
+    version.connections = connections;
+    common::write_version(&graph_path, 1, &version);
+}
+
+#[test]
+fn reloads_changed_library_files() {
+    let root = common::temp_root("proc_flow_reload");
+    let graph_id = uuid::Uuid::new_v4();
+    let (value_id, extra_id, result_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    write_pass_library(&root, graph_id, vec![pin("Value", value_id)], pin("Result", result_id));
+
+    let mut catalogue = Catalogue::new();
+    catalogue.load_libraries(CatalogueConfig::new().without_default_paths().with_path(root.clone()));
+    let paths = catalogue.config.paths();
+    let mut watcher = pf::watcher::LibraryWatcher::new(&paths);
+    assert!(watcher.poll(&paths).is_empty());
+
+    write_pass_library(&root, graph_id, vec![pin("Value", value_id), pin("Extra", extra_id)], pin("Result", result_id));
+    let changed = watcher.poll(&paths);
+    assert!(changed.iter().any(|file| file.ends_with("version.json")));
+    assert_eq!(vec![graph_id], catalogue.reload(&changed));
+    let graph_ref = catalogue.get_graph_ref(graph_id, 1).unwrap();
+    assert_eq!(2, catalogue.get_graph_version(&graph_ref).unwrap().inputs.len());
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn refreshes_running_nodes_when_their_graph_changes() {
+    let root = common::temp_root("proc_flow_reload");
+    let graph_id = uuid::Uuid::new_v4();
+    let (value_id, extra_id, result_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    write_pass_library(&root, graph_id, vec![pin("Value", value_id)], pin("Result", result_id));
+
+    let mut engine = pf::engine::Engine::with_config(CatalogueConfig::new().without_default_paths().with_path(root.clone()));
+    let instance_id = uuid::Uuid::new_v4();
+    let refreshed = engine.subscribe(EngineEventFilter::all().with_instance(instance_id).with_kind(EngineEventKind::PinsRefreshed));
+    let graph_actor = engine.boot_graph(graph_id, 1, instance_id).expect("did not get aid back from engine boot of pass graph");
+    engine.set_input_pin_value(graph_actor.clone(), value_id, Some(Message::new(2.0)), "f64".to_owned());
+    let watch = engine.watch_libraries(std::time::Duration::from_millis(50));
+
+    write_pass_library(&root, graph_id, vec![pin("Value", value_id), pin("Extra", extra_id)], pin("Result", result_id));
+    refreshed.recv_timeout(std::time::Duration::from_secs(5)).expect("pass graph did not refresh its pins");
+    watch.stop();
+    let result = engine.compute_output_pin_value(graph_actor.clone(), result_id, None).unwrap().expect("retrieved empty result from graph");
+    assert_eq!(2.0, *result.content_as::<f64>().expect("result was not a f64"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn stops_nodes_from_the_previous_version_once_a_compute_finishes() {
+    let root = common::temp_root("proc_flow_reload");
+    let graph_id = uuid::Uuid::new_v4();
+    let fractal_id = uuid::Uuid::parse_str("c9693e01-0c9c-4428-97fc-c2cff76ac94c").unwrap();
+    let grid_id = uuid::Uuid::parse_str("9d837344-3bbb-4cdf-b8be-b307fcf428eb").unwrap();
+    write_slow_library(&root, graph_id, fractal_id, false);
+
+    let mut engine = pf::engine::Engine::with_config(CatalogueConfig::new().without_default_paths().with_path(root.clone()));
+    let instance_id = uuid::Uuid::new_v4();
+    let refreshed = engine.subscribe(EngineEventFilter::all().with_instance(instance_id).with_kind(EngineEventKind::PinsRefreshed));
+    let stopped = engine.subscribe(EngineEventFilter::all().with_graph(fractal_id).with_kind(EngineEventKind::NodeStopped));
+    let graph_actor = engine.boot_graph(graph_id, 1, instance_id).expect("did not get aid back from engine boot of slow graph");
+    // Idle nodes are left running for longer than the test, so only the refresh stops them.
+    engine.update_datum(graph_actor.clone(), pf::nodes::meta::graph::IDLE_TIMEOUT_DATUM.to_owned(), serde_json::json!(600.0));
+    for (id, value) in [
+        ("3d1174f1-cc27-47b6-91c1-e1dcc81d06a0", 4096u64),
+        ("126bc9cb-96df-4bfa-b449-549ba9a669a7", 4096u64),
+        ("7f66fee9-01ee-49af-b762-dfe92cf80bb9", 8u64),
+    ] {
+        engine.set_input_pin_value(graph_actor.clone(), uuid::Uuid::parse_str(id).unwrap(), Some(Message::new(value)), "u64".to_owned());
+    }
+    let watch = engine.watch_libraries(std::time::Duration::from_millis(20));
+
+    // The graph changes while the noise is still being made.
+    let changer = {
+        let root = root.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            write_slow_library(&root, graph_id, fractal_id, true);
+        })
+    };
+    let grid = engine.compute_output_pin_value(graph_actor.clone(), grid_id, None).unwrap();
+    assert!(grid.is_some(), "retrieved empty grid from graph");
+    changer.join().unwrap();
+    refreshed.recv_timeout(std::time::Duration::from_secs(5)).expect("slow graph did not refresh its pins");
+    stopped.recv_timeout(std::time::Duration::from_secs(5)).expect("slow graph did not stop its fractal node from the previous version");
+    watch.stop();
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+    let _ = std::fs::remove_dir_all(&root);
+}
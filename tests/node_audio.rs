@@ -0,0 +1,55 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::nodes::audio::AudioBuffer;
+
+#[test]
+fn renders_filtered_oscillator_to_wav() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let sine_id = uuid::Uuid::parse_str("aa8946a0-8efd-43c2-88e9-1717ca44f85f").unwrap();
+    let frequency_pin_id = uuid::Uuid::parse_str("e8332c90-58b0-4903-94d7-f04d4a9d1207").unwrap();
+    let duration_pin_id = uuid::Uuid::parse_str("757f68fa-b641-40bd-9449-b29ef88b1840").unwrap();
+    let oscillator_audio_pin_id = uuid::Uuid::parse_str("9c740a3d-08b8-44ac-b617-018ab1f8d66a").unwrap();
+    let low_pass_id = uuid::Uuid::parse_str("c0685742-d186-4560-ae7f-d3b86ff4ca2e").unwrap();
+    let filter_audio_pin_id = uuid::Uuid::parse_str("da7078db-d4d2-4b47-af52-27febccc8503").unwrap();
+    let cutoff_pin_id = uuid::Uuid::parse_str("b76ebc57-ed04-4375-9323-5306c159a9cb").unwrap();
+    let filtered_audio_pin_id = uuid::Uuid::parse_str("14053f58-9eb6-49f8-b7d2-670e2bddf1d4").unwrap();
+    let render_id = uuid::Uuid::parse_str("693cbadd-b90e-4bd1-a81e-8de7bf31dcbe").unwrap();
+    let render_audio_pin_id = uuid::Uuid::parse_str("4b053e16-64ff-4a8c-9454-c5ba669ac30c").unwrap();
+    let path_pin_id = uuid::Uuid::parse_str("c74ad962-befa-4d3a-8232-5016ad888577").unwrap();
+    let seconds_pin_id = uuid::Uuid::parse_str("85d6ceb6-7863-4916-87c9-350c063682b9").unwrap();
+    let sample_rate_pin_id = uuid::Uuid::parse_str("ec3e81bd-8c37-4dc3-b7b1-f4f3729f8ece").unwrap();
+    let rendered_path_pin_id = uuid::Uuid::parse_str("fa3047c5-ff53-4407-8a92-068fdcdc96e6").unwrap();
+
+    let sine_actor = engine.boot_graph(sine_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of sine node");
+    engine.set_input_pin_value(sine_actor.clone(), frequency_pin_id, Some(Message::new(440.0f64)), "f64".to_owned());
+    engine.set_input_pin_value(sine_actor.clone(), duration_pin_id, Some(Message::new(1.0f64)), "f64".to_owned());
+    let sine = engine.compute_output_pin_value(sine_actor.clone(), oscillator_audio_pin_id, None).unwrap().expect("retrieved empty sine");
+    let sine = (*sine.content_as::<AudioBuffer>().expect("sine was not audio")).clone();
+    assert_eq!(44100, sine.frames());
+
+    let filter_actor = engine.boot_graph(low_pass_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of filter node");
+    engine.set_input_pin_value(filter_actor.clone(), filter_audio_pin_id, Some(Message::new(sine)), "audio".to_owned());
+    engine.set_input_pin_value(filter_actor.clone(), cutoff_pin_id, Some(Message::new(5000.0f64)), "f64".to_owned());
+    let filtered = engine.compute_output_pin_value(filter_actor.clone(), filtered_audio_pin_id, None).unwrap().expect("retrieved empty filtered audio");
+    let filtered = (*filtered.content_as::<AudioBuffer>().expect("filtered audio was not audio")).clone();
+
+    let path = std::env::temp_dir().join(format!("proc_flow_audio_{}.wav", uuid::Uuid::new_v4()));
+    let render_actor = engine.boot_graph(render_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of render node");
+    engine.set_input_pin_value(render_actor.clone(), render_audio_pin_id, Some(Message::new(filtered)), "audio".to_owned());
+    engine.set_input_pin_value(render_actor.clone(), path_pin_id, Some(Message::new(path.to_string_lossy().into_owned())), "string".to_owned());
+    engine.set_input_pin_value(render_actor.clone(), seconds_pin_id, Some(Message::new(0.5f64)), "f64".to_owned());
+    engine.set_input_pin_value(render_actor.clone(), sample_rate_pin_id, Some(Message::new(22050u64)), "u64".to_owned());
+    let rendered = engine.compute_output_pin_value(render_actor.clone(), rendered_path_pin_id, None).unwrap().expect("retrieved empty render path");
+    assert_eq!(path.to_string_lossy(), rendered.content_as::<String>().expect("render path was not a string").as_str());
+
+    let wav = AudioBuffer::load_wav(&path).expect("could not load rendered audio");
+    assert_eq!(22050, wav.sample_rate);
+    assert_eq!(1, wav.channels);
+    assert_eq!(11025, wav.frames());
+    let peak = wav.samples.iter().fold(0.0f32, |peak, sample| peak.max(sample.abs()));
+    assert!((peak - 0.5).abs() < 0.05, "peak of {} was not near the sine amplitude", peak);
+    let _ = std::fs::remove_file(&path);
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
@@ -0,0 +1,52 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::nodes::color::{Color, Gradient};
+use pf::nodes::image::Image;
+
+#[test]
+fn colorizes_heightmap_with_palette_gradient() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let hex_id = uuid::Uuid::parse_str("6e09d2c4-2963-4d77-859b-fa500c31f6f5").unwrap();
+    let hex_pin_id = uuid::Uuid::parse_str("8d003a86-e7d7-41d4-a5b3-2211e7d81744").unwrap();
+    let constructed_color_pin_id = uuid::Uuid::parse_str("faff9fe1-7e7a-4f3b-9739-165bfa716524").unwrap();
+    let complementary_id = uuid::Uuid::parse_str("653103b5-97bd-41ff-9dc5-f4ebe18e2068").unwrap();
+    let palette_color_pin_id = uuid::Uuid::parse_str("d143d70c-3fba-4a9f-8cda-6c26570a2757").unwrap();
+    let output_palette_pin_id = uuid::Uuid::parse_str("db4e3173-5460-438b-89dd-0f9a547bbff1").unwrap();
+    let gradient_id = uuid::Uuid::parse_str("25c36d30-f770-41e8-aea8-ae3a3e1b060d").unwrap();
+    let palette_pin_id = uuid::Uuid::parse_str("db2dd2e4-32d7-4a52-b14e-e83ba39a6c5d").unwrap();
+    let output_gradient_pin_id = uuid::Uuid::parse_str("eeadfe80-783e-40d7-b051-5ae563328f36").unwrap();
+    let gradient_map_id = uuid::Uuid::parse_str("0cce16a8-e7c2-4387-9f7a-720a775b5a95").unwrap();
+    let gradient_pin_id = uuid::Uuid::parse_str("8849fda5-89f1-4a07-8fd6-bf73c92f2d67").unwrap();
+    let image_pin_id = uuid::Uuid::parse_str("a00a3ba1-e0a8-4793-bf16-2f181ec84828").unwrap();
+    let output_image_pin_id = uuid::Uuid::parse_str("c20c396c-09b2-4d73-bc3f-c36e564183de").unwrap();
+
+    let hex_actor = engine.boot_graph(hex_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of hex node");
+    engine.set_input_pin_value(hex_actor.clone(), hex_pin_id, Some(Message::new(String::from("#ff8000"))), "string".to_owned());
+    let orange = engine.compute_output_pin_value(hex_actor.clone(), constructed_color_pin_id, None).unwrap().expect("retrieved empty color");
+    let orange = *orange.content_as::<Color>().expect("hex color was not a color");
+    assert_eq!("#ff8000", orange.to_hex());
+
+    let complementary_actor = engine.boot_graph(complementary_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of palette node");
+    engine.set_input_pin_value(complementary_actor.clone(), palette_color_pin_id, Some(Message::new(orange)), "color".to_owned());
+    let palette = engine.compute_output_pin_value(complementary_actor.clone(), output_palette_pin_id, None).unwrap().expect("retrieved empty palette");
+    let palette = (*palette.content_as::<Vec<Color>>().expect("palette was not colors")).clone();
+    assert_eq!(vec!["#ff8000", "#007fff"], palette.iter().map(Color::to_hex).collect::<Vec<_>>());
+
+    let gradient_actor = engine.boot_graph(gradient_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of gradient node");
+    engine.set_input_pin_value(gradient_actor.clone(), palette_pin_id, Some(Message::new(palette)), "palette".to_owned());
+    let gradient = engine.compute_output_pin_value(gradient_actor.clone(), output_gradient_pin_id, None).unwrap().expect("retrieved empty gradient");
+    let gradient = (*gradient.content_as::<Gradient>().expect("gradient was not a gradient")).clone();
+
+    let mut heightmap = Image::filled(2, 1, [0.0, 0.0, 0.0, 1.0]).unwrap();
+    heightmap.set(1, 0, [1.0, 1.0, 1.0, 1.0]);
+    let map_actor = engine.boot_graph(gradient_map_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of gradient map node");
+    engine.set_input_pin_value(map_actor.clone(), gradient_pin_id, Some(Message::new(gradient)), "gradient".to_owned());
+    engine.set_input_pin_value(map_actor.clone(), image_pin_id, Some(Message::new(heightmap)), "image".to_owned());
+    let colored = engine.compute_output_pin_value(map_actor.clone(), output_image_pin_id, None).unwrap().expect("retrieved empty image");
+    let colored = colored.content_as::<Image>().expect("gradient map was not an image");
+    assert_eq!("#ff8000", Color::from_pixel(colored.get(0, 0)).to_hex());
+    assert_eq!("#007fff", Color::from_pixel(colored.get(1, 0)).to_hex());
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
@@ -0,0 +1,133 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::event::*;
+
+#[test]
+fn passes_loop_iterations_through_gate() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let loop_id = uuid::Uuid::parse_str("25f60946-1af3-47b0-902a-89a78727fc09").unwrap();
+    let run_pin_id = uuid::Uuid::parse_str("0a886c43-6557-4dd2-a0fc-2f608dc8ad24").unwrap();
+    let index_pin_id = uuid::Uuid::parse_str("cdbfbcf2-56ee-4cc0-b261-ffb1781df369").unwrap();
+    let gate_id = uuid::Uuid::parse_str("80998e1b-02d0-4668-b0ae-11fc92b40b55").unwrap();
+    let enter_pin_id = uuid::Uuid::parse_str("7c38dd8b-6a68-488a-8d43-4fe2620dd8b1").unwrap();
+    let exit_pin_id = uuid::Uuid::parse_str("8c584f71-aeb6-4ddb-959d-5265c0a05a60").unwrap();
+    let loop_instance_id = uuid::Uuid::new_v4();
+    let gate_instance_id = uuid::Uuid::new_v4();
+    let exited = engine.subscribe(EngineEventFilter::all().with_instance(gate_instance_id).with_kind(EngineEventKind::MessageSent));
+    let loop_actor = engine.boot_graph(loop_id, 1, loop_instance_id).expect("did not get aid back from engine boot of loop node");
+    let gate_actor = engine.boot_graph(gate_id, 1, gate_instance_id).expect("did not get aid back from engine boot of gate node");
+    assert!(engine.link_pins(loop_actor.clone(), index_pin_id, gate_actor.clone(), pf::graph::PinRef::new_pin(gate_instance_id, enter_pin_id)));
+    engine.send_value(loop_actor.clone(), run_pin_id, Some(Message::new(3u64)));
+    for expected in 0..3u64 {
+        let event = exited.recv_timeout(std::time::Duration::from_secs(5)).expect("gate did not pass on a loop iteration");
+        match event.event {
+            EngineEventType::MessageSent(pin, Some(value)) => {
+                assert_eq!(exit_pin_id, pin);
+                assert_eq!(expected, *value.content_as::<u64>().expect("loop index was not a u64"));
+            }
+            _ => panic!("unexpected event for loop iteration"),
+        }
+    }
+    // Counts too large to run within the actor are rejected instead of stalling it.
+    let errors = engine.subscribe(EngineEventFilter::all().with_instance(loop_instance_id).with_kind(EngineEventKind::Error));
+    engine.send_value(loop_actor.clone(), run_pin_id, Some(Message::new(pf::nodes::flow::repeat::MAX_ITERATIONS + 1)));
+    errors.recv_timeout(std::time::Duration::from_secs(5)).expect("loop did not reject a count over the most iterations");
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn branches_and_selects_values() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let branch_id = uuid::Uuid::parse_str("0914cee4-9617-40b9-9808-9c9c0b20f4ed").unwrap();
+    let condition_receive_pin_id = uuid::Uuid::parse_str("1d70c396-3a03-4eab-be66-bb72b6d2c37b").unwrap();
+    let branch_value_pin_id = uuid::Uuid::parse_str("768962c8-c590-4c2e-8b3a-27710ed3a30d").unwrap();
+    let true_pin_id = uuid::Uuid::parse_str("e8d83ebd-af5f-4e2c-bdd4-5ae0e1a6a66c").unwrap();
+    let select_if_id = uuid::Uuid::parse_str("50fc7ba7-da96-41b0-9b89-6cea20e5d9e8").unwrap();
+    let condition_pin_id = uuid::Uuid::parse_str("bf8f1a03-93cf-41ef-8655-a102f49adfa3").unwrap();
+    let if_true_pin_id = uuid::Uuid::parse_str("074997a3-8328-4b74-b9d1-cf6b78bdabd5").unwrap();
+    let if_false_pin_id = uuid::Uuid::parse_str("4b0eb04c-ec5a-438e-8b3d-845794cdf90f").unwrap();
+    let selected_pin_id = uuid::Uuid::parse_str("11b4bb99-df9e-4967-8806-916198ab8948").unwrap();
+
+    let branch_instance_id = uuid::Uuid::new_v4();
+    let sent = engine.subscribe(EngineEventFilter::all().with_instance(branch_instance_id).with_kind(EngineEventKind::MessageSent));
+    let branch_actor = engine.boot_graph(branch_id, 1, branch_instance_id).expect("did not get aid back from engine boot of branch node");
+    engine.set_input_pin_value(branch_actor.clone(), branch_value_pin_id, Some(Message::new(String::from("high ground"))), "string".to_owned());
+    engine.send_value(branch_actor.clone(), condition_receive_pin_id, Some(Message::new(true)));
+    let event = sent.recv_timeout(std::time::Duration::from_secs(5)).expect("branch did not send anything");
+    match event.event {
+        EngineEventType::MessageSent(pin, Some(value)) => {
+            assert_eq!(true_pin_id, pin);
+            assert_eq!("high ground", *value.content_as::<String>().expect("branch value was not a string"));
+        }
+        _ => panic!("unexpected event for branch"),
+    }
+
+    let select_actor = engine.boot_graph(select_if_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of select node");
+    engine.set_input_pin_value(select_actor.clone(), condition_pin_id, Some(Message::new(false)), "bool".to_owned());
+    engine.set_input_pin_value(select_actor.clone(), if_true_pin_id, Some(Message::new(1.0f64)), "f64".to_owned());
+    engine.set_input_pin_value(select_actor.clone(), if_false_pin_id, Some(Message::new(2.0f64)), "f64".to_owned());
+    let selected = engine.compute_output_pin_value(select_actor.clone(), selected_pin_id, None).unwrap().expect("retrieved empty selection");
+    assert_eq!(2.0, *selected.content_as::<f64>().expect("selection was not a number"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn pulls_linked_inputs_before_receiving() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let select_if_id = uuid::Uuid::parse_str("50fc7ba7-da96-41b0-9b89-6cea20e5d9e8").unwrap();
+    let condition_pin_id = uuid::Uuid::parse_str("bf8f1a03-93cf-41ef-8655-a102f49adfa3").unwrap();
+    let if_true_pin_id = uuid::Uuid::parse_str("074997a3-8328-4b74-b9d1-cf6b78bdabd5").unwrap();
+    let selected_pin_id = uuid::Uuid::parse_str("11b4bb99-df9e-4967-8806-916198ab8948").unwrap();
+    let loop_id = uuid::Uuid::parse_str("25f60946-1af3-47b0-902a-89a78727fc09").unwrap();
+    let run_pin_id = uuid::Uuid::parse_str("0a886c43-6557-4dd2-a0fc-2f608dc8ad24").unwrap();
+    let count_pin_id = uuid::Uuid::parse_str("5ec1d59e-54af-4958-9107-36fe14ebd9f2").unwrap();
+    let done_pin_id = uuid::Uuid::parse_str("25158796-31ed-4aaf-a24c-b70f426b9f87").unwrap();
+    let branch_id = uuid::Uuid::parse_str("0914cee4-9617-40b9-9808-9c9c0b20f4ed").unwrap();
+    let condition_receive_pin_id = uuid::Uuid::parse_str("1d70c396-3a03-4eab-be66-bb72b6d2c37b").unwrap();
+    let branch_value_pin_id = uuid::Uuid::parse_str("768962c8-c590-4c2e-8b3a-27710ed3a30d").unwrap();
+    let true_pin_id = uuid::Uuid::parse_str("e8d83ebd-af5f-4e2c-bdd4-5ae0e1a6a66c").unwrap();
+
+    let select_instance_id = uuid::Uuid::new_v4();
+    let select_actor = engine.boot_graph(select_if_id, 1, select_instance_id).expect("did not get aid back from engine boot of select node");
+    engine.set_input_pin_value(select_actor.clone(), condition_pin_id, Some(Message::new(true)), "bool".to_owned());
+    engine.set_input_pin_value(select_actor.clone(), if_true_pin_id, Some(Message::new(2u64)), "u64".to_owned());
+    let selected = pf::graph::PinRef::new_pin(select_instance_id, selected_pin_id);
+
+    // The loop runs as many times as the count linked from the select node, with no count sent along.
+    let loop_instance_id = uuid::Uuid::new_v4();
+    let done = engine.subscribe(EngineEventFilter::all().with_instance(loop_instance_id).with_kind(EngineEventKind::MessageSent));
+    let loop_actor = engine.boot_graph(loop_id, 1, loop_instance_id).expect("did not get aid back from engine boot of loop node");
+    assert!(engine.link_pins(loop_actor.clone(), count_pin_id, select_actor.clone(), selected.clone()));
+    engine.send_value(loop_actor.clone(), run_pin_id, None);
+    let mut count = None;
+    while count.is_none() {
+        let event = done.recv_timeout(std::time::Duration::from_secs(5)).expect("loop did not finish");
+        if let EngineEventType::MessageSent(pin, Some(value)) = event.event {
+            if pin == done_pin_id {
+                count = value.content_as::<u64>().map(|count| *count);
+            }
+        }
+    }
+    assert_eq!(Some(2), count);
+
+    // The branch passes on the value linked from the select node.
+    let branch_instance_id = uuid::Uuid::new_v4();
+    let sent = engine.subscribe(EngineEventFilter::all().with_instance(branch_instance_id).with_kind(EngineEventKind::MessageSent));
+    let branch_actor = engine.boot_graph(branch_id, 1, branch_instance_id).expect("did not get aid back from engine boot of branch node");
+    assert!(engine.link_pins(branch_actor.clone(), branch_value_pin_id, select_actor.clone(), selected));
+    engine.send_value(branch_actor.clone(), condition_receive_pin_id, Some(Message::new(true)));
+    let event = sent.recv_timeout(std::time::Duration::from_secs(5)).expect("branch did not send anything");
+    match event.event {
+        EngineEventType::MessageSent(pin, Some(value)) => {
+            assert_eq!(true_pin_id, pin);
+            assert_eq!(2u64, *value.content_as::<u64>().expect("branch value was not a u64"));
+        }
+        _ => panic!("unexpected event for branch"),
+    }
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
@@ -0,0 +1,64 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::nodes::geometry::Mesh;
+use pf::nodes::image::Image;
+
+#[test]
+fn saves_and_reloads_gltf_meshes() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let cube_id = uuid::Uuid::parse_str("3d5f69ae-d1a3-4028-bb8c-21b9e0849c6f").unwrap();
+    let size_pin_id = uuid::Uuid::parse_str("b4fdff13-6ec1-416c-844a-2d85b64d6f86").unwrap();
+    let cube_mesh_pin_id = uuid::Uuid::parse_str("0193f116-230d-4bc9-b9bc-3f096635a91c").unwrap();
+    let load_id = uuid::Uuid::parse_str("9c7a57f7-bca1-4488-81d6-b7375c15f518").unwrap();
+    let save_id = uuid::Uuid::parse_str("530bb9c8-7098-4ed0-beb7-873079fa4d33").unwrap();
+    let path_pin_id = uuid::Uuid::parse_str("f0a2c8ab-3192-4528-861b-eb6aa59f4a9c").unwrap();
+    let mesh_pin_id = uuid::Uuid::parse_str("efa05aed-6366-440f-addb-fa7efd3a7a0f").unwrap();
+    let loaded_mesh_pin_id = uuid::Uuid::parse_str("5f824da5-f9b0-4363-8c57-87002a81b096").unwrap();
+    let saved_path_pin_id = uuid::Uuid::parse_str("9c3fbd05-d3a0-46ff-82e9-b198fd651341").unwrap();
+
+    let cube_actor = engine.boot_graph(cube_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of cube node");
+    engine.set_input_pin_value(cube_actor.clone(), size_pin_id, Some(Message::new(2.0f64)), "f64".to_owned());
+    let cube = engine.compute_output_pin_value(cube_actor.clone(), cube_mesh_pin_id, None).unwrap().expect("retrieved empty cube");
+    let cube = (*cube.content_as::<Mesh>().expect("cube was not a mesh")).clone();
+    assert_eq!((24, 12), (cube.vertex_count(), cube.triangle_count()));
+    assert_eq!(([-1.0; 3], [1.0; 3]), cube.bounds());
+
+    for extension in ["glb", "gltf"].iter() {
+        let path = std::env::temp_dir().join(format!("proc_flow_mesh_{}.{}", uuid::Uuid::new_v4(), extension));
+        let path_string = path.to_string_lossy().into_owned();
+
+        let save_actor = engine.boot_graph(save_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of save mesh node");
+        engine.set_input_pin_value(save_actor.clone(), mesh_pin_id, Some(Message::new(cube.clone())), "mesh".to_owned());
+        engine.set_input_pin_value(save_actor.clone(), path_pin_id, Some(Message::new(path_string.clone())), "string".to_owned());
+        let saved = engine.compute_output_pin_value(save_actor.clone(), saved_path_pin_id, None).unwrap().expect("retrieved empty saved path");
+        assert_eq!(path_string, *saved.content_as::<String>().expect("saved path was not a string"));
+
+        let load_actor = engine.boot_graph(load_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of load mesh node");
+        engine.set_input_pin_value(load_actor.clone(), path_pin_id, Some(Message::new(path_string.clone())), "string".to_owned());
+        let loaded = engine.compute_output_pin_value(load_actor.clone(), loaded_mesh_pin_id, None).unwrap().expect("retrieved empty loaded mesh");
+        assert_eq!(cube, *loaded.content_as::<Mesh>().expect("loaded mesh was not a mesh"));
+
+        std::fs::remove_file(&path).expect("could not remove saved mesh");
+        if *extension == "gltf" {
+            std::fs::remove_file(path.with_extension("bin")).expect("could not remove saved mesh buffer");
+        }
+    }
+
+    let terrain_id = uuid::Uuid::parse_str("c0a87009-2d40-42be-ba3c-997a0952bab9").unwrap();
+    let heightmap_pin_id = uuid::Uuid::parse_str("bab458f1-c036-4ad1-adc8-e08a47cc368b").unwrap();
+    let height_pin_id = uuid::Uuid::parse_str("14360f3a-58be-4efb-9fdc-0ae9d07e78c4").unwrap();
+    let terrain_mesh_pin_id = uuid::Uuid::parse_str("19c70073-cc92-426b-8ff0-67047c4c1216").unwrap();
+    let mut heightmap = Image::filled(3, 3, [0.0, 0.0, 0.0, 1.0]).unwrap();
+    heightmap.set(1, 1, [1.0, 1.0, 1.0, 1.0]);
+    let terrain_actor = engine.boot_graph(terrain_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of terrain node");
+    engine.set_input_pin_value(terrain_actor.clone(), heightmap_pin_id, Some(Message::new(heightmap)), "image".to_owned());
+    engine.set_input_pin_value(terrain_actor.clone(), height_pin_id, Some(Message::new(4.0f64)), "f64".to_owned());
+    let terrain = engine.compute_output_pin_value(terrain_actor.clone(), terrain_mesh_pin_id, None).unwrap().expect("retrieved empty terrain");
+    let terrain = terrain.content_as::<Mesh>().expect("terrain was not a mesh");
+    assert_eq!((9, 8), (terrain.vertex_count(), terrain.triangle_count()));
+    assert_eq!([0.0, 4.0, 0.0], terrain.positions[4]);
+    assert_eq!([0.0, 1.0, 0.0], terrain.normals[4]);
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
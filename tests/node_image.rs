@@ -0,0 +1,64 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::nodes::image::Image;
+
+#[test]
+fn saves_loads_and_derives_normals() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let load_id = uuid::Uuid::parse_str("5603cbaf-8854-4339-a31b-53951893a853").unwrap();
+    let save_id = uuid::Uuid::parse_str("274222df-f14c-41e8-9ab1-e58bbdf5134f").unwrap();
+    let path_pin_id = uuid::Uuid::parse_str("6900c419-9048-475b-9f43-8102d01fae45").unwrap();
+    let image_pin_id = uuid::Uuid::parse_str("568bd5fd-d2f5-48af-9b3b-ede2b26f7f9f").unwrap();
+    let loaded_image_pin_id = uuid::Uuid::parse_str("21d044e4-3990-4216-a8bb-c8e3b7903c26").unwrap();
+    let saved_path_pin_id = uuid::Uuid::parse_str("22f4c637-4185-4626-8321-8c7e31d1c839").unwrap();
+    let normal_id = uuid::Uuid::parse_str("6c88477e-058b-46f3-b762-2c61d585b126").unwrap();
+    let height_pin_id = uuid::Uuid::parse_str("078beec9-0354-4858-be58-646e9574b459").unwrap();
+    let normal_map_pin_id = uuid::Uuid::parse_str("6127b999-b0ce-4793-bf0a-62a883f0d421").unwrap();
+
+    let mut gradient = Image::filled(8, 4, [0.0, 0.0, 0.0, 1.0]).unwrap();
+    for y in 0..4 {
+        for x in 0..8 {
+            let value = x as f32 / 7.0;
+            gradient.set(x, y, [value, value, value, 1.0]);
+        }
+    }
+    let path = std::env::temp_dir().join(format!("proc_flow_image_{}.png", uuid::Uuid::new_v4()));
+    let path_string = path.to_string_lossy().into_owned();
+
+    let save_actor = engine.boot_graph(save_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of save image node");
+    engine.set_input_pin_value(save_actor.clone(), image_pin_id, Some(Message::new(gradient.clone())), "image".to_owned());
+    engine.set_input_pin_value(save_actor.clone(), path_pin_id, Some(Message::new(path_string.clone())), "string".to_owned());
+    let saved = engine.compute_output_pin_value(save_actor.clone(), saved_path_pin_id, None).unwrap().expect("retrieved empty saved path");
+    assert_eq!(path_string, *saved.content_as::<String>().expect("saved path was not a string"));
+
+    let load_actor = engine.boot_graph(load_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of load image node");
+    engine.set_input_pin_value(load_actor.clone(), path_pin_id, Some(Message::new(path_string.clone())), "string".to_owned());
+    let loaded = engine.compute_output_pin_value(load_actor.clone(), loaded_image_pin_id, None).unwrap().expect("retrieved empty loaded image");
+    let loaded = (*loaded.content_as::<Image>().expect("loaded image was not an image")).clone();
+    assert_eq!((8, 4), (loaded.width, loaded.height));
+    for (original, reloaded) in gradient.pixels.iter().zip(loaded.pixels.iter()) {
+        assert!((original[0] - reloaded[0]).abs() < 0.001);
+    }
+    std::fs::remove_file(&path).expect("could not remove saved image");
+
+    let normal_actor = engine.boot_graph(normal_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of normal map node");
+    engine.set_input_pin_value(normal_actor.clone(), height_pin_id, Some(Message::new(Image::filled(4, 4, [0.5, 0.5, 0.5, 1.0]).unwrap())), "image".to_owned());
+    let normals = engine.compute_output_pin_value(normal_actor.clone(), normal_map_pin_id, None).unwrap().expect("retrieved empty normal map");
+    let normals = normals.content_as::<Image>().expect("normal map was not an image");
+    assert!(normals.pixels.iter().all(|pixel| *pixel == [0.5, 0.5, 1.0, 1.0]));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn rejects_oversized_images() {
+    assert_eq!(Ok(12), pf::nodes::image::image_size(4, 3));
+    assert!(pf::nodes::image::image_size(70_000, 70_000).is_err());
+    assert!(pf::nodes::image::image_size(1 << 32, 1).is_err());
+    assert!(pf::nodes::image::image_size(u64::MAX, 2).is_err());
+    assert!(Image::filled(70_000, 70_000, [0.0; 4]).is_err());
+    let small = Image::filled(2, 2, [1.0; 4]).unwrap();
+    assert!(pf::nodes::image::filter::resize(&small, 70_000, 70_000).is_err());
+    assert_eq!(4, pf::nodes::image::filter::resize(&small, 2, 2).unwrap().pixels.len());
+}
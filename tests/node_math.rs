@@ -0,0 +1,74 @@
+use proc_flow_lib as pf;
+use nalgebra::{Matrix4, Vector3};
+
+#[test]
+fn computes_scalars_vectors_and_matrices() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let add_id = uuid::Uuid::parse_str("6d6388bb-cdda-4af0-abe1-7a18a48cd39f").unwrap();
+    let add_values_pin_id = uuid::Uuid::parse_str("e239fc7c-13cd-4743-8591-13fe062fd499").unwrap();
+    let add_result_pin_id = uuid::Uuid::parse_str("f76fcf19-8d4e-4672-9745-4c48419e5644").unwrap();
+    let remap_id = uuid::Uuid::parse_str("8c31f36d-fd54-4235-b8da-f3e53f78b82e").unwrap();
+    let remap_value_pin_id = uuid::Uuid::parse_str("5c89326b-81c6-4db7-a97c-08e9774c3635").unwrap();
+    let remap_to_max_pin_id = uuid::Uuid::parse_str("327c4777-7c53-4cd6-a3b5-d810a7289e13").unwrap();
+    let remap_result_pin_id = uuid::Uuid::parse_str("448abaa0-ca15-4024-ac9e-2ebd4e45fb5e").unwrap();
+    let cross_id = uuid::Uuid::parse_str("7196d74a-22f4-4496-bb68-4461341a0313").unwrap();
+    let cross_a_pin_id = uuid::Uuid::parse_str("3b34e696-3ff5-41cc-8cf4-c9c04871a75c").unwrap();
+    let cross_b_pin_id = uuid::Uuid::parse_str("04459385-df60-48d3-b0f9-a689d6937173").unwrap();
+    let cross_result_pin_id = uuid::Uuid::parse_str("7fc989e2-ce43-40db-8bff-b4fb16888651").unwrap();
+    let transform_id = uuid::Uuid::parse_str("4d34c3d2-bcef-484f-ae47-bcd9409760c1").unwrap();
+    let transform_matrix_pin_id = uuid::Uuid::parse_str("eee9ecbc-b9ef-4a02-8377-d42efaafdb54").unwrap();
+    let transform_vector_pin_id = uuid::Uuid::parse_str("04eeef24-5a3f-4abc-ade8-c60f001efd85").unwrap();
+    let transform_result_pin_id = uuid::Uuid::parse_str("1ff15120-ee14-4e27-82ee-9647b0bbe65e").unwrap();
+
+    let add_actor = engine.boot_graph(add_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of add node");
+    engine.set_input_pin_value(add_actor.clone(), add_values_pin_id, Some(axiom::prelude::Message::new(vec![1.0, 2.0, 3.5])), "f64".to_owned());
+    let sum = engine.compute_output_pin_value(add_actor.clone(), add_result_pin_id, None).unwrap().expect("retrieved empty sum");
+    assert_eq!(6.5, *sum.content_as::<f64>().expect("sum was not a f64"));
+
+    let remap_actor = engine.boot_graph(remap_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of remap node");
+    engine.set_input_pin_value(remap_actor.clone(), remap_value_pin_id, Some(axiom::prelude::Message::new(0.25)), "f64".to_owned());
+    engine.set_input_pin_value(remap_actor.clone(), remap_to_max_pin_id, Some(axiom::prelude::Message::new(100.0)), "f64".to_owned());
+    let remapped = engine.compute_output_pin_value(remap_actor.clone(), remap_result_pin_id, None).unwrap().expect("retrieved empty remap");
+    assert_eq!(25.0, *remapped.content_as::<f64>().expect("remap was not a f64"));
+
+    let cross_actor = engine.boot_graph(cross_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of cross product node");
+    engine.set_input_pin_value(cross_actor.clone(), cross_a_pin_id, Some(axiom::prelude::Message::new(Vector3::<f64>::x())), "vec3".to_owned());
+    engine.set_input_pin_value(cross_actor.clone(), cross_b_pin_id, Some(axiom::prelude::Message::new(Vector3::<f64>::y())), "vec3".to_owned());
+    let cross = engine.compute_output_pin_value(cross_actor.clone(), cross_result_pin_id, None).unwrap().expect("retrieved empty cross product");
+    assert_eq!(Vector3::z(), *cross.content_as::<Vector3<f64>>().expect("cross product was not a vector"));
+
+    let transform_actor = engine.boot_graph(transform_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of transform point node");
+    let translation = Matrix4::new_translation(&Vector3::new(1.0, 2.0, 3.0));
+    engine.set_input_pin_value(transform_actor.clone(), transform_matrix_pin_id, Some(axiom::prelude::Message::new(translation)), "mat4".to_owned());
+    engine.set_input_pin_value(transform_actor.clone(), transform_vector_pin_id, Some(axiom::prelude::Message::new(Vector3::new(1.0, 1.0, 1.0))), "vec3".to_owned());
+    let point = engine.compute_output_pin_value(transform_actor.clone(), transform_result_pin_id, None).unwrap().expect("retrieved empty transformed point");
+    assert_eq!(Vector3::new(2.0, 3.0, 4.0), *point.content_as::<Vector3<f64>>().expect("transformed point was not a vector"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn fails_computes_that_pull_a_failing_input() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let add_id = uuid::Uuid::parse_str("6d6388bb-cdda-4af0-abe1-7a18a48cd39f").unwrap();
+    let add_values_pin_id = uuid::Uuid::parse_str("e239fc7c-13cd-4743-8591-13fe062fd499").unwrap();
+    let add_result_pin_id = uuid::Uuid::parse_str("f76fcf19-8d4e-4672-9745-4c48419e5644").unwrap();
+    let arcsine_id = uuid::Uuid::parse_str("aec50a67-d77e-4285-a454-8d793ef1cfb8").unwrap();
+    let arcsine_value_pin_id = uuid::Uuid::parse_str("24b29853-a326-4840-b94c-6ad027a42dc1").unwrap();
+    let arcsine_result_pin_id = uuid::Uuid::parse_str("b79ccf7d-eda4-4974-8862-083b84ada5a1").unwrap();
+
+    let arcsine_instance_id = uuid::Uuid::new_v4();
+    let arcsine_actor = engine.boot_graph(arcsine_id, 1, arcsine_instance_id).expect("did not get aid back from engine boot of arcsine node");
+    engine.set_input_pin_value(arcsine_actor.clone(), arcsine_value_pin_id, Some(axiom::prelude::Message::new(2.0)), "f64".to_owned());
+    assert!(engine.compute_output_pin_value(arcsine_actor.clone(), arcsine_result_pin_id, None).is_err(), "arcsine of 2 did not fail");
+    assert!(engine.compute_output_pin_value(arcsine_actor.clone(), uuid::Uuid::new_v4(), None).is_err(), "unknown output pin did not fail");
+
+    let add_actor = engine.boot_graph(add_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of add node");
+    assert!(engine.link_pins(add_actor.clone(), add_values_pin_id, arcsine_actor.clone(), pf::graph::PinRef::new_pin(arcsine_instance_id, arcsine_result_pin_id)));
+    assert!(engine.compute_output_pin_value(add_actor.clone(), add_result_pin_id, None).is_err(), "add did not fail when its input failed");
+    assert!(engine.compute_output_pin_value(add_actor.clone(), add_result_pin_id, None).is_err(), "add did not fail again when its input failed");
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
@@ -0,0 +1,111 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::catalogue::CatalogueConfig;
+use pf::event::*;
+
+fn fixture_engine() -> pf::engine::Engine {
+    let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("data");
+    pf::engine::Engine::with_config(CatalogueConfig::new().without_default_paths().with_path(fixtures))
+}
+
+#[test]
+fn computes_outputs_through_nested_graphs() {
+    let mut engine = fixture_engine();
+    let sum_of_four_id = uuid::Uuid::parse_str("5ae733e9-645e-41e6-a869-edc151f6a1ef").unwrap();
+    let input_pin_ids = [
+        uuid::Uuid::parse_str("43432ed8-6eb8-44c1-bcbc-79a101cc9e5f").unwrap(),
+        uuid::Uuid::parse_str("d0473252-9d04-4c33-b911-296bfbdb1161").unwrap(),
+        uuid::Uuid::parse_str("54ba1196-40f2-4614-b1dc-fc35797ca26f").unwrap(),
+        uuid::Uuid::parse_str("7b4976ad-3200-4390-99e4-b3c9e0a64c43").unwrap(),
+    ];
+    let sum_pin_id = uuid::Uuid::parse_str("7ba10567-60b1-4ec0-853f-cd3ecedb0571").unwrap();
+
+    let graph_actor = engine.boot_graph(sum_of_four_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of sum of four graph");
+    for (value, pin_id) in input_pin_ids.iter().enumerate() {
+        engine.set_input_pin_value(graph_actor.clone(), *pin_id, Some(Message::new(value as f64 + 1.0)), "f64".to_owned());
+    }
+    let sum = engine.compute_output_pin_value(graph_actor.clone(), sum_pin_id, None).unwrap().expect("retrieved empty sum from graph");
+    assert_eq!(10.0, *sum.content_as::<f64>().expect("sum was not a f64"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn relays_messages_through_graph() {
+    let mut engine = fixture_engine();
+    let relay_id = uuid::Uuid::parse_str("ea8d9d4b-6d91-4df3-babe-2ef6071db028").unwrap();
+    let in_pin_id = uuid::Uuid::parse_str("c7199163-a5b0-4de9-abf4-7353cf98714c").unwrap();
+    let out_pin_id = uuid::Uuid::parse_str("c4775437-d8cb-461c-ba3e-813a8a9a50b3").unwrap();
+    let relay_instance_id = uuid::Uuid::new_v4();
+
+    let sent = engine.subscribe(EngineEventFilter::all().with_instance(relay_instance_id).with_kind(EngineEventKind::MessageSent));
+    let relay_actor = engine.boot_graph(relay_id, 1, relay_instance_id).expect("did not get aid back from engine boot of relay graph");
+    engine.send_value(relay_actor.clone(), in_pin_id, Some(Message::new(String::from("across"))));
+    let event = sent.recv_timeout(std::time::Duration::from_secs(5)).expect("relay graph did not send anything");
+    match event.event {
+        EngineEventType::MessageSent(pin, Some(value)) => {
+            assert_eq!(out_pin_id, pin);
+            assert_eq!("across", *value.content_as::<String>().expect("relayed value was not a string"));
+        }
+        _ => panic!("unexpected event for relay graph"),
+    }
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn spawns_graph_nodes_when_needed_and_stops_them_when_idle() {
+    let mut engine = fixture_engine();
+    let sum_of_three_id = uuid::Uuid::parse_str("ea3d15c5-28a1-4010-9447-72a131af2a6c").unwrap();
+    let input_pin_ids = [
+        uuid::Uuid::parse_str("52efc022-b78b-43b5-b204-5b3c1cf41779").unwrap(),
+        uuid::Uuid::parse_str("8374f5ed-24ec-43ac-bc95-539b857d5a71").unwrap(),
+        uuid::Uuid::parse_str("7c8341d6-4101-4fe9-a22b-167a1f40ecf5").unwrap(),
+    ];
+    let sum_pin_id = uuid::Uuid::parse_str("1cf2ef1b-70ba-4233-aade-2ac30e0a0e25").unwrap();
+    let add_id = uuid::Uuid::parse_str("6d6388bb-cdda-4af0-abe1-7a18a48cd39f").unwrap();
+
+    let booted = engine.subscribe(EngineEventFilter::all().with_graph(add_id).with_kind(EngineEventKind::NodeBooted));
+    let stopped = engine.subscribe(EngineEventFilter::all().with_graph(add_id).with_kind(EngineEventKind::NodeStopped));
+    let graph_actor = engine.boot_graph(sum_of_three_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of sum of three graph");
+    engine.update_datum(graph_actor.clone(), pf::nodes::meta::graph::IDLE_TIMEOUT_DATUM.to_owned(), serde_json::json!(0.25));
+    for (value, pin_id) in input_pin_ids.iter().enumerate() {
+        engine.set_input_pin_value(graph_actor.clone(), *pin_id, Some(Message::new(value as f64 + 1.0)), "f64".to_owned());
+    }
+    assert!(booted.try_recv().is_err(), "graph spawned its nodes before anything needed them");
+
+    let sum = engine.compute_output_pin_value(graph_actor.clone(), sum_pin_id, None).unwrap().expect("retrieved empty sum from graph");
+    assert_eq!(6.0, *sum.content_as::<f64>().expect("sum was not a f64"));
+    for _ in 0..2 {
+        booted.recv_timeout(std::time::Duration::from_secs(5)).expect("graph did not spawn its add nodes");
+        stopped.recv_timeout(std::time::Duration::from_secs(5)).expect("graph did not stop its idle add nodes");
+    }
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn fails_graph_outputs_whose_nodes_fail_and_still_stops_them_when_idle() {
+    let mut engine = fixture_engine();
+    let arcsine_graph_id = uuid::Uuid::parse_str("3d0f6a52-8c0e-4b8a-9f52-6a1f0c7e2d41").unwrap();
+    let value_pin_id = uuid::Uuid::parse_str("a5c0e7b1-2f4d-4e63-9b8a-0d7c3f1e6a24").unwrap();
+    let angle_pin_id = uuid::Uuid::parse_str("e81b4d9c-6a3f-4c27-8e05-b2d9f7a1c3e6").unwrap();
+    let arcsine_id = uuid::Uuid::parse_str("aec50a67-d77e-4285-a454-8d793ef1cfb8").unwrap();
+
+    let stopped = engine.subscribe(EngineEventFilter::all().with_graph(arcsine_id).with_kind(EngineEventKind::NodeStopped));
+    let graph_actor = engine.boot_graph(arcsine_graph_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of arcsine graph");
+    engine.update_datum(graph_actor.clone(), pf::nodes::meta::graph::IDLE_TIMEOUT_DATUM.to_owned(), serde_json::json!(0.25));
+    engine.set_input_pin_value(graph_actor.clone(), value_pin_id, Some(Message::new(2.0)), "f64".to_owned());
+    assert!(engine.compute_output_pin_value(graph_actor.clone(), angle_pin_id, None).is_err(), "graph did not fail when its arcsine node failed");
+    stopped.recv_timeout(std::time::Duration::from_secs(5)).expect("graph did not stop its idle arcsine node after it failed");
+
+    engine.set_input_pin_value(graph_actor.clone(), value_pin_id, Some(Message::new(1.0)), "f64".to_owned());
+    let angle = engine.compute_output_pin_value(graph_actor.clone(), angle_pin_id, None).unwrap().expect("retrieved empty angle from graph");
+    assert_eq!(std::f64::consts::FRAC_PI_2, *angle.content_as::<f64>().expect("angle was not a f64"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
@@ -0,0 +1,43 @@
+use proc_flow_lib as pf;
+use pf::nodes::noise::NoiseGrid;
+
+#[test]
+fn samples_seeded_grids() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let fractal_id = uuid::Uuid::parse_str("c9693e01-0c9c-4428-97fc-c2cff76ac94c").unwrap();
+    let seed_pin_id = uuid::Uuid::parse_str("47168f6c-d51b-4b46-a8b1-9d4437b852d2").unwrap();
+    let width_pin_id = uuid::Uuid::parse_str("3d1174f1-cc27-47b6-91c1-e1dcc81d06a0").unwrap();
+    let height_pin_id = uuid::Uuid::parse_str("126bc9cb-96df-4bfa-b449-549ba9a669a7").unwrap();
+    let fractal_type_pin_id = uuid::Uuid::parse_str("9a6b469d-912c-48d6-a41c-2655362a0cb2").unwrap();
+    let grid_2d_pin_id = uuid::Uuid::parse_str("9d837344-3bbb-4cdf-b8be-b307fcf428eb").unwrap();
+
+    let mut grids = Vec::new();
+    for _ in 0..2 {
+        let noise_actor = engine.boot_graph(fractal_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of fractal noise node");
+        engine.set_input_pin_value(noise_actor.clone(), seed_pin_id, Some(axiom::prelude::Message::new(1234 as u64)), "u64".to_owned());
+        engine.set_input_pin_value(noise_actor.clone(), width_pin_id, Some(axiom::prelude::Message::new(16 as u64)), "u64".to_owned());
+        engine.set_input_pin_value(noise_actor.clone(), height_pin_id, Some(axiom::prelude::Message::new(8 as u64)), "u64".to_owned());
+        engine.set_input_pin_value(noise_actor.clone(), fractal_type_pin_id, Some(axiom::prelude::Message::new("ridged".to_owned())), "string".to_owned());
+        let grid = engine
+            .compute_output_pin_value(noise_actor.clone(), grid_2d_pin_id, None)
+            .unwrap()
+            .expect("retrieved empty noise grid");
+        grids.push((*grid.content_as::<NoiseGrid>().expect("noise grid output was not a noise grid")).clone());
+    }
+
+    assert_eq!((16, 8, 1), (grids[0].width, grids[0].height, grids[0].depth));
+    assert_eq!(16 * 8, grids[0].values.len());
+    assert_eq!(grids[0].values, grids[1].values);
+    assert!(grids[0].values.iter().any(|value| *value != grids[0].values[0]));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn rejects_oversized_grids() {
+    assert_eq!(Ok(64 * 64 * 64), pf::nodes::noise::grid_size(64, 64, 64));
+    assert!(pf::nodes::noise::grid_size(u64::MAX, 2, 1).is_err());
+    assert!(pf::nodes::noise::grid_size(1 << 20, 1 << 20, 1).is_err());
+    assert!(pf::nodes::noise::grid_size(pf::nodes::noise::MAX_GRID_VALUES as u64 + 1, 1, 1).is_err());
+}
@@ -0,0 +1,53 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::nodes::geometry::Mesh;
+use pf::nodes::sdf::Sdf;
+use pf::nodes::voxel::Volume;
+
+#[test]
+fn combines_and_polygonizes_fields() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let sphere_id = uuid::Uuid::parse_str("434838cb-1738-4b46-804b-b04d76c92172").unwrap();
+    let radius_pin_id = uuid::Uuid::parse_str("05528a33-e05b-4249-a2b9-e82226a8e99b").unwrap();
+    let primitive_shape_pin_id = uuid::Uuid::parse_str("8b77cd5f-7754-4b2f-b88d-abc80236d026").unwrap();
+    let union_id = uuid::Uuid::parse_str("ca641b65-aa6a-4fa5-8caa-a12328c56a06").unwrap();
+    let shapes_pin_id = uuid::Uuid::parse_str("63ce80c3-2b5d-4bda-b6f4-dbf0fbcf8e99").unwrap();
+    let combined_shape_pin_id = uuid::Uuid::parse_str("0a06cfbd-db93-4e56-8504-ce0597fad21d").unwrap();
+    let distance_id = uuid::Uuid::parse_str("b4a735ba-f65c-41e2-ae0c-db6a92835517").unwrap();
+    let polygonize_id = uuid::Uuid::parse_str("11b1a638-22e3-4066-9094-727b9b971b28").unwrap();
+    let shape_pin_id = uuid::Uuid::parse_str("7c7fe74c-5ca1-47c3-a2ee-7e7540aff3d4").unwrap();
+    let point_pin_id = uuid::Uuid::parse_str("d7d677da-706f-4739-a0aa-8b9d73decb46").unwrap();
+    let volume_pin_id = uuid::Uuid::parse_str("1f97bc46-0827-4ec3-8ff5-efab0f5238a5").unwrap();
+    let distance_pin_id = uuid::Uuid::parse_str("2efc7b04-f01c-40a3-9dd0-b85f12628327").unwrap();
+    let mesh_pin_id = uuid::Uuid::parse_str("9471d210-1ca1-4281-9d5a-0250d28cc9b1").unwrap();
+
+    let sphere_actor = engine.boot_graph(sphere_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of sphere node");
+    engine.set_input_pin_value(sphere_actor.clone(), radius_pin_id, Some(Message::new(1.0f64)), "f64".to_owned());
+    let sphere = engine.compute_output_pin_value(sphere_actor.clone(), primitive_shape_pin_id, None).unwrap().expect("retrieved empty sphere");
+    let sphere = (*sphere.content_as::<Sdf>().expect("sphere was not a field")).clone();
+    assert_eq!(Sdf::Sphere { radius: 1.0 }, sphere);
+
+    let shapes = vec![sphere, Sdf::Box { half_size: [2.0, 0.25, 0.25] }];
+    let union_actor = engine.boot_graph(union_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of union node");
+    engine.set_input_pin_value(union_actor.clone(), shapes_pin_id, Some(Message::new(shapes)), "sdf".to_owned());
+    let union = engine.compute_output_pin_value(union_actor.clone(), combined_shape_pin_id, None).unwrap().expect("retrieved empty union");
+    let union = (*union.content_as::<Sdf>().expect("union was not a field")).clone();
+
+    let distance_actor = engine.boot_graph(distance_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of distance node");
+    engine.set_input_pin_value(distance_actor.clone(), shape_pin_id, Some(Message::new(union.clone())), "sdf".to_owned());
+    engine.set_input_pin_value(distance_actor.clone(), point_pin_id, Some(Message::new(nalgebra::Vector3::new(3.0f64, 0.0, 0.0))), "vec3".to_owned());
+    let distance = engine.compute_output_pin_value(distance_actor.clone(), distance_pin_id, None).unwrap().expect("retrieved empty distance");
+    assert!((1.0 - *distance.content_as::<f64>().expect("distance was not a number")).abs() < 0.0001);
+
+    let polygonize_actor = engine.boot_graph(polygonize_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of polygonize node");
+    engine.set_input_pin_value(polygonize_actor.clone(), shape_pin_id, Some(Message::new(union)), "sdf".to_owned());
+    engine.set_input_pin_value(polygonize_actor.clone(), volume_pin_id, Some(Message::new(Volume::new([60, 30, 30], 0.1, [-3.0, -1.5, -1.5], false).unwrap())), "volume".to_owned());
+    let mesh = engine.compute_output_pin_value(polygonize_actor.clone(), mesh_pin_id, None).unwrap().expect("retrieved empty mesh");
+    let mesh = mesh.content_as::<Mesh>().expect("polygonized field was not a mesh");
+    let (min, max) = mesh.bounds();
+    assert!((min[0] + 2.0).abs() < 0.05 && (max[0] - 2.0).abs() < 0.05);
+    assert!((min[1] + 1.0).abs() < 0.05 && (max[1] - 1.0).abs() < 0.05);
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
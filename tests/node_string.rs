@@ -0,0 +1,53 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+
+#[test]
+fn builds_asset_names() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let concat_id = uuid::Uuid::parse_str("d5c58e38-f8a1-449c-9a5a-9b93a2bc800d").unwrap();
+    let concat_strings_pin_id = uuid::Uuid::parse_str("70fac27a-5700-41d7-8249-6cb4d7f8c56b").unwrap();
+    let concat_separator_pin_id = uuid::Uuid::parse_str("bac4fb74-ebb6-4021-85a8-7a125bb14320").unwrap();
+    let concat_text_pin_id = uuid::Uuid::parse_str("51d5f7bf-e75c-4516-812f-c4ed4e4966e2").unwrap();
+    let format_id = uuid::Uuid::parse_str("fa93acee-efc6-4eb3-a34b-ee1fe5c9051c").unwrap();
+    let format_template_pin_id = uuid::Uuid::parse_str("9822a136-b466-4339-80ba-9c3ec9beb3d5").unwrap();
+    let format_values_pin_id = uuid::Uuid::parse_str("e142b037-539e-4040-898f-b9fc8bd98eb7").unwrap();
+    let format_text_pin_id = uuid::Uuid::parse_str("f3fb5878-1b83-46f3-bf2f-f768d3866897").unwrap();
+    let replace_id = uuid::Uuid::parse_str("ed767fd6-8940-420c-a965-236e77d7623b").unwrap();
+    let replace_text_pin_id = uuid::Uuid::parse_str("b27c3fef-1a41-4c8b-950d-8a958d2b5f46").unwrap();
+    let replace_pattern_pin_id = uuid::Uuid::parse_str("4a4f02e5-b7e7-4419-8114-00bdbf22f106").unwrap();
+    let replace_replacement_pin_id = uuid::Uuid::parse_str("a3754195-5c5f-4451-b4e2-6b6f737b6c84").unwrap();
+    let replace_output_pin_id = uuid::Uuid::parse_str("f529f42e-cab7-4cfb-9836-021f38847df6").unwrap();
+    let snake_id = uuid::Uuid::parse_str("b709e515-31b7-4288-9a8a-dd8d991e2707").unwrap();
+    let snake_text_pin_id = uuid::Uuid::parse_str("de5cac9e-337c-4078-81df-e7e67ea83a6c").unwrap();
+    let snake_output_pin_id = uuid::Uuid::parse_str("f052ffbf-d947-4253-ae47-dd325cf9abb3").unwrap();
+
+    let concat_actor = engine.boot_graph(concat_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of concatenate node");
+    engine.set_input_pin_value(concat_actor.clone(), concat_strings_pin_id, Some(Message::new(vec!["assets".to_owned(), "rocks".to_owned(), "granite".to_owned()])), "string".to_owned());
+    engine.set_input_pin_value(concat_actor.clone(), concat_separator_pin_id, Some(Message::new("/".to_owned())), "string".to_owned());
+    let path = engine.compute_output_pin_value(concat_actor.clone(), concat_text_pin_id, None).unwrap().expect("retrieved empty concatenation");
+    assert_eq!("assets/rocks/granite", *path.content_as::<String>().expect("concatenation was not a string"));
+
+    let format_actor = engine.boot_graph(format_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of format node");
+    let mut values = std::collections::HashMap::new();
+    values.insert("name".to_owned(), "granite".to_owned());
+    values.insert("index".to_owned(), "07".to_owned());
+    engine.set_input_pin_value(format_actor.clone(), format_template_pin_id, Some(Message::new("{name}_{index}.png {{raw}}".to_owned())), "string".to_owned());
+    engine.set_input_pin_value(format_actor.clone(), format_values_pin_id, Some(Message::new(values)), "string_map".to_owned());
+    let formatted = engine.compute_output_pin_value(format_actor.clone(), format_text_pin_id, None).unwrap().expect("retrieved empty format");
+    assert_eq!("granite_07.png {raw}", *formatted.content_as::<String>().expect("format was not a string"));
+
+    let replace_actor = engine.boot_graph(replace_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of regex replace node");
+    engine.set_input_pin_value(replace_actor.clone(), replace_text_pin_id, Some(Message::new("rock_v1.png rock_v22.png".to_owned())), "string".to_owned());
+    engine.set_input_pin_value(replace_actor.clone(), replace_pattern_pin_id, Some(Message::new(r"_v(\d+)".to_owned())), "string".to_owned());
+    engine.set_input_pin_value(replace_actor.clone(), replace_replacement_pin_id, Some(Message::new("-$1".to_owned())), "string".to_owned());
+    let replaced = engine.compute_output_pin_value(replace_actor.clone(), replace_output_pin_id, None).unwrap().expect("retrieved empty replacement");
+    assert_eq!("rock-1.png rock-22.png", *replaced.content_as::<String>().expect("replacement was not a string"));
+
+    let snake_actor = engine.boot_graph(snake_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of snake case node");
+    engine.set_input_pin_value(snake_actor.clone(), snake_text_pin_id, Some(Message::new("loadHTTPServer2 now".to_owned())), "string".to_owned());
+    let snake = engine.compute_output_pin_value(snake_actor.clone(), snake_output_pin_id, None).unwrap().expect("retrieved empty snake case");
+    assert_eq!("load_http_server2_now", *snake.content_as::<String>().expect("snake case was not a string"));
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
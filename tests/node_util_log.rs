@@ -2,7 +2,7 @@ use proc_flow_lib as pf;
 
 #[test]
 fn calculates_output() {
-    let mut engine = pf::engine::Engine::new();
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
     let log_id = uuid::Uuid::parse_str("fd41d8ef-d10f-4499-8a90-35b73d8ff246").unwrap();
     let log_info_input_pin_id = uuid::Uuid::parse_str("5e6ab872-5cca-4e01-8dbb-2df843102dc0").unwrap();
     let log_info_output_pin_id = uuid::Uuid::parse_str("44a986b1-dc09-45d9-ab65-e2c0c7b6f5ce").unwrap();
@@ -10,7 +10,7 @@ fn calculates_output() {
     match log_actor {
         Some(log_actor) => {
             engine.set_input_pin_value(log_actor.clone(), log_info_input_pin_id, Some(axiom::prelude::Message::new("testing log actor".to_owned())), "string".to_owned());
-            let value = engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None);
+            let value = engine.compute_output_pin_value(log_actor.clone(), log_info_output_pin_id, None).expect("log actor could not compute output");
             match value {
                 Some(value) => {
                     match value.content_as::<String>() {
@@ -26,4 +26,41 @@ fn calculates_output() {
         None => panic!("did not get aid back from engine boot of log node"),
     };
     assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
-}
\ No newline at end of file
+}
+#[test]
+fn passes_messages_through_send_pins() {
+    use pf::event::*;
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let log_id = uuid::Uuid::parse_str("fd41d8ef-d10f-4499-8a90-35b73d8ff246").unwrap();
+    let log_info_receive_pin_id = uuid::Uuid::parse_str("6b9c6c69-13e8-473a-ac47-818fcdf6d7bd").unwrap();
+    let log_warn_receive_pin_id = uuid::Uuid::parse_str("4eb1bc59-ca1b-4754-be49-0ad13f86421a").unwrap();
+    let log_info_send_pin_id = uuid::Uuid::parse_str("dfc26f11-fa2b-4667-aad3-456edbdd9c84").unwrap();
+    let log_warn_send_pin_id = uuid::Uuid::parse_str("3982006c-9e32-4e59-a544-58bc9a367daf").unwrap();
+    let first_id = uuid::Uuid::new_v4();
+    let second_id = uuid::Uuid::new_v4();
+    let sent = engine.subscribe(EngineEventFilter::all().with_instance(second_id).with_kind(EngineEventKind::MessageSent));
+    let first = engine.boot_graph(log_id, 1, first_id).expect("did not get aid back from engine boot of first log node");
+    let second = engine.boot_graph(log_id, 1, second_id).expect("did not get aid back from engine boot of second log node");
+    assert!(engine.link_pins(first.clone(), log_info_send_pin_id, second.clone(), pf::graph::PinRef::new_pin(second_id, log_warn_receive_pin_id)));
+    engine.send_value(first.clone(), log_info_receive_pin_id, Some(axiom::prelude::Message::new("testing log passthrough".to_owned())));
+    let event = sent.recv_timeout(std::time::Duration::from_secs(5)).expect("second log node did not send anything");
+    match event.event {
+        EngineEventType::MessageSent(pin, Some(value)) => {
+            assert_eq!(log_warn_send_pin_id, pin);
+            assert_eq!("testing log passthrough", *value.content_as::<String>().unwrap());
+        }
+        _ => panic!("unexpected event for passed through message"),
+    }
+    // Passing through only signals the pass through send pin, not the warn or error ones.
+    let log_pass_receive_pin_id = uuid::Uuid::parse_str("bccf1a26-793d-4c80-ad25-be110c4dc1d7").unwrap();
+    let log_pass_send_pin_id = uuid::Uuid::parse_str("76e93dc1-50d7-4686-8b5e-07019f4597df").unwrap();
+    engine.send_value(second.clone(), log_pass_receive_pin_id, Some(axiom::prelude::Message::new("inline".to_owned())));
+    let event = sent.recv_timeout(std::time::Duration::from_secs(5)).expect("second log node did not pass the message through");
+    match event.event {
+        EngineEventType::MessageSent(pin, _) => assert_eq!(log_pass_send_pin_id, pin),
+        _ => panic!("unexpected event for passed through message"),
+    }
+    assert!(sent.recv_timeout(std::time::Duration::from_millis(200)).is_err());
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
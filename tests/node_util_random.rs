@@ -0,0 +1,44 @@
+use proc_flow_lib as pf;
+
+fn random_value(instance_id: uuid::Uuid) -> f64 {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let random_id = uuid::Uuid::parse_str("7ea23d03-9c75-4c7d-8d5f-f6b114bf776e").unwrap();
+    let random_min_pin_id = uuid::Uuid::parse_str("741e8159-2f51-472d-9700-e6e0a2496766").unwrap();
+    let random_max_pin_id = uuid::Uuid::parse_str("0a17d763-1476-45ac-9165-cafce0fd79e9").unwrap();
+    let random_value_pin_id = uuid::Uuid::parse_str("3c83e8cd-3296-44e8-a799-ce90f3eeaf2a").unwrap();
+    let random_actor = engine.boot_graph(random_id, 1, instance_id).expect("did not get aid back from engine boot of random node");
+    engine.set_input_pin_value(random_actor.clone(), random_min_pin_id, Some(axiom::prelude::Message::new(10.0 as f64)), "f64".to_owned());
+    engine.set_input_pin_value(random_actor.clone(), random_max_pin_id, Some(axiom::prelude::Message::new(20.0 as f64)), "f64".to_owned());
+    let value = engine
+        .compute_output_pin_value(random_actor.clone(), random_value_pin_id, None)
+        .unwrap()
+        .expect("retrieved empty random value");
+    let value = *value.content_as::<f64>().expect("random value was not a float");
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+    value
+}
+
+#[test]
+fn reproduces_values_for_an_instance() {
+    let instance_id = uuid::Uuid::parse_str("5442ec37-39e2-4d06-acdb-3d116a961940").unwrap();
+    let first = random_value(instance_id);
+    let second = random_value(instance_id);
+    assert_eq!(first, second);
+    assert!(first >= 10.0 && first < 20.0);
+    assert_ne!(first, random_value(uuid::Uuid::parse_str("0b37f207-95ff-4679-951c-9654a7053f59").unwrap()));
+}
+
+#[test]
+fn pins_generator_output_for_a_seed() {
+    use rand::{Rng, RngCore};
+    // These must never change, or saved graphs would give different numbers.
+    let mut rng = pf::seed::seeded_rng(42);
+    let values: Vec<u64> = (0..3).map(|_| rng.next_u64()).collect();
+    assert_eq!(vec![12578764544318200737, 17529487244874322312, 7886285670807131020], values);
+    let instance = uuid::Uuid::parse_str("00000000-0000-0000-0000-000000000001").unwrap();
+    assert_eq!(17391186116655616539, pf::seed::node_seed(Some(7), &instance));
+    // The whole integer range can be sampled without overflowing.
+    let value = rng.sample(rand::distributions::Uniform::new_inclusive(i64::MAX - 1, i64::MAX));
+    assert!(value >= i64::MAX - 1);
+}
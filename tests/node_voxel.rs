@@ -0,0 +1,70 @@
+use proc_flow_lib as pf;
+use axiom::prelude::Message;
+use pf::nodes::geometry::Mesh;
+use pf::nodes::voxel::Volume;
+
+#[test]
+fn fills_sparse_volume_and_extracts_surface() {
+    let mut engine = pf::engine::Engine::with_config(pf::catalogue::CatalogueConfig::new().without_default_paths());
+    let empty_id = uuid::Uuid::parse_str("994c3bfe-1890-4466-9fdf-62c2b14abc9a").unwrap();
+    let sphere_id = uuid::Uuid::parse_str("2de0d203-e40b-4a37-b536-a9ac2e4257f0").unwrap();
+    let surface_id = uuid::Uuid::parse_str("619c1e0f-e669-48c3-93e3-7bf763a732c8").unwrap();
+    let width_pin_id = uuid::Uuid::parse_str("f3cb233c-8e7c-41c0-9007-978bec690114").unwrap();
+    let height_pin_id = uuid::Uuid::parse_str("d5fb3084-cc00-4112-a7ae-f4667f415d22").unwrap();
+    let depth_pin_id = uuid::Uuid::parse_str("5099b59d-2b32-4a2e-889d-96c57cf2d11e").unwrap();
+    let voxel_size_pin_id = uuid::Uuid::parse_str("babc5f1e-8d8f-4bc7-ab73-9c836365d7a0").unwrap();
+    let origin_pin_id = uuid::Uuid::parse_str("063148c0-bc67-41a2-840b-5baf21a965b6").unwrap();
+    let sparse_pin_id = uuid::Uuid::parse_str("38220564-1640-4240-a3f3-f8d3f1738e02").unwrap();
+    let volume_pin_id = uuid::Uuid::parse_str("4f37d045-f956-468e-8122-1ae592706276").unwrap();
+    let radius_pin_id = uuid::Uuid::parse_str("09295cd7-d37c-4ebe-bad3-5dabf38b8c1a").unwrap();
+    let output_volume_pin_id = uuid::Uuid::parse_str("e592dae7-9228-4ef5-9a1c-2d856e6d9947").unwrap();
+    let surface_volume_pin_id = uuid::Uuid::parse_str("00183220-185a-4bb3-86f2-d4d88990211a").unwrap();
+    let surface_mesh_pin_id = uuid::Uuid::parse_str("b167941c-ecac-4a0d-bf63-8922d68988d3").unwrap();
+
+    let empty_actor = engine.boot_graph(empty_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of empty volume node");
+    for pin in [width_pin_id, height_pin_id, depth_pin_id].iter() {
+        engine.set_input_pin_value(empty_actor.clone(), *pin, Some(Message::new(64u64)), "u64".to_owned());
+    }
+    engine.set_input_pin_value(empty_actor.clone(), voxel_size_pin_id, Some(Message::new(0.1f64)), "f64".to_owned());
+    engine.set_input_pin_value(empty_actor.clone(), origin_pin_id, Some(Message::new(nalgebra::Vector3::new(-3.2f64, -3.2, -3.2))), "vec3".to_owned());
+    engine.set_input_pin_value(empty_actor.clone(), sparse_pin_id, Some(Message::new(true)), "bool".to_owned());
+    let empty = engine.compute_output_pin_value(empty_actor.clone(), output_volume_pin_id, None).unwrap().expect("retrieved empty volume");
+    let empty = (*empty.content_as::<Volume>().expect("empty volume was not a volume")).clone();
+    assert!(empty.is_sparse());
+    assert_eq!(64, empty.stored_voxels());
+
+    let sphere_actor = engine.boot_graph(sphere_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of sphere fill node");
+    engine.set_input_pin_value(sphere_actor.clone(), volume_pin_id, Some(Message::new(empty)), "volume".to_owned());
+    engine.set_input_pin_value(sphere_actor.clone(), radius_pin_id, Some(Message::new(1.0f64)), "f64".to_owned());
+    let sphere = engine.compute_output_pin_value(sphere_actor.clone(), output_volume_pin_id, None).unwrap().expect("retrieved empty sphere volume");
+    let sphere = (*sphere.content_as::<Volume>().expect("sphere volume was not a volume")).clone();
+    assert!(sphere.get(32, 32, 32) < 0.0);
+    assert!(sphere.get(0, 0, 0) > 0.0);
+    assert!(sphere.stored_voxels() < 64 * 64 * 64 / 2);
+
+    let surface_actor = engine.boot_graph(surface_id, 1, uuid::Uuid::new_v4()).expect("did not get aid back from engine boot of surface node");
+    engine.set_input_pin_value(surface_actor.clone(), surface_volume_pin_id, Some(Message::new(sphere)), "volume".to_owned());
+    let mesh = engine.compute_output_pin_value(surface_actor.clone(), surface_mesh_pin_id, None).unwrap().expect("retrieved empty surface mesh");
+    let mesh = mesh.content_as::<Mesh>().expect("surface was not a mesh");
+    assert!(mesh.triangle_count() > 0);
+    for position in mesh.positions.iter() {
+        let radius = (position[0] * position[0] + position[1] * position[1] + position[2] * position[2]).sqrt();
+        assert!((radius - 1.0).abs() < 0.01);
+    }
+
+    engine.shutdown();
+    assert_eq!(pf::axiom::prelude::ShutdownResult::Ok, engine.wait(std::time::Duration::from_secs(5)));
+}
+
+#[test]
+fn rejects_oversized_volumes() {
+    assert_eq!(Ok(24), pf::nodes::voxel::voxel_count([2, 3, 4]));
+    assert!(pf::nodes::voxel::voxel_count([1 << 21, 1 << 21, 1 << 21]).is_err());
+    assert!(pf::nodes::voxel::voxel_count([u64::MAX, u64::MAX, 2]).is_err());
+    assert!(pf::nodes::voxel::voxel_count([u64::MAX, 0, 1]).is_err());
+    assert!(Volume::new([100_000, 100_000, 100_000], 1.0, [0.0; 3], false).is_err());
+    assert!(Volume::new([100_000, 100_000, 100_000], 1.0, [0.0; 3], true).is_err());
+    assert!(Volume::new([usize::MAX, 0, 1], 1.0, [0.0; 3], true).is_err());
+    let sparse = Volume::new([32, 32, 32], 1.0, [0.0; 3], true).unwrap();
+    assert_eq!([2, 2, 2], sparse.chunks());
+}
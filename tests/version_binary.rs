@@ -0,0 +1,115 @@
+mod common;
+
+use proc_flow_lib as pf;
+use pf::encoding::*;
+use pf::graph::*;
+
+fn version() -> VersionInfo {
+    let (node_id, pin_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    let mut data = std::collections::HashMap::new();
+    data.insert(String::from("matrix"), serde_json::json!([[1.0, 0.5], [-2.25, 4.0]]));
+    data.insert(String::from("counts"), serde_json::json!([1, -2, 3]));
+    data.insert(String::from("mixed"), serde_json::json!([1, 2.0, "three", null, true, {"four": [4]}]));
+    data.insert(String::from("large"), serde_json::json!(u64::MAX));
+    data.insert(String::from("path"), serde_json::json!("textures/bricks.png"));
+    let mut input = PinRef::new_pin(node_id, pin_id);
+    input.value = Some(serde_json::json!({"x": 1.5, "y": 2}));
+    VersionInfo {
+        format: 1,
+        seed: Some(42),
+        receives: Vec::new(),
+        sends: Vec::new(),
+        inputs: vec![PinInfo::new_basic(String::from("Size"), uuid::Uuid::new_v4(), String::from("f64"))],
+        outputs: Vec::new(),
+        nodes: vec![NodeInfo {
+            uuid: node_id,
+            x: 1.0,
+            y: -3.5,
+            data,
+            graph: GraphRef {
+                name: String::from("Log"),
+                uuid: uuid::Uuid::new_v4(),
+                library: None,
+                version: VersionSpec::Latest,
+            },
+        }],
+        connections: vec![ConnectionInfo {
+            receives: None,
+            sends: None,
+            output: None,
+            input: Some(input),
+        }],
+    }
+}
+
+#[test]
+fn converts_between_json_and_binary_losslessly() {
+    let original = version();
+    let json = encode_version(&original, VersionEncoding::Json).unwrap();
+    let binary = encode_version(&original, VersionEncoding::Binary).unwrap();
+    assert_eq!(VersionEncoding::Json, VersionEncoding::detect(&json));
+    assert_eq!(VersionEncoding::Binary, VersionEncoding::detect(&binary));
+    assert!(binary.starts_with(BINARY_MAGIC));
+
+    let from_binary = decode_version(&binary).unwrap();
+    let expected: serde_json::Value = serde_json::from_slice(&json).unwrap();
+    assert_eq!(expected, serde_json::to_value(&from_binary).unwrap());
+    assert_eq!(binary, encode_version(&from_binary, VersionEncoding::Binary).unwrap());
+    let data = &from_binary.nodes[0].data;
+    assert!(data["matrix"][0][0].is_f64());
+    assert!(data["counts"][0].is_u64());
+    assert_eq!(serde_json::json!(u64::MAX), data["large"]);
+
+    let mut newer = binary.clone();
+    newer[BINARY_MAGIC.len()..BINARY_MAGIC.len() + 4].copy_from_slice(&(BINARY_FORMAT + 1).to_le_bytes());
+    assert!(decode_version(&newer).is_err());
+
+    let root = common::temp_root("proc_flow_convert");
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join(VERSION_JSON), &json).unwrap();
+    assert_eq!(VersionEncoding::Binary, convert_version_file(&root.join(VERSION_JSON), &root.join(VERSION_BIN)).unwrap());
+    assert_eq!(binary, std::fs::read(root.join(VERSION_BIN)).unwrap());
+    assert_eq!(VersionEncoding::Json, convert_version_file(&root.join(VERSION_BIN), &root.join("back.json")).unwrap());
+    let back: serde_json::Value = serde_json::from_slice(&std::fs::read(root.join("back.json")).unwrap()).unwrap();
+    assert_eq!(expected, back);
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+#[test]
+fn loads_binary_versions_from_libraries() {
+    let root = common::temp_root("proc_flow_binary");
+    let library_path = root.join("loose").join("binary");
+    let graph_path = library_path.join("shape");
+    let (library_id, graph_id) = (uuid::Uuid::new_v4(), uuid::Uuid::new_v4());
+    std::fs::create_dir_all(graph_path.join("1")).unwrap();
+    std::fs::create_dir_all(graph_path.join("2")).unwrap();
+    common::write_library(&library_path, "Binary", library_id);
+    common::write_graph(&graph_path, "Shape", graph_id);
+    let mut older = version();
+    older.seed = Some(1);
+    std::fs::write(graph_path.join("1").join(VERSION_JSON), encode_version(&older, VersionEncoding::Json).unwrap()).unwrap();
+    std::fs::write(graph_path.join("2").join(VERSION_BIN), encode_version(&version(), VersionEncoding::Binary).unwrap()).unwrap();
+
+    let mut catalogue = pf::catalogue::Catalogue::new();
+    catalogue.load_libraries(pf::catalogue::CatalogueConfig::new().without_default_paths().with_path(root.join("loose")));
+    assert_eq!(vec![1, 2], pf::library::get_graph_versions(&catalogue.libraries[&library_id].graphs[&graph_id]));
+    let latest = catalogue.get_graph_version(&GraphRef { name: String::new(), uuid: graph_id, library: None, version: VersionSpec::Latest }).unwrap();
+    assert_eq!(Some(42), latest.seed);
+    assert_eq!(serde_json::json!([[1.0, 0.5], [-2.25, 4.0]]), latest.nodes[0].data["matrix"]);
+    let first = catalogue.get_graph_version(&GraphRef { name: String::new(), uuid: graph_id, library: None, version: VersionSpec::Exact(1) }).unwrap();
+    assert_eq!(Some(1), first.seed);
+
+    // Packages count a version once when it is stored in both encodings.
+    std::fs::write(graph_path.join("2").join(VERSION_JSON), encode_version(&older, VersionEncoding::Json).unwrap()).unwrap();
+    let packages = root.join("packages");
+    std::fs::create_dir_all(&packages).unwrap();
+    pf::package::pack(&library_path, &packages.join("binary.pflib")).unwrap();
+    catalogue.load_libraries(pf::catalogue::CatalogueConfig::new().without_default_paths().with_path(packages));
+    let graph = &catalogue.libraries[&library_id].graphs[&graph_id];
+    assert_eq!(2, graph.versions);
+    assert_eq!(vec![1, 2], pf::library::get_graph_versions(graph));
+    let latest = catalogue.get_graph_version(&GraphRef { name: String::new(), uuid: graph_id, library: None, version: VersionSpec::Latest }).unwrap();
+    assert_eq!(Some(42), latest.seed);
+
+    let _ = std::fs::remove_dir_all(&root);
+}